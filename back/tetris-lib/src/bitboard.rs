@@ -0,0 +1,201 @@
+use crate::{
+    ActivePiece, Board, EngineError, RotateDirection, RotationSystem, RotationSystemKind,
+    Tetrimino, Tile, piece::PieceShapes, spawn_origin,
+};
+
+/// 줄마다 `u16` 비트마스크로 저장하는 보드
+///
+/// 고정된 칸만 들고 있고 떨어지는 블록은 [`ActivePiece`] 로 따로 다룬다.
+/// 충돌 검사는 칸 4개만 보고, 이동과 회전은 할당 없이 새 [`ActivePiece`] 를 돌려준다.
+/// 봇이나 서버 검증처럼 시뮬레이션을 많이 돌릴 때 쓴다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitBoard {
+    /// 아래 비트가 x = 0
+    rows: Vec<u16>,
+    width: u8,
+    full_row: u16,
+    rotation_system: RotationSystemKind,
+    shapes: PieceShapes,
+}
+
+impl BitBoard {
+    pub const MAX_WIDTH: usize = 16;
+
+    /// 너비가 0 이거나 [`BitBoard::MAX_WIDTH`] 보다 넓으면 [`EngineError::OutOfBounds`]
+    pub fn new(width: usize, height: usize) -> Result<Self, EngineError> {
+        if width == 0 || width > Self::MAX_WIDTH {
+            return Err(EngineError::OutOfBounds);
+        }
+        let rotation_system = RotationSystemKind::default();
+        Ok(Self {
+            rows: vec![0; height],
+            width: width as u8,
+            full_row: ((1u32 << width) - 1) as u16,
+            rotation_system,
+            shapes: PieceShapes::new(&rotation_system),
+        })
+    }
+
+    pub fn with_rotation_system(mut self, rotation_system: RotationSystemKind) -> Self {
+        self.rotation_system = rotation_system;
+        self.shapes = PieceShapes::new(&rotation_system);
+        self
+    }
+
+    pub fn rotation_system(&self) -> RotationSystemKind {
+        self.rotation_system
+    }
+
+    pub fn width(&self) -> usize {
+        self.width as usize
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn rows(&self) -> &[u16] {
+        &self.rows
+    }
+
+    /// 벽, 바닥, 천장 밖도 채워진 것으로 본다
+    pub fn is_filled(&self, x: isize, y: isize) -> bool {
+        let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y)) else {
+            return true;
+        };
+        if x >= self.width() || y >= self.rows.len() {
+            return true;
+        }
+        self.rows[y] & (1 << x) != 0
+    }
+
+    pub fn cells(&self, piece: &ActivePiece) -> [(isize, isize); 4] {
+        self.shapes.cells(piece)
+    }
+
+    pub fn collides(&self, piece: &ActivePiece) -> bool {
+        self.cells(piece)
+            .iter()
+            .any(|(x, y)| self.is_filled(*x, *y))
+    }
+
+    /// 스폰 지점에 놓을 수 없으면 None
+    pub fn spawn(&self, kind: Tetrimino) -> Option<ActivePiece> {
+        let (x, y) = spawn_origin(&self.rotation_system, kind, self.width(), self.height());
+        let (rotation, _) = self.rotation_system.spawn(kind);
+        let piece = ActivePiece {
+            kind,
+            rotation,
+            origin: (x as isize, y as isize),
+        };
+        (!self.collides(&piece)).then_some(piece)
+    }
+
+    pub fn try_move(&self, piece: &ActivePiece, dx: isize, dy: isize) -> Option<ActivePiece> {
+        let moved = ActivePiece {
+            origin: (piece.origin.0 + dx, piece.origin.1 + dy),
+            ..*piece
+        };
+        (!self.collides(&moved)).then_some(moved)
+    }
+
+    /// 회전한 블록과 성공한 월킥 테스트 번호
+    pub fn try_rotate(
+        &self,
+        piece: &ActivePiece,
+        dir: RotateDirection,
+    ) -> Option<(ActivePiece, usize)> {
+        let rotation = piece.rotation.next(&dir);
+        let mut test_idx = 0;
+        while let Some((kx, ky)) =
+            self.rotation_system
+                .wall_kick(piece.kind, piece.rotation, &dir, test_idx)
+        {
+            let rotated = ActivePiece {
+                kind: piece.kind,
+                rotation,
+                origin: (piece.origin.0 + kx as isize, piece.origin.1 + ky as isize),
+            };
+            if !self.collides(&rotated) {
+                return Some((rotated, test_idx));
+            }
+            test_idx += 1;
+        }
+        None
+    }
+
+    /// 바닥에 닿을 때까지 내려갈 수 있는 칸 수
+    pub fn drop_distance(&self, piece: &ActivePiece) -> isize {
+        let mut distance = 0;
+        while self.try_move(piece, 0, distance + 1).is_some() {
+            distance += 1;
+        }
+        distance
+    }
+
+    pub fn hard_drop(&self, piece: &ActivePiece) -> ActivePiece {
+        ActivePiece {
+            origin: (piece.origin.0, piece.origin.1 + self.drop_distance(piece)),
+            ..*piece
+        }
+    }
+
+    /// 블록을 칸에 새긴다, 보드 밖 칸은 버린다
+    pub fn place(&mut self, piece: &ActivePiece) {
+        self.place_cells(&self.cells(piece));
+    }
+
+    pub(crate) fn place_cells(&mut self, cells: &[(isize, isize)]) {
+        for &(x, y) in cells {
+            let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y)) else {
+                continue;
+            };
+            if x < self.width() && y < self.rows.len() {
+                self.rows[y] |= 1 << x;
+            }
+        }
+    }
+
+    /// 꽉 찬 줄을 지우고 지운 줄 수를 돌려준다
+    pub fn clear_lines(&mut self) -> u8 {
+        let mut write = self.rows.len();
+        for read in (0..self.rows.len()).rev() {
+            if self.rows[read] != self.full_row {
+                write -= 1;
+                self.rows[write] = self.rows[read];
+            }
+        }
+        self.rows[..write].fill(0);
+        write as u8
+    }
+
+    /// 고정하고 줄을 지운다
+    pub fn lock(&mut self, piece: &ActivePiece) -> u8 {
+        self.place(piece);
+        self.clear_lines()
+    }
+
+    pub fn is_perfect_clear(&self) -> bool {
+        self.rows.iter().all(|row| *row == 0)
+    }
+}
+
+/// 고정된 칸만 옮긴다, 떨어지는 블록은 [`Board::active_piece`] 로 따로 가져온다
+///
+/// 너비가 0 이거나 [`BitBoard::MAX_WIDTH`] 보다 넓으면 [`EngineError::OutOfBounds`].
+impl TryFrom<&Board> for BitBoard {
+    type Error = EngineError;
+
+    fn try_from(board: &Board) -> Result<Self, Self::Error> {
+        let mut bitboard = BitBoard::new(board.x_len(), board.y_len())?
+            .with_rotation_system(board.rotation_system());
+        for (y, line) in board.board().iter().enumerate() {
+            for (x, tile) in line.iter().enumerate() {
+                if matches!(tile, Tile::Placed(_)) {
+                    bitboard.rows[y] |= 1 << x;
+                }
+            }
+        }
+        Ok(bitboard)
+    }
+}
@@ -0,0 +1,377 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BitBoard, ClearChain, ClearResult, Game, GameInput, LockResult, Placement, SeededRng, TSpin,
+    movegen,
+};
+
+/// 한 단계에서 남겨두는 후보 수
+const BEAM_WIDTH: usize = 8;
+
+/// 보드 평가 가중치, 합이 클수록 좋은 보드
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct BotWeights {
+    /// 위가 막힌 빈 칸 하나마다
+    pub holes: f32,
+    /// 이웃한 열 높이 차이의 합
+    pub bumpiness: f32,
+    /// 모든 열 높이의 합
+    pub aggregate_height: f32,
+    /// 가장 깊은 우물 깊이, 테트리스 자리를 남겨두게 한다
+    pub well_depth: f32,
+    /// T-스핀 자리 하나마다
+    pub tslot: f32,
+    /// 지운 줄 수별 보상
+    pub clear: [f32; 5],
+    /// T-스핀(미니 포함)으로 지운 줄 수별 보상
+    pub tspin_clear: [f32; 4],
+    pub perfect_clear: f32,
+    /// B2B 를 이어갈 때
+    pub b2b: f32,
+    /// 이어지던 B2B 가 끊길 때
+    pub b2b_break: f32,
+}
+
+impl Default for BotWeights {
+    fn default() -> Self {
+        Self {
+            holes: -4.0,
+            bumpiness: -0.4,
+            aggregate_height: -0.5,
+            well_depth: 0.3,
+            tslot: 1.5,
+            clear: [0.0, -1.5, -1.0, -0.5, 8.0],
+            tspin_clear: [0.0, 4.0, 10.0, 12.0],
+            perfect_clear: 20.0,
+            b2b: 2.0,
+            b2b_break: -4.0,
+        }
+    }
+}
+
+impl BotWeights {
+    /// 고정된 칸만 보고 매기는 점수
+    pub fn evaluate(&self, board: &BitBoard) -> f32 {
+        let heights = column_heights(board);
+
+        let mut holes = 0;
+        for (x, height) in heights.iter().enumerate() {
+            let top = board.height() - height;
+            holes += board.rows()[top..]
+                .iter()
+                .filter(|row| **row & (1 << x) == 0)
+                .count();
+        }
+
+        let bumpiness: usize = heights.windows(2).map(|h| h[0].abs_diff(h[1])).sum();
+        let aggregate_height: usize = heights.iter().sum();
+
+        // 벽은 무한히 높은 것으로 본다
+        let well_depth = (0..heights.len())
+            .map(|x| {
+                let left = x.checked_sub(1).map_or(usize::MAX, |l| heights[l]);
+                let right = heights.get(x + 1).copied().unwrap_or(usize::MAX);
+                left.min(right).saturating_sub(heights[x])
+            })
+            .max()
+            .unwrap_or(0)
+            .min(board.height());
+
+        holes as f32 * self.holes
+            + bumpiness as f32 * self.bumpiness
+            + aggregate_height as f32 * self.aggregate_height
+            + well_depth as f32 * self.well_depth
+            + tslot_count(board) as f32 * self.tslot
+    }
+
+    /// 블록 하나를 고정했을 때의 보상, `was_b2b` 는 고정하기 전 B2B 가 살아있었는지
+    pub fn clear_reward(&self, result: &ClearResult, was_b2b: bool) -> f32 {
+        let lines = result.lines as usize;
+        let mut reward = if result.tspin == TSpin::None {
+            self.clear[lines.min(4)]
+        } else {
+            self.tspin_clear[lines.min(3)]
+        };
+        if result.is_perfect_clear {
+            reward += self.perfect_clear;
+        }
+        if result.is_b2b() {
+            reward += self.b2b;
+        } else if was_b2b && result.b2b.is_none() {
+            reward += self.b2b_break;
+        }
+        reward
+    }
+}
+
+/// 봇 세기 설정
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct BotConfig {
+    pub weights: BotWeights,
+    /// 지금 블록 다음으로 넥스트를 몇 개까지 내다볼지
+    pub depth: u8,
+    /// 초당 놓는 블록 수 상한, None 이면 매 tick 놓는다
+    pub pps: Option<f32>,
+    /// 가장 좋은 자리 대신 아무 자리나 고를 확률 0.0..=1.0
+    pub mistake_rate: f32,
+    pub use_hold: bool,
+    pub seed: u64,
+}
+
+impl Default for BotConfig {
+    fn default() -> Self {
+        Self::normal()
+    }
+}
+
+impl BotConfig {
+    pub fn easy() -> Self {
+        Self {
+            weights: BotWeights::default(),
+            depth: 0,
+            pps: Some(0.8),
+            mistake_rate: 0.15,
+            use_hold: false,
+            seed: 0,
+        }
+    }
+
+    pub fn normal() -> Self {
+        Self {
+            weights: BotWeights::default(),
+            depth: 1,
+            pps: Some(1.5),
+            mistake_rate: 0.03,
+            use_hold: true,
+            seed: 0,
+        }
+    }
+
+    pub fn hard() -> Self {
+        Self {
+            weights: BotWeights::default(),
+            depth: 2,
+            pps: Some(3.0),
+            mistake_rate: 0.0,
+            use_hold: true,
+            seed: 0,
+        }
+    }
+}
+
+/// 봇이 고른 수
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct BotMove {
+    /// 먼저 홀드하고 나온 블록을 놓는다
+    pub hold: bool,
+    pub placement: Placement,
+    pub score: f32,
+}
+
+impl BotMove {
+    /// `Game::tick` 에 그대로 넘길 입력
+    pub fn inputs(&self) -> Vec<GameInput> {
+        let hold = self.hold.then_some(GameInput::Hold);
+        hold.into_iter()
+            .chain(self.placement.inputs.iter().copied())
+            .collect()
+    }
+}
+
+/// 탐색 중인 한 갈래
+struct Branch {
+    /// 처음 놓은 자리, `think` 의 후보 번호
+    root: usize,
+    board: BitBoard,
+    chain: ClearChain,
+    reward: f32,
+    score: f32,
+}
+
+/// 이동 생성기와 보드 평가로 자리를 고르는 봇
+#[derive(Debug, Clone)]
+pub struct Bot {
+    config: BotConfig,
+    rng: SeededRng,
+    wait_tick: u32,
+}
+
+impl Bot {
+    pub fn new(config: BotConfig) -> Self {
+        Self {
+            rng: SeededRng::new(config.seed),
+            config,
+            wait_tick: 0,
+        }
+    }
+
+    pub fn config(&self) -> &BotConfig {
+        &self.config
+    }
+
+    /// 한 프레임마다 불러서 나온 입력을 `Game::tick` 에 넘긴다
+    ///
+    /// `pps` 상한이 될 때까지는 빈 입력을 돌려준다.
+    pub fn tick(&mut self, game: &Game) -> Vec<GameInput> {
+        self.wait_tick += 1;
+        if game.is_over() || game.current().is_none() {
+            return vec![];
+        }
+        if let Some(pps) = self.config.pps
+            && (self.wait_tick as f32) < 60.0 / pps
+        {
+            return vec![];
+        }
+        self.wait_tick = 0;
+        self.think(game).map(|m| m.inputs()).unwrap_or_default()
+    }
+
+    /// 지금 떨어지는 블록을 어디에 놓을지 고른다, 놓을 곳이 없거나 [`BitBoard`] 로 옮길 수 없으면 None
+    pub fn think(&mut self, game: &Game) -> Option<BotMove> {
+        let board = BitBoard::try_from(game.board()).ok()?;
+        let chain = ClearChain {
+            combo: game.combo(),
+            b2b: game.b2b(),
+        };
+        let next = game.next().iter().copied().collect::<Vec<_>>();
+
+        // (홀드 여부, 시작 블록, 그 뒤에 올 블록들)
+        let mut roots = vec![];
+        if let Some(active) = game.board().active_piece() {
+            roots.push((false, movegen::placements_from(&board, active), &next[..]));
+        }
+        if self.config.use_hold && game.is_can_hold() {
+            let swapped = match game.hold() {
+                Some(hold) => Some((hold, &next[..])),
+                None => next.split_first().map(|(first, rest)| (*first, rest)),
+            };
+            if let Some((kind, rest)) = swapped
+                && let Some(spawn) = board.spawn(kind)
+            {
+                roots.push((true, movegen::placements_from(&board, spawn), rest));
+            }
+        }
+
+        let mut moves = vec![];
+        let mut beam = vec![];
+        for (hold, placements, rest) in roots {
+            for placement in placements {
+                let branch = self.branch(
+                    moves.len(),
+                    &Branch {
+                        root: 0,
+                        board: board.clone(),
+                        chain: chain.clone(),
+                        reward: 0.0,
+                        score: 0.0,
+                    },
+                    &placement,
+                );
+                moves.push(BotMove {
+                    hold,
+                    placement,
+                    score: branch.score,
+                });
+                beam.push((branch, rest));
+            }
+        }
+        if moves.is_empty() {
+            return None;
+        }
+
+        if self.rng.next_f64() < self.config.mistake_rate as f64 {
+            let idx = self.rng.below(moves.len() as u32) as usize;
+            return Some(moves.swap_remove(idx));
+        }
+
+        // 넥스트를 차례로 놓아보며 모든 갈래 중 `BEAM_WIDTH` 개씩만 남긴다
+        for _ in 0..self.config.depth {
+            beam.sort_by(|(a, _), (b, _)| b.score.total_cmp(&a.score));
+            beam.truncate(BEAM_WIDTH);
+            let mut children = vec![];
+            for (branch, queue) in &beam {
+                let Some((kind, rest)) = queue.split_first() else {
+                    continue;
+                };
+                let Some(spawn) = branch.board.spawn(*kind) else {
+                    continue;
+                };
+                for placement in movegen::placements_from(&branch.board, spawn) {
+                    children.push((self.branch(branch.root, branch, &placement), rest));
+                }
+            }
+            if children.is_empty() {
+                break;
+            }
+            beam = children;
+        }
+        let (best, _) = beam
+            .into_iter()
+            .reduce(|best, b| if b.0.score > best.0.score { b } else { best })?;
+        let mut best_move = moves.swap_remove(best.root);
+        best_move.score = best.score;
+        Some(best_move)
+    }
+
+    fn branch(&self, root: usize, parent: &Branch, placement: &Placement) -> Branch {
+        let weights = &self.config.weights;
+        let mut board = parent.board.clone();
+        let mut chain = parent.chain.clone();
+        let was_b2b = chain.b2b.is_some();
+        let lines = board.lock(&placement.piece);
+        let result = chain.clear(LockResult {
+            kind: placement.piece.kind,
+            tspin: placement.tspin,
+            lines,
+            is_perfect_clear: lines > 0 && board.is_perfect_clear(),
+        });
+        let reward = parent.reward + weights.clear_reward(&result, was_b2b);
+        let score = reward + weights.evaluate(&board);
+        Branch {
+            root,
+            board,
+            chain,
+            reward,
+            score,
+        }
+    }
+}
+
+/// 열마다 맨 위 고정 칸까지의 높이
+fn column_heights(board: &BitBoard) -> Vec<usize> {
+    (0..board.width())
+        .map(|x| {
+            board
+                .rows()
+                .iter()
+                .position(|row| row & (1 << x) != 0)
+                .map_or(0, |top| board.height() - top)
+        })
+        .collect()
+}
+
+/// 아래가 뚫린 T 모양 빈 자리 중 양쪽 아래가 막히고 위 한쪽이 덮인 곳
+fn tslot_count(board: &BitBoard) -> usize {
+    let mut count = 0;
+    for y in 1..board.height() as isize - 1 {
+        for x in 1..board.width() as isize - 1 {
+            let is_empty = [(x - 1, y), (x, y), (x + 1, y), (x, y + 1)]
+                .iter()
+                .all(|(cx, cy)| !board.is_filled(*cx, *cy));
+            if is_empty
+                && board.is_filled(x - 1, y + 1)
+                && board.is_filled(x + 1, y + 1)
+                && (board.is_filled(x - 1, y - 1) || board.is_filled(x + 1, y - 1))
+            {
+                count += 1;
+            }
+        }
+    }
+    count
+}
@@ -0,0 +1,217 @@
+use std::collections::{HashMap, VecDeque, hash_map::Entry};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ActivePiece, BitBoard, Board, GameInput, MoveDirection, RotateDirection, Tetrimino};
+
+/// 피네스에서 세는 키 입력 하나
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub enum FinesseInput {
+    /// 한 칸 이동
+    Tap(MoveDirection),
+    /// 벽이나 블록에 막힐 때까지 꾹 눌러 이동
+    Das(MoveDirection),
+    Rotate(RotateDirection),
+    /// 바닥까지 꾹 눌러 내림
+    SoftDrop,
+}
+
+const FINESSE_INPUTS: [FinesseInput; 8] = [
+    FinesseInput::Das(MoveDirection::Left),
+    FinesseInput::Das(MoveDirection::Right),
+    FinesseInput::Tap(MoveDirection::Left),
+    FinesseInput::Tap(MoveDirection::Right),
+    FinesseInput::Rotate(RotateDirection::Right),
+    FinesseInput::Rotate(RotateDirection::Left),
+    FinesseInput::Rotate(RotateDirection::Half),
+    FinesseInput::SoftDrop,
+];
+
+/// 블록 하나의 피네스 결과, 하드 드롭은 세지 않는다
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct FinesseReport {
+    /// 실제로 고정된 자리
+    pub piece: ActivePiece,
+    pub used: usize,
+    pub optimal: usize,
+    /// 더 누른 입력 수
+    pub faults: usize,
+    pub optimal_inputs: Vec<FinesseInput>,
+}
+
+impl FinesseReport {
+    pub fn is_perfect(&self) -> bool {
+        self.faults == 0
+    }
+}
+
+/// 스폰 지점에서 `inputs` 를 눌러 놓은 자리를 가장 적은 입력과 비교한다
+///
+/// 보드의 떨어지는 블록은 무시한다, 스폰 지점이 막혀 있거나 [`BitBoard`] 로 옮길 수 없으면 None.
+pub fn analyze(board: &Board, kind: Tetrimino, inputs: &[FinesseInput]) -> Option<FinesseReport> {
+    let bitboard = BitBoard::try_from(board).ok()?;
+    let spawn = bitboard.spawn(kind)?;
+    let mut piece = spawn;
+    for input in inputs {
+        if let Some(next) = apply_input(&bitboard, &piece, input) {
+            piece = next;
+        }
+    }
+    report(&bitboard, spawn, &piece, inputs.len())
+}
+
+/// [`GameInput`] 으로 기록된 입력을 피네스 키 입력으로 묶어서 센다
+///
+/// 같은 방향 이동이 두 번 이상 이어지다 막혀서 끝나면 DAS 한 번으로,
+/// 이어진 소프트 드롭은 한 번으로 센다. 하드 드롭과 홀드는 세지 않는다.
+pub fn analyze_game_inputs(
+    board: &Board,
+    kind: Tetrimino,
+    inputs: &[GameInput],
+) -> Option<FinesseReport> {
+    let bitboard = BitBoard::try_from(board).ok()?;
+    let spawn = bitboard.spawn(kind)?;
+    let mut piece = spawn;
+    let mut presses: Vec<FinesseInput> = vec![];
+    // 마지막 탭이 이어진 횟수
+    let mut taps = 0;
+    for input in inputs {
+        let (press, moved) = match input {
+            GameInput::MoveLeft => (
+                FinesseInput::Tap(MoveDirection::Left),
+                bitboard.try_move(&piece, -1, 0),
+            ),
+            GameInput::MoveRight => (
+                FinesseInput::Tap(MoveDirection::Right),
+                bitboard.try_move(&piece, 1, 0),
+            ),
+            GameInput::RotateLeft => rotate_press(&bitboard, &piece, RotateDirection::Left),
+            GameInput::RotateRight => rotate_press(&bitboard, &piece, RotateDirection::Right),
+            GameInput::RotateHalf => rotate_press(&bitboard, &piece, RotateDirection::Half),
+            GameInput::SoftDrop => (FinesseInput::SoftDrop, bitboard.try_move(&piece, 0, 1)),
+            GameInput::HardDrop | GameInput::Hold => continue,
+        };
+        let is_held = presses.last() == Some(&press)
+            && matches!(press, FinesseInput::Tap(_) | FinesseInput::SoftDrop);
+        if !is_held {
+            squash_das(&bitboard, &piece, &mut presses, taps);
+            taps = 0;
+            presses.push(press.clone());
+        }
+        if matches!(press, FinesseInput::Tap(_)) {
+            taps += 1;
+        }
+        if let Some(moved) = moved {
+            piece = moved;
+        }
+    }
+    squash_das(&bitboard, &piece, &mut presses, taps);
+    report(&bitboard, spawn, &piece, presses.len())
+}
+
+fn rotate_press(
+    board: &BitBoard,
+    piece: &ActivePiece,
+    dir: RotateDirection,
+) -> (FinesseInput, Option<ActivePiece>) {
+    let rotated = board.try_rotate(piece, dir.clone()).map(|(p, _)| p);
+    (FinesseInput::Rotate(dir), rotated)
+}
+
+/// 마지막 탭 묶음이 막힐 때까지 간 것이면 DAS 하나로, 아니면 탭 여러 번으로 바꾼다
+fn squash_das(board: &BitBoard, piece: &ActivePiece, presses: &mut Vec<FinesseInput>, taps: usize) {
+    let Some(FinesseInput::Tap(dir)) = presses.last().cloned() else {
+        return;
+    };
+    if taps >= 2 && board.try_move(piece, dir.dx(), 0).is_none() {
+        presses.pop();
+        presses.push(FinesseInput::Das(dir));
+        return;
+    }
+    for _ in 1..taps {
+        presses.push(FinesseInput::Tap(dir));
+    }
+}
+
+fn report(
+    board: &BitBoard,
+    spawn: ActivePiece,
+    piece: &ActivePiece,
+    used: usize,
+) -> Option<FinesseReport> {
+    let piece = board.hard_drop(piece);
+    let optimal_inputs = optimal_inputs(board, spawn, &piece)?;
+    Some(FinesseReport {
+        piece,
+        used,
+        optimal: optimal_inputs.len(),
+        faults: used.saturating_sub(optimal_inputs.len()),
+        optimal_inputs,
+    })
+}
+
+/// `start` 에서 하드 드롭했을 때 `target` 과 같은 칸에 놓이는 가장 짧은 키 입력
+pub fn optimal_inputs(
+    board: &BitBoard,
+    start: ActivePiece,
+    target: &ActivePiece,
+) -> Option<Vec<FinesseInput>> {
+    let goal = sorted_cells(board, target);
+    let mut parents: HashMap<ActivePiece, Option<(ActivePiece, FinesseInput)>> = HashMap::new();
+    let mut queue = VecDeque::new();
+    parents.insert(start, None);
+    queue.push_back(start);
+    while let Some(piece) = queue.pop_front() {
+        if sorted_cells(board, &board.hard_drop(&piece)) == goal {
+            let mut inputs = vec![];
+            let mut piece = &piece;
+            while let Some(Some((parent, input))) = parents.get(piece) {
+                inputs.push(input.clone());
+                piece = parent;
+            }
+            inputs.reverse();
+            return Some(inputs);
+        }
+        for input in FINESSE_INPUTS {
+            let Some(next) = apply_input(board, &piece, &input) else {
+                continue;
+            };
+            if let Entry::Vacant(entry) = parents.entry(next) {
+                entry.insert(Some((piece, input)));
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// 움직이지 못하면 None
+fn apply_input(board: &BitBoard, piece: &ActivePiece, input: &FinesseInput) -> Option<ActivePiece> {
+    match input {
+        FinesseInput::Tap(dir) => board.try_move(piece, dir.dx(), 0),
+        FinesseInput::Das(dir) => {
+            let mut moved = board.try_move(piece, dir.dx(), 0)?;
+            while let Some(next) = board.try_move(&moved, dir.dx(), 0) {
+                moved = next;
+            }
+            Some(moved)
+        }
+        FinesseInput::Rotate(dir) => board
+            .try_rotate(piece, dir.clone())
+            .map(|(rotated, _)| rotated),
+        FinesseInput::SoftDrop => {
+            let dropped = board.hard_drop(piece);
+            (dropped != *piece).then_some(dropped)
+        }
+    }
+}
+
+fn sorted_cells(board: &BitBoard, piece: &ActivePiece) -> [(isize, isize); 4] {
+    let mut cells = board.cells(piece);
+    cells.sort_unstable();
+    cells
+}
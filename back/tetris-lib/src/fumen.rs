@@ -0,0 +1,687 @@
+//! 커뮤니티에서 보드를 주고받을 때 쓰는 fumen(v115) 문자열
+//!
+//! 필드는 가로 10칸, 세로 23칸에 맨 아래 가비지 줄 하나가 더 있다.
+//! fumen 좌표는 아래에서 위로 y 가 커지고, 보드는 아랫줄끼리 맞춘다.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ActivePiece, Board, GARBAGE_TILE, Rotate, Tetrimino, Tile};
+
+const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const WIDTH: usize = 10;
+/// v115 필드 높이, 가비지 줄은 뺀 값
+pub const FUMEN_HEIGHT: usize = 23;
+/// 첫 조각 뒤로는 이 길이마다 `?` 를 넣는다
+const HEAD_LEN: usize = 42;
+const CHUNK_LEN: usize = 47;
+/// 주석 글자는 ' '..='~' 를 0 부터 센다
+const COMMENT_BASE: u32 = 96;
+const MAX_REPEAT: usize = 63;
+
+/// fumen 을 읽거나 만들다 실패한 이유
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub enum FumenError {
+    /// `v115@` 같은 머리가 없다
+    UnsupportedVersion,
+    InvalidData,
+    /// 가로가 10칸이 아니거나 fumen 필드 위로 블록이 있다
+    UnsupportedBoard,
+    /// 블록 모양이 회전 방식과 맞지 않거나 보드와 겹친다
+    InvalidPiece,
+}
+
+impl std::fmt::Display for FumenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FumenError::UnsupportedVersion => write!(f, "Unsupported fumen version"),
+            FumenError::InvalidData => write!(f, "Invalid fumen data"),
+            FumenError::UnsupportedBoard => write!(f, "Board does not fit in a fumen field"),
+            FumenError::InvalidPiece => write!(f, "Invalid fumen piece"),
+        }
+    }
+}
+
+impl std::error::Error for FumenError {}
+
+/// fumen 한 페이지
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct FumenPage {
+    /// 고정된 칸만 쓴다, 떨어지는 블록은 `piece`
+    pub board: Board,
+    pub piece: Option<ActivePiece>,
+    /// 다음 페이지로 넘어갈 때 블록을 고정하고 줄을 지운다
+    pub lock: bool,
+    /// 앞 페이지와 같으면 다시 적지 않는다
+    pub comment: String,
+}
+
+impl FumenPage {
+    pub fn new(board: Board) -> Self {
+        Self {
+            piece: board.active_piece(),
+            board,
+            lock: true,
+            comment: String::new(),
+        }
+    }
+}
+
+impl Board {
+    /// 한 페이지 fumen, 떨어지는 블록이 있으면 페이지 블록으로 넣는다
+    pub fn to_fumen(&self) -> Result<String, FumenError> {
+        encode(&[FumenPage::new(self.clone())])
+    }
+
+    /// 첫 페이지를 가로 10칸 보드로 읽는다, 페이지 블록은 떨어지는 블록이 된다
+    pub fn from_fumen(fumen: &str) -> Result<Self, FumenError> {
+        let page = decode(fumen)?
+            .into_iter()
+            .next()
+            .ok_or(FumenError::InvalidData)?;
+        let mut board = page.board;
+        board.active = page.piece;
+        Ok(board)
+    }
+}
+
+/// 페이지들을 `v115@...` 문자열로 만든다
+pub fn encode(pages: &[FumenPage]) -> Result<String, FumenError> {
+    let mut fields = Vec::with_capacity(pages.len());
+    let mut actions = Vec::with_capacity(pages.len());
+    for page in pages {
+        fields.push(Field::from_board(&page.board, FUMEN_HEIGHT)?);
+        actions.push(match page.piece {
+            Some(piece) => Some(FumenPiece::from_active(&page.board, &piece)?),
+            None => None,
+        });
+    }
+
+    let mut values = Values::default();
+    let mut prev = Field::new(FUMEN_HEIGHT);
+    let mut prev_comment = "";
+    let mut repeat = 0;
+    for (idx, page) in pages.iter().enumerate() {
+        let diffs = fields[idx].diff(&prev);
+        if repeat > 0 {
+            repeat -= 1;
+        } else {
+            values.push_field(&diffs, fields[idx].blocks.len());
+            if diffs.iter().all(|d| *d == 8) {
+                // 필드가 안 바뀐 다음 페이지 수
+                let mut base = fields[idx].after(actions[idx].as_ref(), page.lock);
+                for next in idx + 1..pages.len() {
+                    if repeat == MAX_REPEAT || fields[next] != base {
+                        break;
+                    }
+                    base = fields[next].after(actions[next].as_ref(), pages[next].lock);
+                    repeat += 1;
+                }
+                values.push(repeat as u32, 1);
+            }
+        }
+
+        let is_comment = if idx == 0 {
+            !page.comment.is_empty()
+        } else {
+            page.comment != prev_comment
+        };
+        let (kind, rotation, position) = match &actions[idx] {
+            Some(piece) => (
+                piece.kind as u32,
+                piece.rotation as u32,
+                piece.encode_position() as u32,
+            ),
+            None => (0, 0, 0),
+        };
+        let flags = [!page.lock, is_comment, idx == 0, false, false]
+            .iter()
+            .fold(0, |acc, flag| acc * 2 + u32::from(*flag));
+        let field_blocks = fields[idx].blocks.len() as u32;
+        values.push(
+            ((flags * field_blocks + position) * 4 + rotation) * 8 + kind,
+            3,
+        );
+
+        if is_comment {
+            values.push_comment(&escape(&page.comment));
+        }
+        prev_comment = &page.comment;
+        prev = fields[idx].after(actions[idx].as_ref(), page.lock);
+    }
+
+    let data = values
+        .0
+        .iter()
+        .map(|v| TABLE[*v as usize] as char)
+        .collect::<String>();
+    let mut chunks = vec![&data[..data.len().min(HEAD_LEN)]];
+    let mut rest = &data[data.len().min(HEAD_LEN)..];
+    while !rest.is_empty() {
+        let (chunk, tail) = rest.split_at(rest.len().min(CHUNK_LEN));
+        chunks.push(chunk);
+        rest = tail;
+    }
+    Ok(format!("v115@{}", chunks.join("?")))
+}
+
+/// fumen 문자열을 페이지들로 읽는다, URL 이어도 `v115@` 뒤만 본다
+pub fn decode(fumen: &str) -> Result<Vec<FumenPage>, FumenError> {
+    let (field_height, data) = if let Some((_, data)) = fumen.rsplit_once("115@") {
+        (FUMEN_HEIGHT, data)
+    } else if let Some((_, data)) = fumen.rsplit_once("110@") {
+        (21, data)
+    } else {
+        return Err(FumenError::UnsupportedVersion);
+    };
+    let mut values = Values(
+        data.bytes()
+            .filter(|c| *c != b'?' && !c.is_ascii_whitespace())
+            .map(|c| {
+                TABLE
+                    .iter()
+                    .position(|t| *t == c)
+                    .map(|v| v as u8)
+                    .ok_or(FumenError::InvalidData)
+            })
+            .collect::<Result<_, _>>()?,
+    );
+    values.0.reverse();
+
+    let mut pages = vec![];
+    let mut prev = Field::new(field_height);
+    let mut repeat = 0;
+    let mut comment = String::new();
+    while !values.0.is_empty() {
+        let mut field = prev.clone();
+        if repeat > 0 {
+            repeat -= 1;
+        } else {
+            let mut idx = 0;
+            let mut is_changed = false;
+            while idx < field.blocks.len() {
+                let value = values.poll(2)? as usize;
+                let diff = value / field.blocks.len();
+                let count = value % field.blocks.len() + 1;
+                if idx + count > field.blocks.len() || diff > 16 {
+                    return Err(FumenError::InvalidData);
+                }
+                for block in &mut field.blocks[idx..idx + count] {
+                    *block = (*block as usize + diff)
+                        .checked_sub(8)
+                        .filter(|b| *b <= 8)
+                        .ok_or(FumenError::InvalidData)? as u8;
+                }
+                is_changed |= diff != 8;
+                idx += count;
+            }
+            if !is_changed {
+                repeat = values.poll(1)?;
+            }
+        }
+
+        let mut action = values.poll(3)? as usize;
+        let kind = action % 8;
+        action /= 8;
+        let rotation = action % 4;
+        action /= 4;
+        let position = action % field.blocks.len();
+        // 아래 비트부터 rise, mirror, color, comment, lock(0 이 고정)
+        let flags = action / field.blocks.len();
+        let is_rise = flags & 1 != 0;
+        let is_mirror = flags & 2 != 0;
+        let is_comment = flags & 8 != 0;
+        let is_lock = flags & 16 == 0;
+
+        if is_comment {
+            comment = unescape(&values.poll_comment()?);
+        }
+        let piece = match kind {
+            0 => None,
+            kind => Some(FumenPiece::decode(
+                kind as u8,
+                rotation as u8,
+                position,
+                field_height,
+            )?),
+        };
+
+        let mut board = field.to_board()?;
+        let active = match &piece {
+            Some(piece) => Some(piece.to_active(&board)?),
+            None => None,
+        };
+        board.active = None;
+        pages.push(FumenPage {
+            board,
+            piece: active,
+            lock: is_lock,
+            comment: comment.clone(),
+        });
+
+        prev = field.after(piece.as_ref(), is_lock);
+        if is_lock {
+            if is_rise {
+                prev.rise();
+            }
+            if is_mirror {
+                prev.mirror();
+            }
+        }
+    }
+    Ok(pages)
+}
+
+/// fumen 순서 칸 배열, 위 줄부터 읽고 마지막 10칸이 가비지 줄
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Field {
+    height: usize,
+    blocks: Vec<u8>,
+}
+
+impl Field {
+    fn new(height: usize) -> Self {
+        Self {
+            height,
+            blocks: vec![0; (height + 1) * WIDTH],
+        }
+    }
+
+    /// fumen y 는 가비지 줄이 -1
+    fn index(&self, x: i8, y: i8) -> Option<usize> {
+        let row = self.height as isize - 1 - y as isize;
+        ((0..WIDTH as i8).contains(&x) && (0..=self.height as isize).contains(&row))
+            .then(|| row as usize * WIDTH + x as usize)
+    }
+
+    fn from_board(board: &Board, height: usize) -> Result<Self, FumenError> {
+        if board.x_len() != WIDTH {
+            return Err(FumenError::UnsupportedBoard);
+        }
+        let mut field = Self::new(height);
+        for (y, line) in board.board().iter().enumerate() {
+            let fumen_y = board.y_len() - 1 - y;
+            for (x, tile) in line.iter().enumerate() {
+                let Tile::Placed(placed) = tile else {
+                    continue;
+                };
+                let idx = i8::try_from(fumen_y)
+                    .ok()
+                    .filter(|_| fumen_y < height)
+                    .and_then(|fumen_y| field.index(x as i8, fumen_y))
+                    .ok_or(FumenError::UnsupportedBoard)?;
+                field.blocks[idx] = to_fumen_block(*placed);
+            }
+        }
+        Ok(field)
+    }
+
+    /// 가비지 줄은 버린다
+    fn to_board(&self) -> Result<Board, FumenError> {
+        let mut board = Board::new_common();
+        let y_len = board.y_len();
+        for fumen_y in 0..self.height.min(y_len) {
+            for x in 0..WIDTH {
+                let block = self.blocks[self.index(x as i8, fumen_y as i8).unwrap()];
+                if block != 0 {
+                    board
+                        .set_location(
+                            x,
+                            y_len - 1 - fumen_y,
+                            Tile::Placed(from_fumen_block(block)),
+                        )
+                        .map_err(|_| FumenError::UnsupportedBoard)?;
+                }
+            }
+        }
+        Ok(board)
+    }
+
+    /// 앞 필드와의 차이, 8 이 안 바뀐 칸
+    fn diff(&self, prev: &Field) -> Vec<u8> {
+        self.blocks
+            .iter()
+            .zip(&prev.blocks)
+            .map(|(cur, prev)| cur + 8 - prev)
+            .collect()
+    }
+
+    /// 다음 페이지가 시작할 필드
+    fn after(&self, piece: Option<&FumenPiece>, is_lock: bool) -> Self {
+        let mut field = self.clone();
+        if !is_lock {
+            return field;
+        }
+        if let Some(piece) = piece {
+            for (x, y) in piece.cells() {
+                if let Some(idx) = field.index(x, y) {
+                    field.blocks[idx] = piece.kind;
+                }
+            }
+        }
+        // 가비지 줄은 지우지 않는다
+        let playfield = field.height * WIDTH;
+        let mut rows = field.blocks[..playfield]
+            .chunks(WIDTH)
+            .filter(|row| row.contains(&0))
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+        let mut blocks = vec![0; playfield - rows.len()];
+        blocks.append(&mut rows);
+        field.blocks.splice(..playfield, blocks);
+        field
+    }
+
+    /// 가비지 줄이 필드 맨 아래로 올라온다
+    fn rise(&mut self) {
+        self.blocks.drain(..WIDTH);
+        self.blocks.extend([0; WIDTH]);
+    }
+
+    fn mirror(&mut self) {
+        let playfield = self.height * WIDTH;
+        for row in self.blocks[..playfield].chunks_mut(WIDTH) {
+            row.reverse();
+        }
+    }
+}
+
+/// fumen 블록 번호 I, L, O, Z, T, J, S, 회색
+const FUMEN_KINDS: [Tetrimino; 7] = [
+    Tetrimino::I,
+    Tetrimino::L,
+    Tetrimino::O,
+    Tetrimino::Z,
+    Tetrimino::T,
+    Tetrimino::J,
+    Tetrimino::S,
+];
+
+fn to_fumen_block(placed: u8) -> u8 {
+    FUMEN_KINDS
+        .iter()
+        .position(|kind| *kind as u8 == placed)
+        .map_or(8, |idx| idx as u8 + 1)
+}
+
+fn from_fumen_block(block: u8) -> u8 {
+    match block {
+        1..=7 => FUMEN_KINDS[block as usize - 1] as u8,
+        _ => GARBAGE_TILE,
+    }
+}
+
+/// fumen 식 블록 위치, 회전 중심 기준이고 y 는 위로 커진다
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FumenPiece {
+    /// fumen 블록 번호
+    kind: u8,
+    /// 0 이 180도, 1 오른쪽, 2 스폰, 3 왼쪽
+    rotation: u8,
+    x: i8,
+    y: i8,
+    height: usize,
+}
+
+impl FumenPiece {
+    fn decode(kind: u8, rotation: u8, position: usize, height: usize) -> Result<Self, FumenError> {
+        if kind > 7 {
+            return Err(FumenError::InvalidPiece);
+        }
+        let mut piece = Self {
+            kind,
+            rotation,
+            x: (position % WIDTH) as i8,
+            y: (height as isize - (position / WIDTH) as isize - 1) as i8,
+            height,
+        };
+        let (dx, dy) = piece.position_offset();
+        piece.x -= dx;
+        piece.y -= dy;
+        Ok(piece)
+    }
+
+    fn encode_position(&self) -> usize {
+        let (dx, dy) = self.position_offset();
+        let (x, y) = (self.x + dx, self.y + dy);
+        (self.height as isize - y as isize - 1) as usize * WIDTH + x as usize
+    }
+
+    /// 옛 fumen 이 위치를 적던 기준점과 회전 중심의 차이
+    fn position_offset(&self) -> (i8, i8) {
+        let kind = FUMEN_KINDS.get(self.kind as usize - 1);
+        match (kind, self.rotation) {
+            (Some(Tetrimino::O), 3) => (-1, 1),
+            (Some(Tetrimino::O), 0) => (-1, 0),
+            (Some(Tetrimino::O), 2) => (0, 1),
+            (Some(Tetrimino::I), 0) => (-1, 0),
+            (Some(Tetrimino::I), 3) => (0, 1),
+            (Some(Tetrimino::S), 2) => (0, 1),
+            (Some(Tetrimino::S), 1) => (1, 0),
+            (Some(Tetrimino::Z), 2) => (0, 1),
+            (Some(Tetrimino::Z), 3) => (-1, 0),
+            _ => (0, 0),
+        }
+    }
+
+    fn cells(&self) -> [(i8, i8); 4] {
+        fumen_shape(self.kind, self.rotation).map(|(dx, dy)| (self.x + dx, self.y + dy))
+    }
+
+    /// 칸이 같은 보드 블록으로 바꾼다, 회전 방식마다 모양이 달라서 칸으로 맞춘다
+    fn to_active(&self, board: &Board) -> Result<ActivePiece, FumenError> {
+        let kind = FUMEN_KINDS[self.kind as usize - 1];
+        let y_len = board.y_len() as isize;
+        let target = self
+            .cells()
+            .map(|(x, y)| (x as isize, y_len - 1 - y as isize));
+        let preferred = fumen_to_rotate(self.rotation);
+        let rotations = [Rotate::D0, Rotate::D90, Rotate::D180, Rotate::D270];
+        let piece = board
+            .shapes
+            .piece_at(kind, std::iter::once(preferred).chain(rotations), target)
+            .ok_or(FumenError::InvalidPiece)?;
+        let is_free = board.shapes.cells(&piece).iter().all(|(x, y)| {
+            usize::try_from(*x)
+                .ok()
+                .zip(usize::try_from(*y).ok())
+                .and_then(|(x, y)| board.get(x, y))
+                .is_some_and(|tile| !matches!(tile, Tile::Placed(_)))
+        });
+        if !is_free {
+            return Err(FumenError::InvalidPiece);
+        }
+        Ok(piece)
+    }
+
+    fn from_active(board: &Board, piece: &ActivePiece) -> Result<Self, FumenError> {
+        let kind = to_fumen_block(piece.kind as u8);
+        let y_len = board.y_len() as isize;
+        let mut target = [(0, 0); 4];
+        for (cell, (x, y)) in target.iter_mut().zip(board.shapes.cells(piece)) {
+            *cell = i8::try_from(x)
+                .ok()
+                .zip(i8::try_from(y_len - 1 - y).ok())
+                .ok_or(FumenError::InvalidPiece)?;
+        }
+        let preferred = rotate_to_fumen(piece.rotation);
+        std::iter::once(preferred)
+            .chain([2, 1, 0, 3])
+            .find_map(|rotation| {
+                let (x, y) = translation(fumen_shape(kind, rotation), target)?;
+                let piece = Self {
+                    kind,
+                    rotation,
+                    x,
+                    y,
+                    height: FUMEN_HEIGHT,
+                };
+                let (dx, dy) = piece.position_offset();
+                ((0..WIDTH as i8).contains(&(x + dx))
+                    && (0..FUMEN_HEIGHT as i8).contains(&(y + dy)))
+                .then_some(piece)
+            })
+            .ok_or(FumenError::InvalidPiece)
+    }
+}
+
+/// `shape` 를 옮겨서 `target` 과 칸이 같아지는 이동 거리
+fn translation(shape: [(i8, i8); 4], target: [(i8, i8); 4]) -> Option<(i8, i8)> {
+    let mut shape = shape;
+    let mut target = target;
+    shape.sort_unstable();
+    target.sort_unstable();
+    let (dx, dy) = (target[0].0 - shape[0].0, target[0].1 - shape[0].1);
+    shape
+        .iter()
+        .zip(&target)
+        .all(|((sx, sy), (tx, ty))| sx + dx == *tx && sy + dy == *ty)
+        .then_some((dx, dy))
+}
+
+fn fumen_to_rotate(rotation: u8) -> Rotate {
+    match rotation {
+        0 => Rotate::D180,
+        1 => Rotate::D90,
+        2 => Rotate::D0,
+        _ => Rotate::D270,
+    }
+}
+
+fn rotate_to_fumen(rotation: Rotate) -> u8 {
+    match rotation {
+        Rotate::D180 => 0,
+        Rotate::D90 => 1,
+        Rotate::D0 => 2,
+        Rotate::D270 => 3,
+    }
+}
+
+/// 회전 중심 기준 칸, y 는 위로 커진다
+fn fumen_shape(kind: u8, rotation: u8) -> [(i8, i8); 4] {
+    let spawn = match FUMEN_KINDS[kind as usize - 1] {
+        Tetrimino::I => [(0, 0), (-1, 0), (1, 0), (2, 0)],
+        Tetrimino::L => [(0, 0), (-1, 0), (1, 0), (1, 1)],
+        Tetrimino::O => [(0, 0), (1, 0), (0, 1), (1, 1)],
+        Tetrimino::Z => [(0, 0), (1, 0), (0, 1), (-1, 1)],
+        Tetrimino::T => [(0, 0), (-1, 0), (1, 0), (0, 1)],
+        Tetrimino::J => [(0, 0), (-1, 0), (1, 0), (-1, 1)],
+        Tetrimino::S => [(0, 0), (-1, 0), (0, 1), (1, 1)],
+    };
+    spawn.map(|(x, y)| match rotation {
+        0 => (-x, -y),
+        1 => (y, -x),
+        2 => (x, y),
+        _ => (-y, x),
+    })
+}
+
+/// 64진수 숫자들, 읽을 때는 뒤집어 두고 끝에서 꺼낸다
+#[derive(Default)]
+struct Values(Vec<u8>);
+
+impl Values {
+    fn push(&mut self, mut value: u32, len: usize) {
+        for _ in 0..len {
+            self.0.push((value % 64) as u8);
+            value /= 64;
+        }
+    }
+
+    fn poll(&mut self, len: usize) -> Result<u32, FumenError> {
+        let mut value = 0;
+        for i in 0..len {
+            let digit = self.0.pop().ok_or(FumenError::InvalidData)?;
+            value += digit as u32 * 64u32.pow(i as u32);
+        }
+        Ok(value)
+    }
+
+    fn push_field(&mut self, diffs: &[u8], field_blocks: usize) {
+        let mut idx = 0;
+        while idx < diffs.len() {
+            let diff = diffs[idx];
+            let count = diffs[idx..].iter().take_while(|d| **d == diff).count();
+            self.push((diff as usize * field_blocks + count - 1) as u32, 2);
+            idx += count;
+        }
+    }
+
+    fn push_comment(&mut self, comment: &str) {
+        let bytes = &comment.as_bytes()[..comment.len().min(4095)];
+        self.push(bytes.len() as u32, 2);
+        for chunk in bytes.chunks(4) {
+            let value = chunk
+                .iter()
+                .rev()
+                .fold(0, |acc, c| acc * COMMENT_BASE + (*c as u32 - 32));
+            self.push(value, 5);
+        }
+    }
+
+    fn poll_comment(&mut self) -> Result<String, FumenError> {
+        let len = self.poll(2)? as usize;
+        let mut comment = String::with_capacity(len);
+        for _ in 0..len.div_ceil(4) {
+            let mut value = self.poll(5)?;
+            for _ in 0..4 {
+                if comment.len() == len {
+                    break;
+                }
+                comment.push((value % COMMENT_BASE + 32) as u8 as char);
+                value /= COMMENT_BASE;
+            }
+        }
+        Ok(comment)
+    }
+}
+
+/// 자바스크립트 `escape` 와 같은 결과
+fn escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for unit in text.encode_utf16() {
+        match unit {
+            0x30..=0x39 | 0x41..=0x5A | 0x61..=0x7A => escaped.push(unit as u8 as char),
+            _ if b"@*_+-./".contains(&(unit as u8)) && unit < 0x80 => {
+                escaped.push(unit as u8 as char)
+            }
+            0..=0xFF => escaped.push_str(&format!("%{unit:02X}")),
+            _ => escaped.push_str(&format!("%u{unit:04X}")),
+        }
+    }
+    escaped
+}
+
+/// 자바스크립트 `unescape` 와 같은 결과, 잘못된 `%` 는 그대로 둔다
+fn unescape(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut units = vec![];
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let hex = |from: usize, len: usize| {
+            text.get(from..from + len)
+                .and_then(|h| u16::from_str_radix(h, 16).ok())
+        };
+        if bytes[idx] == b'%' {
+            if bytes.get(idx + 1) == Some(&b'u')
+                && let Some(unit) = hex(idx + 2, 4)
+            {
+                units.push(unit);
+                idx += 6;
+                continue;
+            }
+            if let Some(unit) = hex(idx + 1, 2) {
+                units.push(unit);
+                idx += 3;
+                continue;
+            }
+        }
+        units.push(bytes[idx] as u16);
+        idx += 1;
+    }
+    String::from_utf16_lossy(&units)
+}
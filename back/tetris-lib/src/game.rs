@@ -0,0 +1,548 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Board, EngineError, History, HoldMode, HoldSlot, LockDelay, LockReset, MoveDirection,
+    RotateDirection, Tetrimino, TopOut,
+    randomizer::{PieceRandomizer, Randomizer, RandomizerKind},
+    rotation::RotationSystemKind,
+    scoring::{AttackTable, ClearChain, ClearResult, ScoreTable},
+};
+
+pub const LEVEL_UP_LINE: u32 = 10;
+pub const MAX_LEVEL: u32 = 20;
+
+pub fn level_to_gravity_tick(level: u32) -> u32 {
+    match level {
+        0..=1 => 48,
+        2 => 43,
+        3 => 38,
+        4 => 33,
+        5 => 28,
+        6 => 23,
+        7 => 18,
+        8 => 13,
+        9 => 8,
+        10 => 6,
+        11..=13 => 5,
+        14..=15 => 4,
+        16..=17 => 3,
+        18..=19 => 2,
+        _ => 1, // 20 이상
+    }
+}
+
+/// 지운 줄 수로 정해지는 레벨
+pub fn level_of_lines(start_level: u32, lines: u32) -> u32 {
+    (start_level + lines / LEVEL_UP_LINE).min(MAX_LEVEL)
+}
+
+/// 소프트 드롭 한 칸마다 얻는 점수
+pub const SOFT_DROP_SCORE: u32 = 1;
+
+/// 하드 드롭으로 얻는 점수, `cnt` 는 [`Board::hard_drop`] 이 돌려준 값
+pub fn hard_drop_score(cnt: u32) -> u32 {
+    2 * cnt.saturating_sub(1)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct GameConfig {
+    pub width: usize,
+    pub height: usize,
+    pub start_level: u32,
+    /// 바닥에 닿은 뒤 고정되기까지의 tick
+    pub lock_delay: u32,
+    /// 고정 타이머를 되돌리는 규칙
+    #[serde(default)]
+    pub lock_reset: LockReset,
+    /// 넥스트 미리보기 개수
+    pub next_len: usize,
+    #[serde(default)]
+    pub hold_mode: HoldMode,
+    /// 처음부터 홀드 칸에 들어 있는 블록
+    #[serde(default)]
+    pub initial_hold: Option<Tetrimino>,
+    /// None 이면 `push_next` 로 직접 채운다
+    pub randomizer: Option<RandomizerKind>,
+    pub seed: u64,
+    pub rotation_system: RotationSystemKind,
+    pub score_table: ScoreTable,
+    pub attack_table: AttackTable,
+    /// 되돌릴 수 있는 고정 횟수, 0 이면 기록하지 않는다
+    #[serde(default)]
+    pub history_len: usize,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            width: 10,
+            height: 26,
+            start_level: 1,
+            lock_delay: 30,
+            lock_reset: LockReset::default(),
+            next_len: 5,
+            hold_mode: HoldMode::default(),
+            initial_hold: None,
+            randomizer: Some(RandomizerKind::SevenBag),
+            seed: 0,
+            rotation_system: RotationSystemKind::default(),
+            score_table: ScoreTable::default(),
+            attack_table: AttackTable::default(),
+            history_len: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub enum GameInput {
+    MoveLeft,
+    MoveRight,
+    RotateLeft,
+    RotateRight,
+    RotateHalf,
+    SoftDrop,
+    HardDrop,
+    Hold,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub enum GameEvent {
+    Spawn {
+        spawn: Tetrimino,
+    },
+    Hold {
+        hold: Tetrimino,
+    },
+    Lock {
+        result: ClearResult,
+        score: u32,
+        attack: u32,
+    },
+    LevelUp {
+        level: u32,
+    },
+    GameOver {
+        reason: TopOut,
+    },
+}
+
+/// 블록 하나를 놓기 전 상태, 떨어지는 블록은 없다
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GameSnapshot {
+    board: Board,
+    hold: HoldSlot,
+    next: VecDeque<Tetrimino>,
+    randomizer: Option<PieceRandomizer>,
+    level: u32,
+    lines: u32,
+    score: u32,
+    chain: ClearChain,
+}
+
+/// 중력, 락 딜레이, 홀드, 넥스트, 점수를 한곳에서 처리하는 게임 엔진
+///
+/// `tick` 한번이 한 프레임(1/60초)이다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Game {
+    config: GameConfig,
+    board: Board,
+    current: Option<Tetrimino>,
+    hold: HoldSlot,
+    next: VecDeque<Tetrimino>,
+    randomizer: Option<PieceRandomizer>,
+    level: u32,
+    lines: u32,
+    score: u32,
+    chain: ClearChain,
+    tick: u32,
+    gravity_tick: u32,
+    lock_delay: LockDelay,
+    is_over: bool,
+    history: History<GameSnapshot>,
+    /// 마지막 기록 뒤에 블록을 고정했다, 다음 블록이 나오기 전이면 아직 기록되지 않았다
+    is_locked_since_record: bool,
+}
+
+impl Game {
+    pub fn new(config: GameConfig) -> Self {
+        let mut game = Self {
+            board: Board::new(config.width, config.height)
+                .with_rotation_system(config.rotation_system),
+            level: config.start_level,
+            randomizer: config.randomizer.map(|kind| kind.build(config.seed)),
+            lock_delay: LockDelay::new(config.lock_delay, config.lock_reset),
+            history: History::new(config.history_len),
+            hold: HoldSlot::new(config.hold_mode, config.initial_hold),
+            config,
+            current: None,
+            next: VecDeque::new(),
+            lines: 0,
+            score: 0,
+            chain: ClearChain::default(),
+            tick: 0,
+            gravity_tick: 0,
+            is_over: false,
+            is_locked_since_record: false,
+        };
+        game.fill_next();
+        game
+    }
+
+    /// 미리 만들어둔 보드에서 시작한다, 회전 방식은 설정을 따른다
+    pub fn with_board(mut self, board: Board) -> Self {
+        self.board = board.with_rotation_system(self.config.rotation_system);
+        self
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn current(&self) -> Option<Tetrimino> {
+        self.current
+    }
+
+    pub fn hold(&self) -> Option<Tetrimino> {
+        self.hold.piece()
+    }
+
+    pub fn is_can_hold(&self) -> bool {
+        self.hold.is_can_hold()
+    }
+
+    pub fn next(&self) -> &VecDeque<Tetrimino> {
+        &self.next
+    }
+
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+
+    pub fn lines(&self) -> u32 {
+        self.lines
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    pub fn combo(&self) -> Option<u32> {
+        self.chain.combo
+    }
+
+    pub fn b2b(&self) -> Option<u32> {
+        self.chain.b2b
+    }
+
+    pub fn tick_count(&self) -> u32 {
+        self.tick
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.is_over
+    }
+
+    pub fn push_next(&mut self, tetrimino: Tetrimino) {
+        self.next.push_back(tetrimino);
+    }
+
+    /// 맨 아래에 방해 줄을 넣는다, 줄마다 구멍 x 목록
+    ///
+    /// 더 넣을 자리가 없으면 [`TopOut::GarbageOut`] 으로 게임이 끝난다.
+    pub fn push_garbage(&mut self, lines: &[Vec<usize>]) -> Vec<GameEvent> {
+        let mut events = vec![];
+        if self.is_over {
+            return events;
+        }
+        for holes in lines {
+            if let Err(EngineError::TopOut(reason)) = self.board.push_garbage(holes) {
+                self.game_over(reason, &mut events);
+                break;
+            }
+        }
+        events
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.history.undo_len() > 0
+            || (self.is_locked_since_record && self.history.current().is_some())
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.history.redo_len() > 0
+    }
+
+    /// 마지막으로 놓은 블록을 놓기 전으로 되돌린다, 기록이 없으면 false
+    ///
+    /// 보드, 홀드, 넥스트, 점수가 돌아가고 그 블록이 스폰 지점에 다시 나온다.
+    pub fn undo(&mut self) -> bool {
+        // 넥스트가 비었거나 게임 오버라 다음 블록이 안 나왔으면 마지막 기록이 그 블록을 놓기 전이다
+        if self.is_locked_since_record
+            && let Some(snapshot) = self.history.current().cloned()
+        {
+            self.restore(snapshot);
+            return true;
+        }
+        let Some(snapshot) = self.history.undo().cloned() else {
+            return false;
+        };
+        self.restore(snapshot);
+        true
+    }
+
+    /// 되돌린 블록을 다시 놓은 상태로 간다, 기록이 없으면 false
+    pub fn redo(&mut self) -> bool {
+        let Some(snapshot) = self.history.redo().cloned() else {
+            return false;
+        };
+        self.restore(snapshot);
+        true
+    }
+
+    /// 한 프레임 진행한다. 입력은 순서대로 적용된다.
+    pub fn tick(&mut self, inputs: &[GameInput]) -> Vec<GameEvent> {
+        let mut events = vec![];
+        if self.is_over {
+            return events;
+        }
+        self.tick += 1;
+
+        if self.current.is_none() {
+            self.begin_turn(&mut events);
+        }
+
+        for input in inputs {
+            if self.current.is_none() {
+                break;
+            }
+            self.apply_input(*input, &mut events);
+        }
+
+        if self.current.is_some() {
+            self.gravity_tick += 1;
+            if self.gravity_tick >= level_to_gravity_tick(self.level) {
+                self.gravity_tick = 0;
+                self.step();
+            }
+        }
+
+        if self.current.is_some() && self.lock_delay.tick(&self.board) {
+            self.lock(&mut events);
+        }
+
+        events
+    }
+
+    fn apply_input(&mut self, input: GameInput, events: &mut Vec<GameEvent>) {
+        match input {
+            GameInput::MoveLeft => {
+                if self
+                    .board
+                    .try_move_falling(MoveDirection::Left)
+                    .and_then(|plan| self.board.apply_move_falling(plan))
+                    .is_ok()
+                {
+                    self.lock_delay.on_move(&self.board);
+                }
+            }
+            GameInput::MoveRight => {
+                if self
+                    .board
+                    .try_move_falling(MoveDirection::Right)
+                    .and_then(|plan| self.board.apply_move_falling(plan))
+                    .is_ok()
+                {
+                    self.lock_delay.on_move(&self.board);
+                }
+            }
+            GameInput::RotateLeft => {
+                if self
+                    .board
+                    .try_rotate_falling(RotateDirection::Left)
+                    .and_then(|plan| self.board.apply_rotate_falling(plan))
+                    .is_ok()
+                {
+                    self.lock_delay.on_move(&self.board);
+                }
+            }
+            GameInput::RotateRight => {
+                if self
+                    .board
+                    .try_rotate_falling(RotateDirection::Right)
+                    .and_then(|plan| self.board.apply_rotate_falling(plan))
+                    .is_ok()
+                {
+                    self.lock_delay.on_move(&self.board);
+                }
+            }
+            GameInput::RotateHalf => {
+                if self
+                    .board
+                    .try_rotate_falling(RotateDirection::Half)
+                    .and_then(|plan| self.board.apply_rotate_falling(plan))
+                    .is_ok()
+                {
+                    self.lock_delay.on_move(&self.board);
+                }
+            }
+            GameInput::SoftDrop => {
+                if self.step() {
+                    self.gravity_tick = 0;
+                    self.score += SOFT_DROP_SCORE;
+                }
+            }
+            GameInput::HardDrop => {
+                let cnt = self.board.hard_drop().unwrap_or(0) as u32;
+                self.score += hard_drop_score(cnt);
+                self.lock(events);
+            }
+            GameInput::Hold => self.hold_falling(events),
+        }
+    }
+
+    fn step(&mut self) -> bool {
+        self.board
+            .try_step()
+            .and_then(|plan| self.board.apply_step(plan))
+            .is_ok()
+    }
+
+    fn spawn(&mut self, tetrimino: Tetrimino, events: &mut Vec<GameEvent>) {
+        let Ok(tiles) = self.board.try_spawn_falling(tetrimino) else {
+            self.game_over(TopOut::BlockOut, events);
+            return;
+        };
+        if self.board.is_block_out(&tiles) || self.board.apply_spawn_falling(tiles).is_err() {
+            self.game_over(TopOut::BlockOut, events);
+            return;
+        }
+        self.current = Some(tetrimino);
+        self.gravity_tick = 0;
+        self.lock_delay.spawn(&self.board);
+        events.push(GameEvent::Spawn { spawn: tetrimino });
+    }
+
+    fn fill_next(&mut self) {
+        if let Some(randomizer) = &mut self.randomizer {
+            while self.next.len() < self.config.next_len {
+                self.next.push_back(randomizer.next_tetrimino());
+            }
+        }
+    }
+
+    fn spawn_from_next(&mut self, events: &mut Vec<GameEvent>) {
+        if let Some(next) = self.next.pop_front() {
+            self.fill_next();
+            self.spawn(next, events);
+        }
+    }
+
+    fn hold_falling(&mut self, events: &mut Vec<GameEvent>) {
+        let Some(current) = self.current else {
+            return;
+        };
+        let Ok(swapped) = self.hold.hold(&mut self.board) else {
+            return;
+        };
+        self.current = None;
+        events.push(GameEvent::Hold { hold: current });
+        match swapped {
+            Some(hold) => self.spawn(hold, events),
+            None => self.spawn_from_next(events),
+        }
+    }
+
+    fn lock(&mut self, events: &mut Vec<GameEvent>) {
+        if self.current.take().is_none() {
+            return;
+        }
+        let is_lock_out = self.board.is_lock_out();
+        let Ok(lock) = self.board.lock_falling() else {
+            return;
+        };
+
+        let result = self.chain.clear(lock);
+        let score = self.config.score_table.score(&result, self.level);
+        let attack = self.config.attack_table.attack(&result);
+        self.score += score;
+        self.lines += result.lines as u32;
+        self.hold.on_lock();
+        self.is_locked_since_record = true;
+        self.history.clear_redo();
+        events.push(GameEvent::Lock {
+            result,
+            score,
+            attack,
+        });
+
+        let level = level_of_lines(self.config.start_level, self.lines);
+        if level > self.level {
+            self.level = level;
+            events.push(GameEvent::LevelUp { level });
+        }
+
+        if is_lock_out {
+            self.game_over(TopOut::LockOut, events);
+        }
+        self.begin_turn(events);
+    }
+
+    /// 다음 블록을 꺼내기 전 상태를 기록하고 꺼낸다, 꺼낼 블록이 없으면 기록하지 않는다
+    fn begin_turn(&mut self, events: &mut Vec<GameEvent>) {
+        if self.is_over || self.next.is_empty() {
+            return;
+        }
+        if self.history.is_enabled() {
+            self.history.record(self.snapshot());
+        }
+        self.is_locked_since_record = false;
+        self.spawn_from_next(events);
+    }
+
+    fn snapshot(&self) -> GameSnapshot {
+        GameSnapshot {
+            board: self.board.clone(),
+            hold: self.hold.clone(),
+            next: self.next.clone(),
+            randomizer: self.randomizer.clone(),
+            level: self.level,
+            lines: self.lines,
+            score: self.score,
+            chain: self.chain.clone(),
+        }
+    }
+
+    fn restore(&mut self, snapshot: GameSnapshot) {
+        self.board = snapshot.board;
+        self.current = None;
+        self.hold = snapshot.hold;
+        self.next = snapshot.next;
+        self.randomizer = snapshot.randomizer;
+        self.level = snapshot.level;
+        self.lines = snapshot.lines;
+        self.score = snapshot.score;
+        self.chain = snapshot.chain;
+        self.is_over = false;
+        self.is_locked_since_record = false;
+        // 기록은 이미 되어 있으니 꺼내기만 한다
+        self.spawn_from_next(&mut vec![]);
+    }
+
+    fn game_over(&mut self, reason: TopOut, events: &mut Vec<GameEvent>) {
+        self.is_over = true;
+        self.current = None;
+        events.push(GameEvent::GameOver { reason });
+    }
+}
@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+
+use crate::SeededRng;
+
+/// 방마다 고르는 방해 줄 구멍 규칙
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub enum GarbageMode {
+    /// 공격 하나로 들어온 줄은 구멍이 모두 같다
+    #[default]
+    Clean,
+    /// 공격 안에서도 줄마다 `change_rate` 확률로 구멍이 옮겨간다
+    Messy { change_rate: f32 },
+    /// 줄마다 구멍이 바뀐다
+    Cheese,
+    /// 줄마다 구멍 `holes` 개, 가로 칸 수보다 하나 적게까지만 뚫는다
+    MultiHole { holes: u8 },
+    /// 구멍 없이 꽉 찬 줄
+    Solid,
+}
+
+/// 시드로 방해 줄 구멍을 정한다
+///
+/// 같은 방은 같은 시드를 쓰면 누가 받든 같은 순서로 구멍이 나온다.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GarbageGenerator {
+    mode: GarbageMode,
+    width: usize,
+    rng: SeededRng,
+    /// 마지막 줄의 구멍
+    hole: Option<usize>,
+}
+
+impl GarbageGenerator {
+    /// 같은 시드로 만든 블록 순서와 다른 수열이 나오게 시드에 섞는 값
+    const SEED_SALT: u64 = 0x6A09_E667_F3BC_C909;
+
+    pub fn new(mode: GarbageMode, width: usize, seed: u64) -> Self {
+        Self {
+            mode,
+            width,
+            rng: SeededRng::new(SeededRng::new(seed ^ Self::SEED_SALT).next_u64()),
+            hole: None,
+        }
+    }
+
+    pub fn mode(&self) -> &GarbageMode {
+        &self.mode
+    }
+
+    /// 공격 하나로 들어올 줄마다 구멍 x 목록, 아래 줄이 마지막
+    pub fn attack(&mut self, lines: u8) -> Vec<Vec<usize>> {
+        let mut garbage = Vec::with_capacity(lines as usize);
+        for line in 0..lines {
+            let holes = match self.mode.clone() {
+                GarbageMode::Clean if line == 0 => vec![self.next_hole()],
+                GarbageMode::Messy { .. } if line == 0 => vec![self.next_hole()],
+                GarbageMode::Messy { change_rate } if self.rng.next_f64() < change_rate as f64 => {
+                    vec![self.next_hole()]
+                }
+                GarbageMode::Clean | GarbageMode::Messy { .. } => self.hole.into_iter().collect(),
+                GarbageMode::Cheese => vec![self.next_hole()],
+                GarbageMode::MultiHole { holes } => self.holes(holes as usize),
+                GarbageMode::Solid => vec![],
+            };
+            garbage.push(holes);
+        }
+        garbage
+    }
+
+    /// 앞 줄과 다른 구멍, 한 칸짜리 보드면 그대로
+    fn next_hole(&mut self) -> usize {
+        let hole = match self.hole {
+            Some(prev) if self.width > 1 => {
+                let shift = self.rng.below(self.width as u32 - 1) as usize + 1;
+                (prev + shift) % self.width
+            }
+            _ => self.rng.below(self.width.max(1) as u32) as usize,
+        };
+        self.hole = Some(hole);
+        hole
+    }
+
+    /// 겹치지 않는 구멍 `count` 개를 작은 x 부터
+    fn holes(&mut self, count: usize) -> Vec<usize> {
+        let mut columns = (0..self.width).collect::<Vec<_>>();
+        let count = count.min(self.width.saturating_sub(1));
+        for i in 0..count {
+            let j = i + self.rng.below((self.width - i) as u32) as usize;
+            columns.swap(i, j);
+        }
+        columns.truncate(count);
+        columns.sort_unstable();
+        columns
+    }
+}
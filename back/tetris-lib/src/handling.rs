@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{GameInput, MoveDirection};
+
+/// 키 반복 설정, 단위는 tick
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct HandlingConfig {
+    /// 좌우를 누르고 자동 이동이 시작될 때까지
+    pub das: u32,
+    /// 자동 이동 간격, 0 이면 바로 벽까지
+    pub arr: u32,
+    /// 소프트 드롭이 중력의 몇 배인지, 0 이면 바로 바닥까지
+    pub sdf: u32,
+    /// DAS 가 찬 상태에서 회전하면 자동 이동을 멈추는 tick, 0 이면 멈추지 않는다
+    pub das_cut: u32,
+}
+
+impl Default for HandlingConfig {
+    fn default() -> Self {
+        Self {
+            das: 8,
+            arr: 2,
+            sdf: 20,
+            das_cut: 0,
+        }
+    }
+}
+
+/// 키를 누르고 뗀 것
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub enum HandlingEvent {
+    Press(GameInput),
+    Release(GameInput),
+}
+
+/// 누르고 뗀 키를 tick 별 [`GameInput`] 으로 바꾼다
+///
+/// 클라이언트와 서버가 같은 설정과 같은 이벤트 순서를 쓰면 같은 tick 에 같은 입력이 나온다.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputHandler {
+    config: HandlingConfig,
+    /// 누르고 있는 좌우 방향, 마지막에 누른 것이 뒤
+    held: Vec<MoveDirection>,
+    /// 지금 방향을 누른 뒤 흐른 tick
+    charge: u32,
+    arr_tick: u32,
+    /// 남은 DAS cut tick
+    cut: u32,
+    is_soft_drop: bool,
+    /// 중력 tick 단위로 쌓은 소프트 드롭
+    soft_drop_acc: u32,
+    /// 다음 tick 에 바로 넣을 입력
+    pending: Vec<GameInput>,
+}
+
+impl InputHandler {
+    pub fn new(config: HandlingConfig) -> Self {
+        Self {
+            config,
+            held: vec![],
+            charge: 0,
+            arr_tick: 0,
+            cut: 0,
+            is_soft_drop: false,
+            soft_drop_acc: 0,
+            pending: vec![],
+        }
+    }
+
+    pub fn config(&self) -> &HandlingConfig {
+        &self.config
+    }
+
+    pub fn handle(&mut self, event: HandlingEvent) {
+        match event {
+            HandlingEvent::Press(input) => self.press(input),
+            HandlingEvent::Release(input) => self.release(input),
+        }
+    }
+
+    pub fn press(&mut self, input: GameInput) {
+        match input {
+            GameInput::MoveLeft | GameInput::MoveRight => {
+                let dir = move_direction(input);
+                self.held.retain(|held| *held != dir);
+                self.held.push(dir);
+                self.charge = 0;
+                self.arr_tick = 0;
+                self.cut = 0;
+                self.pending.push(input);
+            }
+            GameInput::SoftDrop => {
+                if !self.is_soft_drop {
+                    self.is_soft_drop = true;
+                    self.soft_drop_acc = 0;
+                    self.pending.push(input);
+                }
+            }
+            GameInput::RotateLeft | GameInput::RotateRight | GameInput::RotateHalf => {
+                if self.is_charged() {
+                    self.cut = self.config.das_cut;
+                }
+                self.pending.push(input);
+            }
+            GameInput::HardDrop | GameInput::Hold => self.pending.push(input),
+        }
+    }
+
+    pub fn release(&mut self, input: GameInput) {
+        match input {
+            GameInput::MoveLeft | GameInput::MoveRight => {
+                let dir = move_direction(input);
+                let was_active = self.held.last() == Some(&dir);
+                self.held.retain(|held| *held != dir);
+                if was_active {
+                    // 남은 방향은 DAS 를 처음부터 다시 채운다
+                    self.charge = 0;
+                    self.arr_tick = 0;
+                    self.cut = 0;
+                }
+            }
+            GameInput::SoftDrop => self.is_soft_drop = false,
+            _ => {}
+        }
+    }
+
+    /// 한 tick 에 넣을 입력, `gravity_tick` 은 지금 레벨에서 한 칸 떨어지는 tick
+    ///
+    /// ARR 이 0 이면 `width` 번, SDF 가 0 이면 `height` 번 넣어서 벽이나 바닥까지 보낸다.
+    pub fn tick(&mut self, gravity_tick: u32, width: usize, height: usize) -> Vec<GameInput> {
+        let mut inputs = std::mem::take(&mut self.pending);
+
+        if let Some(dir) = self.held.last() {
+            let input = match dir {
+                MoveDirection::Left => GameInput::MoveLeft,
+                MoveDirection::Right => GameInput::MoveRight,
+            };
+            if self.cut > 0 {
+                self.cut -= 1;
+            } else {
+                // 계속 누르고 있어도 넘치지 않게 한다
+                self.charge = self.charge.saturating_add(1);
+                if self.charge >= self.config.das {
+                    if self.config.arr == 0 {
+                        inputs.extend(std::iter::repeat_n(input, width));
+                    } else if self.charge == self.config.das {
+                        self.arr_tick = 0;
+                        inputs.push(input);
+                    } else {
+                        self.arr_tick += 1;
+                        if self.arr_tick >= self.config.arr {
+                            self.arr_tick = 0;
+                            inputs.push(input);
+                        }
+                    }
+                }
+            }
+        }
+
+        if self.is_soft_drop {
+            if self.config.sdf == 0 {
+                inputs.extend(std::iter::repeat_n(GameInput::SoftDrop, height));
+            } else {
+                let gravity_tick = gravity_tick.max(1);
+                self.soft_drop_acc += self.config.sdf;
+                let rows = self.soft_drop_acc / gravity_tick;
+                self.soft_drop_acc %= gravity_tick;
+                inputs.extend((0..rows).map(|_| GameInput::SoftDrop));
+            }
+        }
+        inputs
+    }
+
+    fn is_charged(&self) -> bool {
+        !self.held.is_empty() && self.charge >= self.config.das
+    }
+}
+
+fn move_direction(input: GameInput) -> MoveDirection {
+    match input {
+        GameInput::MoveLeft => MoveDirection::Left,
+        _ => MoveDirection::Right,
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+/// 개수 제한이 있는 되돌리기/다시하기 기록
+///
+/// 지금 상태는 [`History::record`] 로 넘긴 마지막 값이고, 되돌리거나 다시 할 때 서로 자리를 바꾼다.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History<T> {
+    limit: usize,
+    current: Option<T>,
+    undo: VecDeque<T>,
+    redo: Vec<T>,
+}
+
+impl<T> History<T> {
+    /// `limit` 이 0 이면 아무것도 기록하지 않는다
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            current: None,
+            undo: VecDeque::new(),
+            redo: vec![],
+        }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.limit > 0
+    }
+
+    pub fn undo_len(&self) -> usize {
+        self.undo.len()
+    }
+
+    pub fn redo_len(&self) -> usize {
+        self.redo.len()
+    }
+
+    /// 마지막으로 기록하거나 되돌린 상태
+    pub fn current(&self) -> Option<&T> {
+        self.current.as_ref()
+    }
+
+    /// 기록 밖에서 상태가 바뀌었을 때 다시하기 기록을 버린다
+    pub fn clear_redo(&mut self) {
+        self.redo.clear();
+    }
+
+    /// 새 상태를 지금 상태로 삼는다, 다시하기 기록은 버린다
+    pub fn record(&mut self, state: T) {
+        if !self.is_enabled() {
+            return;
+        }
+        if let Some(prev) = self.current.replace(state) {
+            self.undo.push_back(prev);
+            if self.undo.len() > self.limit {
+                self.undo.pop_front();
+            }
+        }
+        self.redo.clear();
+    }
+
+    /// 한 단계 전 상태, 없으면 None
+    pub fn undo(&mut self) -> Option<&T> {
+        let prev = self.undo.pop_back()?;
+        if let Some(current) = self.current.replace(prev) {
+            self.redo.push(current);
+        }
+        self.current.as_ref()
+    }
+
+    /// 되돌린 상태를 다시 적용한다, 없으면 None
+    pub fn redo(&mut self) -> Option<&T> {
+        let next = self.redo.pop()?;
+        if let Some(current) = self.current.replace(next) {
+            self.undo.push_back(current);
+        }
+        self.current.as_ref()
+    }
+}
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Board, Tetrimino};
+
+/// 홀드 규칙
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub enum HoldMode {
+    /// 홀드를 쓸 수 없다
+    Disabled,
+    /// 블록 하나를 고정할 때까지 한 번만
+    #[default]
+    Once,
+    /// 횟수 제한 없이
+    Infinite,
+}
+
+/// 홀드할 수 없는 이유
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub enum HoldError {
+    /// 홀드를 쓰지 않는 규칙
+    Disabled,
+    /// 이번 블록에서 이미 홀드했다
+    AlreadyHeld,
+    /// 떨어지는 블록이 없다
+    NoActivePiece,
+}
+
+impl std::fmt::Display for HoldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HoldError::Disabled => write!(f, "Hold is disabled"),
+            HoldError::AlreadyHeld => write!(f, "Already held this piece"),
+            HoldError::NoActivePiece => write!(f, "No falling block"),
+        }
+    }
+}
+
+impl std::error::Error for HoldError {}
+
+/// 홀드 칸과 이번 블록에서 홀드할 수 있는지
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HoldSlot {
+    mode: HoldMode,
+    piece: Option<Tetrimino>,
+    is_can_hold: bool,
+}
+
+impl HoldSlot {
+    /// `initial` 을 홀드 칸에 넣고 시작한다
+    pub fn new(mode: HoldMode, initial: Option<Tetrimino>) -> Self {
+        Self {
+            mode,
+            piece: initial,
+            is_can_hold: true,
+        }
+    }
+
+    pub fn mode(&self) -> HoldMode {
+        self.mode
+    }
+
+    pub fn piece(&self) -> Option<Tetrimino> {
+        self.piece
+    }
+
+    pub fn is_can_hold(&self) -> bool {
+        match self.mode {
+            HoldMode::Disabled => false,
+            HoldMode::Once => self.is_can_hold,
+            HoldMode::Infinite => true,
+        }
+    }
+
+    /// 떨어지는 블록을 홀드 칸과 바꾸고 보드에서 뺀다
+    ///
+    /// 홀드 칸에 있던 블록을 돌려주고, 비어 있었으면 None 이라 넥스트에서 꺼내야 한다.
+    pub fn hold(&mut self, board: &mut Board) -> Result<Option<Tetrimino>, HoldError> {
+        if self.mode == HoldMode::Disabled {
+            return Err(HoldError::Disabled);
+        }
+        if !self.is_can_hold() {
+            return Err(HoldError::AlreadyHeld);
+        }
+        let Some(active) = board.active_piece() else {
+            return Err(HoldError::NoActivePiece);
+        };
+        board.remove_falling_blocks();
+        self.is_can_hold = false;
+        Ok(self.piece.replace(active.kind))
+    }
+
+    /// 블록을 고정한 뒤, 다시 홀드할 수 있다
+    pub fn on_lock(&mut self) {
+        self.is_can_hold = true;
+    }
+}
@@ -1,12 +1,53 @@
 use serde::{Deserialize, Serialize};
 
+use crate::piece::PieceShapes;
+
+pub mod bitboard;
+pub mod bot;
+pub mod finesse;
+pub mod fumen;
+pub mod game;
+pub mod garbage;
+pub mod handling;
+pub mod history;
+pub mod hold;
+pub mod lock_delay;
+pub mod movegen;
+pub mod perfect_clear;
+pub mod piece;
+pub mod piece_set;
+pub mod randomizer;
+pub mod replay;
+pub mod rotation;
+pub mod scoring;
+pub mod spin;
+pub use bitboard::BitBoard;
+pub use bot::{Bot, BotConfig, BotMove, BotWeights};
+pub use finesse::{FinesseInput, FinesseReport};
+pub use fumen::{FumenError, FumenPage};
+pub use game::{Game, GameConfig, GameEvent, GameInput};
+pub use garbage::{GarbageGenerator, GarbageMode};
+pub use handling::{HandlingConfig, HandlingEvent, InputHandler};
+pub use history::History;
+pub use hold::{HoldError, HoldMode, HoldSlot};
+pub use lock_delay::{LockDelay, LockReset};
+pub use movegen::Placement;
+pub use perfect_clear::PerfectClearStep;
+pub use piece::ActivePiece;
+pub use piece_set::{KickTable, PieceDef, PieceSet, PieceSetError, SetBag, SetPiece};
+pub use randomizer::{PieceRandomizer, Randomizer, RandomizerKind, SeededRng};
+pub use replay::{Replay, ReplayAction, ReplayError, ReplayPlayer};
+pub use rotation::{RotationSystem, RotationSystemKind};
+pub use scoring::{AttackTable, ClearChain, ClearResult, ScoreTable};
+pub use spin::TSpin;
+
 #[cfg(test)]
 mod tests;
 
 #[cfg(feature = "wasm")]
 mod wasm;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
 #[cfg_attr(feature = "wasm", ts(export))]
 pub enum MoveDirection {
@@ -23,94 +64,110 @@ impl MoveDirection {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
 #[cfg_attr(feature = "wasm", ts(export))]
 pub enum RotateDirection {
     Left,
     Right,
+    /// 180도
+    Half,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// 보드 조작이 실패한 이유
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
 #[cfg_attr(feature = "wasm", ts(export))]
-pub enum RotateError {
-    OutOfBounds(RotateDirection),
-    Blocked(RotateDirection, Location),
+pub enum EngineError {
+    /// 떨어지는 블록이 없다
+    NoActivePiece,
+    /// 이미 떨어지는 블록이 있다
+    ActivePieceExists,
+    /// 보드 밖 좌표
+    OutOfBounds,
+    /// 이 위치의 블록이 다른 칸에 막혔다
+    Blocked(Location),
+    /// 블록 번호나 계획이 블록 모양과 맞지 않는다
     InvalidShape,
+    /// 더 놓을 수 없어 게임이 끝났다
+    TopOut(TopOut),
 }
 
-impl std::fmt::Display for RotateError {
+impl std::fmt::Display for EngineError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            RotateError::OutOfBounds(dir) => write!(f, "Rotation out of bounds: {:?}", dir),
-            RotateError::Blocked(dir, pos) => write!(f, "Rotation blocked at {:?}: {:?}", pos, dir),
-            RotateError::InvalidShape => write!(f, "Rotation shape is invalid"),
+            EngineError::NoActivePiece => write!(f, "No falling block"),
+            EngineError::ActivePieceExists => write!(f, "Falling block already exists"),
+            EngineError::OutOfBounds => write!(f, "Out of bounds"),
+            EngineError::Blocked(pos) => write!(f, "Falling block is blocked at {:?}", pos),
+            EngineError::InvalidShape => write!(f, "Falling block shape is invalid"),
+            EngineError::TopOut(reason) => write!(f, "Topped out: {}", reason),
         }
     }
 }
 
-impl std::error::Error for RotateError {}
+impl std::error::Error for EngineError {}
 
-#[derive(Debug, Serialize, Deserialize)]
+/// 가이드라인 탑아웃 이유
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
 #[cfg_attr(feature = "wasm", ts(export))]
-pub enum StepError {
-    OutOfBounds,
-    InvalidShape,
-    Blocked(Location),
+pub enum TopOut {
+    /// 스폰 자리에 이미 블록이 있다
+    BlockOut,
+    /// 블록이 전부 보이는 줄 위에서 고정됐다
+    LockOut,
+    /// 방해 줄이 블록을 천장 밖으로 밀어냈다
+    GarbageOut,
 }
 
-impl std::fmt::Display for StepError {
+impl std::fmt::Display for TopOut {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            StepError::OutOfBounds => write!(f, "Falling block reached the bottom"),
-            StepError::InvalidShape => write!(f, "Falling block shape is invalid"),
-            StepError::Blocked(pos) => write!(f, "Falling block is blocked at {:?}", pos),
+            TopOut::BlockOut => write!(f, "Block out"),
+            TopOut::LockOut => write!(f, "Lock out"),
+            TopOut::GarbageOut => write!(f, "Garbage out"),
         }
     }
 }
 
-impl std::error::Error for StepError {}
-
-#[derive(Debug, Serialize, Deserialize)]
-#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
-#[cfg_attr(feature = "wasm", ts(export))]
-pub enum SpawnError {
-    FallingTileExists,
-}
-
-impl std::fmt::Display for SpawnError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Falling tile already exists")
-    }
-}
-
-impl std::error::Error for SpawnError {}
-
-#[derive(Debug, Serialize, Deserialize)]
+/// 텍스트 보드를 읽다 실패한 이유
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
 #[cfg_attr(feature = "wasm", ts(export))]
-pub enum MoveError {
-    OutOfBounds(MoveDirection),
-    Blocked(MoveDirection, Location),
-    InvalidShape,
+pub enum ParseBoardError {
+    /// 칸 줄이 하나도 없다
+    Empty,
+    /// 칸 수가 첫 줄과 다른 줄
+    UnevenLine(usize),
+    /// 모르는 칸 글자
+    InvalidTile { x: usize, y: usize, tile: char },
+    /// 떨어지는 블록 칸이 회전 방식의 모양과 맞지 않는다
+    InvalidFalling,
+    /// 블록 좌표로 나타낼 수 없는 칸
+    OutOfRange { x: usize, y: usize },
 }
 
-impl std::fmt::Display for MoveError {
+impl std::fmt::Display for ParseBoardError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            MoveError::OutOfBounds(dir) => write!(f, "Move out of bounds: {:?}", dir),
-            MoveError::Blocked(dir, pos) => write!(f, "Move blocked at {:?}: {:?}", pos, dir),
-            MoveError::InvalidShape => write!(f, "Move shape is invalid"),
+            ParseBoardError::Empty => write!(f, "Board text has no lines"),
+            ParseBoardError::UnevenLine(y) => write!(f, "Line {y} has a different width"),
+            ParseBoardError::InvalidTile { x, y, tile } => {
+                write!(f, "Invalid tile {tile:?} at ({x}, {y})")
+            }
+            ParseBoardError::InvalidFalling => write!(f, "Falling block shape is invalid"),
+            ParseBoardError::OutOfRange { x, y } => {
+                write!(f, "Tile at ({x}, {y}) is out of coordinate range")
+            }
         }
     }
 }
 
-impl std::error::Error for MoveError {}
+impl std::error::Error for ParseBoardError {}
 
 #[repr(usize)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
 #[cfg_attr(feature = "wasm", ts(export))]
 pub enum Tetrimino {
@@ -143,7 +200,7 @@ impl From<&Tetrimino> for String {
 pub const GARBAGE_TILE: u8 = 8;
 
 #[repr(usize)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
 #[cfg_attr(feature = "wasm", ts(export))]
 pub enum Rotate {
@@ -171,6 +228,18 @@ impl Rotate {
             Self::D270 => Self::D180,
         }
     }
+
+    pub fn next_half(&self) -> Self {
+        self.next_cw().next_cw()
+    }
+
+    pub fn next(&self, dir: &RotateDirection) -> Self {
+        match dir {
+            RotateDirection::Left => self.next_ccw(),
+            RotateDirection::Right => self.next_cw(),
+            RotateDirection::Half => self.next_half(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -218,12 +287,34 @@ pub struct FallingBlockPlan {
     pub to_be: FallingBlockAt,
 }
 
+/// 블록 고정 결과
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct LockResult {
+    pub kind: Tetrimino,
+    pub tspin: TSpin,
+    pub lines: u8,
+    pub is_perfect_clear: bool,
+}
+
+/// 회전 계획, 성공한 월킥 테스트 번호를 같이 들고 있다
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct RotatePlan {
+    pub dir: RotateDirection,
+    pub fallings: Vec<FallingBlockPlan>,
+    pub kick_idx: usize,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
 #[cfg_attr(feature = "wasm", ts(export))]
 pub enum Tile {
     Falling(FallingBlock),
     Placed(u8),
+    /// 고스트, [`Board::view_with_ghost`] 에만 나온다
     Hint(u8),
     Empty,
 }
@@ -245,8 +336,8 @@ impl std::fmt::Display for Tile {
             Tile::Empty => {
                 f.write_str(".")?;
             }
-            Tile::Placed(_) => {
-                f.write_str("P")?;
+            Tile::Placed(placed) => {
+                f.write_str(&format!("{}", placed_letter(*placed)))?;
             }
             Tile::Hint(_) => {
                 f.write_str("H")?;
@@ -256,118 +347,103 @@ impl std::fmt::Display for Tile {
     }
 }
 
-const RIGHT_ROTATE_TABLE: [[[(i8, i8); 4]; 4]; 8] = [
-    // Empty
-    [
-        [(0, 0), (0, 0), (0, 0), (0, 0)],
-        [(0, 0), (0, 0), (0, 0), (0, 0)],
-        [(0, 0), (0, 0), (0, 0), (0, 0)],
-        [(0, 0), (0, 0), (0, 0), (0, 0)],
-    ],
-    // Tetrimino::I
-    [
-        // id 0, id 1, id 2, id 3
-        [(2, -1), (1, 0), (0, 1), (-1, 2)],   // D0 (D0 -> D90)
-        [(1, 2), (0, 1), (-1, 0), (-2, -1)],  // D90 (D90 -> D180)
-        [(-2, 1), (-1, 0), (0, -1), (1, -2)], // D180 (D180 -> D270)
-        [(-1, -2), (0, -1), (1, 0), (2, 1)],  // D270 (D270 -> D0)
-    ],
-    // Tetrimino::O
-    [
-        [(0, 0), (0, 0), (0, 0), (0, 0)],
-        [(0, 0), (0, 0), (0, 0), (0, 0)],
-        [(0, 0), (0, 0), (0, 0), (0, 0)],
-        [(0, 0), (0, 0), (0, 0), (0, 0)],
-    ],
-    // Tetrimino::T
-    [
-        [(1, 1), (1, -1), (0, 0), (-1, 1)],
-        [(-1, 1), (1, 1), (0, 0), (-1, -1)],
-        [(-1, -1), (-1, 1), (0, 0), (1, -1)],
-        [(1, -1), (-1, -1), (0, 0), (1, 1)],
-    ],
-    // Tetrimino::J
-    [
-        [(2, 0), (1, -1), (0, 0), (-1, 1)],
-        [(0, 2), (1, 1), (0, 0), (-1, -1)],
-        [(-2, 0), (-1, 1), (0, 0), (1, -1)],
-        [(0, -2), (-1, -1), (0, 0), (1, 1)],
-    ],
-    // Tetrimino::L
-    [
-        [(0, 2), (1, -1), (0, 0), (-1, 1)],
-        [(-2, 0), (1, 1), (0, 0), (-1, -1)],
-        [(0, -2), (-1, 1), (0, 0), (1, -1)],
-        [(2, 0), (-1, -1), (0, 0), (1, 1)],
-    ],
-    // Tetrimino::S
-    [
-        [(1, 1), (0, 2), (1, -1), (0, 0)],
-        [(-1, 1), (-2, 0), (1, 1), (0, 0)],
-        [(-1, -1), (0, -2), (-1, 1), (0, 0)],
-        [(1, -1), (2, 0), (-1, -1), (0, 0)],
-    ],
-    // Tetrimino::Z
-    [
-        [(2, 0), (1, 1), (0, 0), (-1, 1)],
-        [(0, 2), (-1, 1), (0, 0), (-1, -1)],
-        [(-2, 0), (-1, -1), (0, 0), (1, -1)],
-        [(0, -2), (1, -1), (0, 0), (1, 1)],
-    ],
-];
-
-const SPAWN_TABLE: [[(i8, i8); 4]; 8] = [
-    // Empty
-    [(0, 0), (0, 0), (0, 0), (0, 0)],
-    // Tetrimino::I
-    // 01234
-    [(0, 1), (1, 1), (2, 1), (3, 1)],
-    // Tetrimino::O
-    // 01
-    // 23
-    [(0, 0), (1, 0), (0, 1), (1, 1)],
-    // Tetrimino::T
-    //  0
-    // 123
-    [(0, 0), (-1, 1), (0, 1), (1, 1)],
-    // Tetrimino::J
-    // 0
-    // 123
-    [(0, 0), (0, 1), (1, 1), (2, 1)],
-    // Tetrimino::L
-    //   0
-    // 123
-    [(0, 0), (-2, 1), (-1, 1), (0, 1)],
-    // Tetrimino::S
-    //  01
-    // 23
-    [(0, 0), (1, 0), (-1, 1), (0, 1)],
-    // Tetrimino::Z
-    // 01
-    //  23
-    [(0, 0), (1, 0), (1, 1), (2, 1)],
-];
-
-const WALL_KICK_JLSTZ_TABLE: [[(i8, i8); 5]; 4] = [
-    // test1 ... test5
-    [(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)], // D0  (D0 -> D90)
-    [(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)],   // D90 (D90 -> D180)
-    [(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)],    // D180 (D180 -> D270)
-    [(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)], // D270 (D270 -> D0)
-];
-
-const WALL_KICK_I_TABLE: [[(i8, i8); 5]; 4] = [
-    // test1 ... test5
-    [(0, 0), (-2, 0), (1, 0), (-2, 1), (1, -2)], // D0  (D0 -> D90)
-    [(0, 0), (-1, 0), (2, 0), (-1, -2), (2, 1)], // D90 (D90 -> D180)
-    [(0, 0), (2, 0), (-1, 0), (2, -1), (-1, 2)], // D180 (D180 -> D270)
-    [(0, 0), (1, 0), (-2, 0), (1, 2), (-2, -1)], // D270 (D270 -> D0)
-];
+/// 텍스트 보드의 고정된 칸 글자, 블록 종류는 소문자이고 나머지는 가비지 `g`
+fn placed_letter(placed: u8) -> char {
+    randomizer::TETRIMINOS
+        .iter()
+        .find(|kind| **kind as u8 == placed)
+        .map_or('g', |kind| {
+            String::from(kind).to_ascii_lowercase().remove(0)
+        })
+}
+
+/// 블록 종류 글자, 대소문자는 가리지 않는다
+fn tetrimino_of_letter(letter: char) -> Option<Tetrimino> {
+    randomizer::TETRIMINOS
+        .into_iter()
+        .find(|kind| String::from(kind).eq_ignore_ascii_case(&letter.to_string()))
+}
+
+/// 보이는 줄 수, 보드가 낮으면 버퍼를 뺀 나머지
+const VISIBLE_HEIGHT: usize = 20;
+/// 보이는 줄 위에 숨겨진 버퍼 줄 수의 범위
+const MIN_BUFFER_HEIGHT: usize = 3;
+const MAX_BUFFER_HEIGHT: usize = 6;
+
+/// 블록별 스폰 지점
+///
+/// 스폰 모양을 감싸는 상자가 가운데(홀수면 왼쪽)에 오고, 버퍼 아래에서 3줄 위에 놓인다.
+pub(crate) fn spawn_origin(
+    system: &impl RotationSystem,
+    tetrimino: Tetrimino,
+    width: usize,
+    height: usize,
+) -> (usize, usize) {
+    let (_, cells) = system.spawn(tetrimino);
+    spawn_origin_of(&cells, width, height)
+}
+
+/// 스폰 모양 `cells` 의 스폰 지점, 블록 세트도 같은 규칙을 쓴다
+pub(crate) fn spawn_origin_of(cells: &[(i8, i8)], width: usize, height: usize) -> (usize, usize) {
+    let min_x = cells.iter().map(|(x, _)| *x).min().unwrap_or(0) as isize;
+    let max_x = cells.iter().map(|(x, _)| *x).max().unwrap_or(0) as isize;
+    let bbox_width = (max_x - min_x + 1) as usize;
+    let left = (width.saturating_sub(bbox_width) / 2) as isize;
+    let x = (left - min_x).max(0) as usize;
+    let y = buffer_height(height).saturating_sub(3).max(1);
+    (x, y)
+}
+
+fn buffer_height(height: usize) -> usize {
+    height
+        .saturating_sub(VISIBLE_HEIGHT)
+        .clamp(MIN_BUFFER_HEIGHT, MAX_BUFFER_HEIGHT)
+        .min(height)
+}
 
 #[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
-#[cfg_attr(feature = "wasm", ts(export))]
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Board(Vec<Vec<Tile>>);
+#[cfg_attr(feature = "wasm", ts(export, as = "Vec<Vec<Tile>>"))]
+#[derive(Debug, Clone)]
+pub struct Board {
+    /// 고정된 칸과 힌트만 들고 있다, 떨어지는 블록은 `active` 에 따로 있다
+    tiles: Vec<Vec<Tile>>,
+    active: Option<ActivePiece>,
+    /// 마지막으로 성공한 동작이 회전이면 그때 회전 방향과 월킥 테스트 번호
+    last_rotation: Option<(RotateDirection, usize)>,
+    rotation_system: RotationSystemKind,
+    shapes: PieceShapes,
+}
+
+// 직렬화는 지금처럼 떨어지는 블록을 합친 타일 2차원 배열만 주고받는다
+/// 직렬화한 값과 같이 화면용 칸만 비교한다
+impl PartialEq for Board {
+    fn eq(&self, other: &Self) -> bool {
+        self.view() == other.view()
+    }
+}
+
+impl Eq for Board {}
+
+impl Serialize for Board {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.view().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Board {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let tiles: Vec<Vec<Tile>> = Vec::deserialize(deserializer)?;
+        let mut board = Self::new(tiles.first().map_or(0, |l| l.len()), tiles.len());
+        for (y, line) in tiles.into_iter().enumerate() {
+            for (x, tile) in line.into_iter().enumerate() {
+                board
+                    .set_location(x, y, tile)
+                    .map_err(serde::de::Error::custom)?;
+            }
+        }
+        Ok(board)
+    }
+}
 
 impl std::fmt::Display for Board {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -377,8 +453,14 @@ impl std::fmt::Display for Board {
             f.write_str("_")?;
         }
         f.write_str("\n")?;
+        // `{:#}` 는 고스트를 같이 찍는다
+        let view = if f.alternate() {
+            self.view_with_ghost()
+        } else {
+            self.view()
+        };
         let mut i = 0;
-        for h in &self.0 {
+        for h in &view {
             f.write_str(&format!("{i}"))?;
             i += 1;
             i %= 10;
@@ -396,6 +478,71 @@ impl std::fmt::Display for Board {
     }
 }
 
+/// `Display` 로 찍은 텍스트를 다시 보드로 읽는다
+///
+/// `_` 로 시작하는 줄은 테두리로 보고, 줄 앞 숫자는 건너뛴다.
+/// `.` 은 빈 칸, 대문자는 떨어지는 블록, 소문자는 고정된 블록, `g` 는 가비지다.
+/// 힌트 `H` 는 빈 칸으로 읽는다.
+impl std::str::FromStr for Board {
+    type Err = ParseBoardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('_'))
+            .map(|line| line.trim_start_matches(|c: char| c.is_ascii_digit()))
+            .collect::<Vec<_>>();
+        let width = lines.first().ok_or(ParseBoardError::Empty)?.chars().count();
+        let mut board = Self::new(width, lines.len());
+        let mut falling = vec![];
+        for (y, line) in lines.iter().enumerate() {
+            if line.chars().count() != width {
+                return Err(ParseBoardError::UnevenLine(y));
+            }
+            for (x, tile) in line.chars().enumerate() {
+                board.tiles[y][x] = match tile {
+                    '.' | 'H' => Tile::Empty,
+                    'g' => Tile::Placed(GARBAGE_TILE),
+                    _ => match tetrimino_of_letter(tile) {
+                        Some(kind) if tile.is_ascii_uppercase() => {
+                            let (Ok(cx), Ok(cy)) = (isize::try_from(x), isize::try_from(y)) else {
+                                return Err(ParseBoardError::OutOfRange { x, y });
+                            };
+                            falling.push((kind, (cx, cy)));
+                            Tile::Empty
+                        }
+                        Some(kind) => Tile::Placed(kind as u8),
+                        None => return Err(ParseBoardError::InvalidTile { x, y, tile }),
+                    },
+                };
+            }
+        }
+
+        if let Some((kind, _)) = falling.first().copied() {
+            let cells = <[(isize, isize); 4]>::try_from(
+                falling
+                    .iter()
+                    .filter(|(k, _)| *k == kind)
+                    .map(|(_, cell)| *cell)
+                    .collect::<Vec<_>>(),
+            )
+            .map_err(|_| ParseBoardError::InvalidFalling)?;
+            if falling.len() != cells.len() {
+                return Err(ParseBoardError::InvalidFalling);
+            }
+            let rotations = [Rotate::D0, Rotate::D90, Rotate::D180, Rotate::D270];
+            board.active = Some(
+                board
+                    .shapes
+                    .piece_at(kind, rotations, cells)
+                    .ok_or(ParseBoardError::InvalidFalling)?,
+            );
+        }
+        Ok(board)
+    }
+}
+
 impl Board {
     const HEIGHT: usize = 26;
     const WIDTH: usize = 10;
@@ -405,84 +552,154 @@ impl Board {
     }
 
     pub fn new(width: usize, height: usize) -> Self {
-        Self(vec![vec![Tile::Empty; width]; height])
-    }
-
-    fn right_rotate_wall_kick_reference(
-        kind: &Tetrimino,
-        rotate: &Rotate,
-        test_idx: usize,
-    ) -> (i8, i8) {
-        let rot_idx = *rotate as usize;
-        if *kind == Tetrimino::I {
-            WALL_KICK_I_TABLE[rot_idx][test_idx]
-        } else {
-            WALL_KICK_JLSTZ_TABLE[rot_idx][test_idx]
+        let rotation_system = RotationSystemKind::default();
+        Self {
+            tiles: vec![vec![Tile::Empty; width]; height],
+            active: None,
+            last_rotation: None,
+            rotation_system,
+            shapes: PieceShapes::new(&rotation_system),
         }
     }
 
-    fn left_rotate_wall_kick_reference(
-        kind: &Tetrimino,
-        rotate: &Rotate,
-        test_idx: usize,
-    ) -> (i8, i8) {
-        let next = rotate.next_ccw();
-        let (dx, dy) = Board::right_rotate_wall_kick_reference(kind, &next, test_idx);
-        (-dx, -dy)
+    pub fn with_rotation_system(mut self, rotation_system: RotationSystemKind) -> Self {
+        self.set_rotation_system(rotation_system);
+        self
     }
 
-    fn right_rotate_reference(kind: &Tetrimino, rotate: &Rotate, id: u8) -> (i8, i8) {
-        let kind_idx = *kind as usize;
-        let rot_idx = *rotate as usize;
-        let id_idx = id as usize;
+    pub fn rotation_system(&self) -> RotationSystemKind {
+        self.rotation_system
+    }
 
-        RIGHT_ROTATE_TABLE[kind_idx][rot_idx][id_idx]
+    pub fn set_rotation_system(&mut self, rotation_system: RotationSystemKind) {
+        self.rotation_system = rotation_system;
+        self.shapes = PieceShapes::new(&rotation_system);
     }
 
-    fn left_rotate_reference(kind: &Tetrimino, rotate: &Rotate, id: u8) -> (i8, i8) {
-        let next = rotate.next_ccw();
-        let (dx, dy) = Board::right_rotate_reference(kind, &next, id);
-        (-dx, -dy)
+    pub fn active_piece(&self) -> Option<ActivePiece> {
+        self.active
     }
 
-    pub fn location(&self, x: usize, y: usize) -> &Tile {
-        &self.0[y][x]
+    pub fn get(&self, x: usize, y: usize) -> Option<&Tile> {
+        self.tiles.get(y)?.get(x)
     }
 
-    pub fn location_mut(&mut self, x: usize, y: usize) -> &mut Tile {
-        &mut self.0[y][x]
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut Tile> {
+        self.tiles.get_mut(y)?.get_mut(x)
     }
 
     pub fn x_len(&self) -> usize {
-        self.0[0].len()
+        self.tiles.first().map_or(0, |line| line.len())
     }
     pub fn y_len(&self) -> usize {
-        self.0.len()
+        self.tiles.len()
+    }
+
+    /// 화면에 보이는 줄 수, 표준 보드(26줄)는 20줄
+    pub fn visible_height(&self) -> usize {
+        self.y_len() - self.buffer_height()
     }
 
+    /// 보이는 줄 위에 숨겨진 줄 수, 표준 보드는 6줄
+    pub fn buffer_height(&self) -> usize {
+        buffer_height(self.y_len())
+    }
+
+    /// 떨어지는 블록이 빠진 칸
     pub fn board(&self) -> &Vec<Vec<Tile>> {
-        &self.0
+        &self.tiles
     }
 
-    pub fn line(&self, y: usize) -> &Vec<Tile> {
-        &self.0[y]
+    /// 떨어지는 블록을 예전처럼 `Tile::Falling` 으로 합친 화면용 칸
+    pub fn view(&self) -> Vec<Vec<Tile>> {
+        let mut view = self.tiles.clone();
+        for FallingBlockAt {
+            falling,
+            location: Location { x, y },
+        } in self.get_falling_blocks()
+        {
+            if let Some(tile) = view.get_mut(y).and_then(|line| line.get_mut(x)) {
+                *tile = Tile::Falling(falling);
+            }
+        }
+        view
     }
 
-    pub fn try_spawn_falling(&self, tetrimino: Tetrimino) -> Result<Vec<TileAt>, SpawnError> {
-        let y = if self.y_len() > 20 + 3 {
-            self.y_len() - (20 + 3)
-        } else {
-            1
+    /// 화면용 칸 하나
+    pub fn view_location(&self, x: usize, y: usize) -> Option<Tile> {
+        let tile = self.get(x, y)?;
+        Some(
+            self.get_falling_blocks()
+                .into_iter()
+                .find(|f| f.location == Location::new(x, y))
+                .map_or_else(|| tile.clone(), |f| Tile::Falling(f.falling)),
+        )
+    }
+
+    /// 화면용 칸을 그대로 쓴다
+    ///
+    /// `Tile::Falling` 은 블록 번호로 떨어지는 블록 위치를 되찾고,
+    /// 다른 타일이 떨어지는 블록을 덮으면 떨어지는 블록을 없앤다.
+    /// `Tile::Hint` 는 화면에만 쓰므로 빈 칸으로 둔다.
+    pub fn set_location(&mut self, x: usize, y: usize, tile: Tile) -> Result<(), EngineError> {
+        if self.get(x, y).is_none() {
+            return Err(EngineError::OutOfBounds);
+        }
+        let tile = match tile {
+            Tile::Falling(falling) => {
+                let piece = self
+                    .shapes
+                    .piece_of(&falling, &Location::new(x, y))
+                    .ok_or(EngineError::InvalidShape)?;
+                if !self.is_piece_in_bounds(&piece) {
+                    return Err(EngineError::OutOfBounds);
+                }
+                self.active = Some(piece);
+                Tile::Empty
+            }
+            Tile::Hint(_) => Tile::Empty,
+            tile => {
+                let is_covered = self
+                    .get_falling_blocks()
+                    .iter()
+                    .any(|f| f.location == Location::new(x, y));
+                if is_covered {
+                    self.active = None;
+                }
+                tile
+            }
         };
-        match tetrimino {
-            Tetrimino::I => self.try_spawn_falling_at(tetrimino, 3, y),
-            Tetrimino::O => self.try_spawn_falling_at(tetrimino, 4, y),
-            Tetrimino::T => self.try_spawn_falling_at(tetrimino, 4, y),
-            Tetrimino::J => self.try_spawn_falling_at(tetrimino, 3, y),
-            Tetrimino::L => self.try_spawn_falling_at(tetrimino, 5, y),
-            Tetrimino::S => self.try_spawn_falling_at(tetrimino, 4, y),
-            Tetrimino::Z => self.try_spawn_falling_at(tetrimino, 3, y),
+        if let Some(location) = self.get_mut(x, y) {
+            *location = tile;
         }
+        Ok(())
+    }
+
+    pub fn line(&self, y: usize) -> Option<&Vec<Tile>> {
+        self.tiles.get(y)
+    }
+
+    fn is_piece_in_bounds(&self, piece: &ActivePiece) -> bool {
+        self.shapes.cells(piece).iter().all(|(x, y)| {
+            *x >= 0 && *y >= 0 && (*x as usize) < self.x_len() && (*y as usize) < self.y_len()
+        })
+    }
+
+    /// 떨어지는 블록이 들어갈 수 있는 칸이면 그 위치, `from` 은 막혔을 때 알려줄 원래 위치
+    fn free_location(&self, x: isize, y: isize, from: &Location) -> Result<Location, EngineError> {
+        let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y)) else {
+            return Err(EngineError::OutOfBounds);
+        };
+        match self.get(x, y) {
+            None => Err(EngineError::OutOfBounds),
+            Some(Tile::Empty) => Ok(Location::new(x, y)),
+            Some(_) => Err(EngineError::Blocked(from.clone())),
+        }
+    }
+
+    pub fn try_spawn_falling(&self, tetrimino: Tetrimino) -> Result<Vec<TileAt>, EngineError> {
+        let (x, y) = spawn_origin(&self.rotation_system, tetrimino, self.x_len(), self.y_len());
+        self.try_spawn_falling_at(tetrimino, x, y)
     }
 
     pub fn try_spawn_falling_at(
@@ -490,436 +707,411 @@ impl Board {
         tetrimino: Tetrimino,
         x: usize,
         y: usize,
-    ) -> Result<Vec<TileAt>, SpawnError> {
-        if !self.get_falling_blocks().is_empty() {
-            return Err(SpawnError::FallingTileExists);
+    ) -> Result<Vec<TileAt>, EngineError> {
+        if self.active.is_some() {
+            return Err(EngineError::ActivePieceExists);
         }
+        let (rotation, cells) = self.rotation_system.spawn(tetrimino);
         let falling = FallingBlock {
             kind: tetrimino,
-            rotation: Rotate::D0,
+            rotation,
             id: 0,
         };
-        let ids = vec![
-            Tile::Falling(falling.clone().with_id(0)),
-            Tile::Falling(falling.clone().with_id(1)),
-            Tile::Falling(falling.clone().with_id(2)),
-            Tile::Falling(falling.with_id(3)),
-        ];
-        Ok(ids
+        let cell = |base: usize, d: i8, len: usize| {
+            (base as isize)
+                .checked_add(d as isize)
+                .and_then(|c| usize::try_from(c).ok())
+                .filter(|c| *c < len)
+        };
+        cells
             .into_iter()
             .enumerate()
-            .map(|(idx, tile)| {
-                let (dx, dy) = SPAWN_TABLE[tetrimino as usize][idx];
-                TileAt {
-                    tile: tile,
-                    location: Location::new((x as i8 + dx) as usize, (y as i8 + dy) as usize),
-                }
+            .map(|(idx, (dx, dy))| {
+                let (Some(cx), Some(cy)) = (cell(x, dx, self.x_len()), cell(y, dy, self.y_len()))
+                else {
+                    return Err(EngineError::OutOfBounds);
+                };
+                Ok(TileAt {
+                    tile: Tile::Falling(falling.clone().with_id(idx as u8)),
+                    location: Location::new(cx, cy),
+                })
             })
-            .collect::<Vec<_>>())
+            .collect()
     }
 
-    pub fn apply_spawn_falling(&mut self, tiles: Vec<TileAt>) {
+    pub fn apply_spawn_falling(&mut self, tiles: Vec<TileAt>) -> Result<(), EngineError> {
+        if self.active.is_some() {
+            return Err(EngineError::ActivePieceExists);
+        }
+        if tiles
+            .iter()
+            .any(|t| self.get(t.location.x, t.location.y).is_none())
+        {
+            return Err(EngineError::OutOfBounds);
+        }
         for TileAt {
             tile,
             location: Location { x, y },
         } in tiles
         {
-            *self.location_mut(x, y) = tile;
+            self.set_location(x, y, tile)?;
         }
+        self.last_rotation = None;
+        Ok(())
     }
 
+    /// 떨어지는 블록을 블록 번호 순서로 펼친다
     pub fn get_falling_blocks(&self) -> Vec<FallingBlockAt> {
-        let mut fallings = vec![];
-        'outer: for y in 0..self.y_len() {
-            for x in 0..self.x_len() {
-                let t = self.location(x, y);
-                match t {
-                    Tile::Falling(falling) => {
-                        fallings.push(FallingBlockAt {
-                            location: Location::new(x, y),
-                            falling: falling.clone(),
-                        });
-                        if fallings.len() == 4 {
-                            break 'outer;
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
-
-        fallings
+        self.active
+            .map_or_else(Vec::new, |piece| self.shapes.fallings(&piece))
     }
 
     pub fn remove_falling_blocks(&mut self) {
-        let fallings = self.get_falling_blocks();
-        for FallingBlockAt {
-            location: Location { x, y },
-            ..
-        } in fallings
-        {
-            *self.location_mut(x, y) = Tile::Empty;
-        }
+        self.active = None;
     }
 
-    pub fn try_move_falling(&self, dir: MoveDirection) -> Result<Vec<FallingBlockPlan>, MoveError> {
-        let fallings = self.get_falling_blocks();
-
-        let mut to_move = vec![];
-        for FallingBlockAt {
-            falling,
-            location: Location { x, y },
-        } in fallings
-        {
-            let new_x = x as isize + dir.dx();
-            if new_x < 0 || new_x >= self.x_len() as isize {
-                return Err(MoveError::OutOfBounds(dir));
-            }
-            let new_x = new_x as usize;
-
-            match self.location(new_x, y) {
-                Tile::Falling(FallingBlock { kind, .. }) if *kind == falling.kind => {}
-                Tile::Empty => {}
-                Tile::Hint(_) => {}
-                _ => {
-                    return Err(MoveError::Blocked(dir, Location::new(x, y)));
-                }
-            }
-            to_move.push(FallingBlockPlan {
-                as_is: Location::new(x, y),
-                to_be: FallingBlockAt {
-                    falling,
-                    location: Location { x: new_x, y: y },
-                },
-            });
+    /// 떨어지는 블록 전체를 (dx, dy) 만큼 옮기는 계획
+    fn try_shift_falling(
+        &self,
+        dx: isize,
+        dy: isize,
+    ) -> Result<Vec<FallingBlockPlan>, EngineError> {
+        if self.active.is_none() {
+            return Err(EngineError::NoActivePiece);
         }
+        self.get_falling_blocks()
+            .into_iter()
+            .map(|FallingBlockAt { falling, location }| {
+                let to = self.free_location(
+                    location.x as isize + dx,
+                    location.y as isize + dy,
+                    &location,
+                )?;
+                Ok(FallingBlockPlan {
+                    as_is: location,
+                    to_be: FallingBlockAt {
+                        falling,
+                        location: to,
+                    },
+                })
+            })
+            .collect()
+    }
 
-        if to_move.len() != 4 {
-            return Err(MoveError::InvalidShape);
-        }
+    pub fn try_move_falling(
+        &self,
+        dir: MoveDirection,
+    ) -> Result<Vec<FallingBlockPlan>, EngineError> {
+        self.try_shift_falling(dir.dx(), 0)
+    }
 
-        Ok(to_move)
+    pub fn apply_move_falling(
+        &mut self,
+        fallings: Vec<FallingBlockPlan>,
+    ) -> Result<(), EngineError> {
+        self.apply_falling_plan(&fallings)?;
+        self.last_rotation = None;
+        Ok(())
     }
 
-    pub fn apply_move_falling(&mut self, fallings: Vec<FallingBlockPlan>) {
-        for FallingBlockPlan {
-            as_is: Location { x, y },
-            ..
-        } in &fallings
-        {
-            *self.location_mut(*x, *y) = Tile::Empty;
+    /// 계획의 도착 위치로 떨어지는 블록을 옮긴다
+    ///
+    /// 계획은 클라이언트에서 올 수도 있어서 도착 위치가 비어 있는지 다시 확인한다.
+    fn apply_falling_plan(&mut self, fallings: &[FallingBlockPlan]) -> Result<(), EngineError> {
+        if self.active.is_none() {
+            return Err(EngineError::NoActivePiece);
         }
-        for FallingBlockPlan {
-            to_be:
-                FallingBlockAt {
-                    falling,
-                    location: Location { x, y },
-                },
+        let Some(FallingBlockPlan {
+            to_be: FallingBlockAt { falling, location },
             ..
-        } in fallings
-        {
-            *self.location_mut(x, y) = Tile::Falling(falling);
+        }) = fallings.first()
+        else {
+            return Err(EngineError::InvalidShape);
+        };
+        let piece = self
+            .shapes
+            .piece_of(falling, location)
+            .ok_or(EngineError::InvalidShape)?;
+        for (x, y) in self.shapes.cells(&piece) {
+            self.free_location(x, y, location)?;
         }
+        self.active = Some(piece);
+        Ok(())
     }
 
     fn try_rotate_falling_wall_kick_test(
         &self,
-        fallings: &Vec<FallingBlockAt>,
-        dir: RotateDirection,
+        system: &impl RotationSystem,
+        fallings: &[FallingBlockAt],
+        dir: &RotateDirection,
         wall_kick: (i8, i8),
-    ) -> Result<Vec<Location>, RotateError> {
-        let mut to_rotate = vec![];
-        for FallingBlockAt {
-            falling,
-            location: Location { x, y },
-        } in fallings
-        {
-            let (dx, dy) = match dir {
-                RotateDirection::Left => {
-                    Board::left_rotate_reference(&falling.kind, &falling.rotation, falling.id)
-                }
-                RotateDirection::Right => {
-                    Board::right_rotate_reference(&falling.kind, &falling.rotation, falling.id)
-                }
-            };
-            let new_x = usize::try_from(*x as i8 + dx + wall_kick.0).ok();
-            let new_y = usize::try_from(*y as i8 + dy + wall_kick.1).ok();
-
-            let (Some(new_x), Some(new_y)) = (new_x, new_y) else {
-                return Err(RotateError::OutOfBounds(dir));
-            };
-
-            if new_x >= self.x_len() || new_y >= self.y_len() {
-                return Err(RotateError::OutOfBounds(dir));
-            }
+    ) -> Result<Vec<Location>, EngineError> {
+        fallings
+            .iter()
+            .map(|FallingBlockAt { falling, location }| {
+                let (dx, dy) =
+                    system.rotate_offset(falling.kind, falling.rotation, dir, falling.id);
+                self.free_location(
+                    location.x as isize + (dx + wall_kick.0) as isize,
+                    location.y as isize + (dy + wall_kick.1) as isize,
+                    location,
+                )
+            })
+            .collect()
+    }
 
-            match self.location(new_x, new_y) {
-                Tile::Falling(FallingBlock { kind, .. }) if *kind == falling.kind => {}
-                Tile::Empty => {}
-                Tile::Hint(_) => {}
-                _ => return Err(RotateError::Blocked(dir, Location::new(*x, *y))),
-            }
-            to_rotate.push(Location { x: new_x, y: new_y });
-        }
-        Ok(to_rotate)
+    pub fn try_rotate_falling(&self, dir: RotateDirection) -> Result<RotatePlan, EngineError> {
+        self.try_rotate_falling_with(&self.rotation_system, dir)
     }
 
-    pub fn try_rotate_falling(
+    /// 보드에 설정된 것 대신 주어진 회전 방식으로 회전한다
+    pub fn try_rotate_falling_with(
         &self,
+        system: &impl RotationSystem,
         dir: RotateDirection,
-    ) -> Result<Vec<FallingBlockPlan>, RotateError> {
+    ) -> Result<RotatePlan, EngineError> {
+        let Some(ActivePiece { kind, rotation, .. }) = self.active else {
+            return Err(EngineError::NoActivePiece);
+        };
         let fallings = self.get_falling_blocks();
         let mut test_idx = 0;
-        let wall_kick_tested_location = loop {
-            let wall_kick = match dir {
-                RotateDirection::Left => Board::left_rotate_wall_kick_reference(
-                    &fallings[0].falling.kind,
-                    &fallings[0].falling.rotation,
-                    test_idx,
-                ),
-                RotateDirection::Right => Board::right_rotate_wall_kick_reference(
-                    &fallings[0].falling.kind,
-                    &fallings[0].falling.rotation,
-                    test_idx,
-                ),
+        let mut last_err = EngineError::InvalidShape;
+        let (wall_kick_tested_location, kick_idx) = loop {
+            let Some(wall_kick) = system.wall_kick(kind, rotation, &dir, test_idx) else {
+                break Err(last_err);
             };
-            match self.try_rotate_falling_wall_kick_test(&fallings, dir.clone(), wall_kick) {
-                Ok(l) => break Ok(l),
-                Err(e) => {
-                    if test_idx >= 4 {
-                        break Err(e);
-                    }
-                }
+            match self.try_rotate_falling_wall_kick_test(system, &fallings, &dir, wall_kick) {
+                Ok(l) => break Ok((l, test_idx)),
+                Err(e) => last_err = e,
             }
             test_idx += 1;
         }?;
 
-        let mut to_rotate = vec![];
-        for (
-            idx,
-            FallingBlockAt {
-                mut falling,
-                location,
-            },
-        ) in fallings.into_iter().enumerate()
-        {
-            match dir {
-                RotateDirection::Left => falling.rotation = falling.rotation.next_ccw(),
-                RotateDirection::Right => falling.rotation = falling.rotation.next_cw(),
-            }
-            let data = &wall_kick_tested_location[idx];
-            to_rotate.push(FallingBlockPlan {
-                as_is: location,
-                to_be: FallingBlockAt {
-                    falling,
-                    location: data.clone(),
+        let to_rotate = fallings
+            .into_iter()
+            .zip(wall_kick_tested_location)
+            .map(
+                |(
+                    FallingBlockAt {
+                        mut falling,
+                        location,
+                    },
+                    to_be,
+                )| {
+                    falling.rotation = falling.rotation.next(&dir);
+                    FallingBlockPlan {
+                        as_is: location,
+                        to_be: FallingBlockAt {
+                            falling,
+                            location: to_be,
+                        },
+                    }
                 },
-            });
-        }
-
-        if to_rotate.len() != 4 {
-            return Err(RotateError::InvalidShape);
-        }
-
-        Ok(to_rotate)
+            )
+            .collect();
+
+        Ok(RotatePlan {
+            dir,
+            fallings: to_rotate,
+            kick_idx,
+        })
     }
 
-    pub fn apply_rotate_falling(&mut self, fallings: Vec<FallingBlockPlan>) {
-        for FallingBlockPlan {
-            as_is: Location { x, y },
-            ..
-        } in &fallings
-        {
-            *self.location_mut(*x, *y) = Tile::Empty;
-        }
-        for FallingBlockPlan {
-            to_be:
-                FallingBlockAt {
-                    falling,
-                    location: Location { x, y },
-                },
-            ..
-        } in fallings
-        {
-            *self.location_mut(x, y) = Tile::Falling(falling);
-        }
+    pub fn apply_rotate_falling(&mut self, plan: RotatePlan) -> Result<(), EngineError> {
+        let RotatePlan {
+            dir,
+            fallings,
+            kick_idx,
+        } = plan;
+        self.apply_falling_plan(&fallings)?;
+        self.last_rotation = Some((dir, kick_idx));
+        Ok(())
     }
 
     // ok 면 다음으로 이동할 위치
-    pub fn try_step(&self) -> Result<Vec<FallingBlockPlan>, StepError> {
-        let fallings = self.get_falling_blocks();
-        let mut to_step = vec![];
-        for FallingBlockAt {
-            falling,
-            location: Location { x, y },
-        } in fallings
-        {
-            // 바닥에 닿은경우
-            if y + 1 >= self.y_len() {
-                return Err(StepError::OutOfBounds);
-            }
-
-            match self.location(x, y + 1) {
-                Tile::Falling(FallingBlock { kind, .. }) if *kind == falling.kind => {}
-                Tile::Empty => {}
-                Tile::Hint(_) => {}
-                _ => return Err(StepError::Blocked(Location::new(x, y))),
-            }
-            to_step.push(FallingBlockPlan {
-                as_is: Location::new(x, y),
-                to_be: FallingBlockAt {
-                    falling,
-                    location: Location::new(x, y + 1),
-                },
-            });
-        }
-        if to_step.len() != 4 {
-            return Err(StepError::InvalidShape);
-        }
-
-        Ok(to_step)
+    pub fn try_step(&self) -> Result<Vec<FallingBlockPlan>, EngineError> {
+        self.try_shift_falling(0, 1)
     }
 
-    pub fn apply_step(&mut self, fallings: Vec<FallingBlockPlan>) {
-        for FallingBlockPlan {
-            as_is: Location { x, y },
-            ..
-        } in &fallings
-        {
-            *self.location_mut(*x, *y) = Tile::Empty;
-        }
-        for FallingBlockPlan {
-            to_be:
-                FallingBlockAt {
-                    falling,
-                    location: Location { x, y },
-                },
-            ..
-        } in fallings
-        {
-            *self.location_mut(x, y) = Tile::Falling(falling);
-        }
+    pub fn apply_step(&mut self, fallings: Vec<FallingBlockPlan>) -> Result<(), EngineError> {
+        self.apply_falling_plan(&fallings)?;
+        self.last_rotation = None;
+        Ok(())
     }
 
-    pub fn place_falling(&mut self) {
+    pub fn place_falling(&mut self) -> Result<(), EngineError> {
         let fallings = self.get_falling_blocks();
+        if fallings.is_empty() {
+            return Err(EngineError::NoActivePiece);
+        }
         for FallingBlockAt {
             falling,
             location: Location { x, y },
         } in fallings
         {
-            *self.location_mut(x, y) = Tile::Placed(falling.kind as u8);
+            if let Some(tile) = self.get_mut(x, y) {
+                *tile = Tile::Placed(falling.kind as u8);
+            }
         }
+        self.active = None;
+        self.last_rotation = None;
+        Ok(())
     }
 
     pub fn try_line_clear(&self) -> Vec<usize> {
-        let mut to_clear_lines = vec![];
-        for y in 0..self.y_len() {
-            let mut placed_cnt = 0;
-            for x in 0..self.x_len() {
-                match self.location(x, y) {
-                    Tile::Placed(_) => {
-                        placed_cnt += 1;
-                    }
-                    _ => {}
-                }
-            }
-            if placed_cnt == self.x_len() {
-                to_clear_lines.push(y);
-            }
-        }
-        to_clear_lines
+        self.tiles
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.iter().all(|t| matches!(t, Tile::Placed(_))))
+            .map(|(y, _)| y)
+            .collect()
     }
 
-    pub fn apply_line_clear(&mut self, to_clear_lines: Vec<usize>) {
+    pub fn apply_line_clear(&mut self, mut to_clear_lines: Vec<usize>) -> Result<(), EngineError> {
+        if to_clear_lines.iter().any(|y| *y >= self.y_len()) {
+            return Err(EngineError::OutOfBounds);
+        }
+        to_clear_lines.sort_unstable();
+        to_clear_lines.dedup();
         for to_clear_line in to_clear_lines.iter().rev() {
-            self.0.remove(*to_clear_line);
+            self.tiles.remove(*to_clear_line);
         }
         for _ in to_clear_lines {
-            self.0.insert(0, vec![Tile::Empty; self.x_len()]);
+            self.tiles.insert(0, vec![Tile::Empty; self.x_len()]);
         }
+        Ok(())
     }
 
-    pub fn hard_drop(&mut self) -> u8 {
+    /// 바닥까지 내리고 내려간 칸 수 + 1 을 돌려준다
+    pub fn hard_drop(&mut self) -> Result<u8, EngineError> {
+        if self.active.is_none() {
+            return Err(EngineError::NoActivePiece);
+        }
         let mut cnt = 0;
         loop {
             cnt += 1;
             match self.try_step() {
-                Ok(plan) => self.apply_step(plan),
-                Err(_) => return cnt,
+                Ok(plan) => self.apply_step(plan)?,
+                Err(_) => return Ok(cnt),
             }
         }
     }
 
-    pub fn show_falling_hint(&mut self) {
-        let fallings = self.get_falling_blocks();
-        let _ = self.hard_drop();
-        let targets = self.get_falling_blocks();
-        for FallingBlockAt {
-            falling,
-            location: Location { x, y },
-        } in targets
-        {
-            *self.location_mut(x, y) = Tile::Hint(falling.kind as u8);
+    /// T-스핀 판정, 고정, 줄 삭제를 한번에 한다
+    pub fn lock_falling(&mut self) -> Result<LockResult, EngineError> {
+        let Some(ActivePiece { kind, .. }) = self.active else {
+            return Err(EngineError::NoActivePiece);
+        };
+        let tspin = self.detect_tspin();
+        self.place_falling()?;
+        let clear = self.try_line_clear();
+        let lines = clear.len() as u8;
+        self.apply_line_clear(clear)?;
+        Ok(LockResult {
+            kind,
+            tspin,
+            lines,
+            is_perfect_clear: lines > 0 && self.is_perfect_clear(),
+        })
+    }
+
+    /// 고정된 블록이 하나도 없는지
+    pub fn is_perfect_clear(&self) -> bool {
+        self.tiles
+            .iter()
+            .flatten()
+            .all(|t| !matches!(t, Tile::Placed(_)))
+    }
+
+    /// 떨어지는 블록을 바닥까지 내렸을 때의 칸, 보드는 바꾸지 않는다
+    pub fn ghost_position(&self) -> Option<[Location; 4]> {
+        let piece = self.active?;
+        let cells = self.shapes.cells(&piece);
+        let is_free = |dy: isize| {
+            cells.iter().all(|(x, y)| {
+                let (Ok(x), Ok(y)) = (usize::try_from(*x), usize::try_from(y + dy)) else {
+                    return false;
+                };
+                self.get(x, y) == Some(&Tile::Empty)
+            })
+        };
+        if !is_free(0) {
+            return None;
         }
-        for FallingBlockAt {
-            falling,
-            location: Location { x, y },
-        } in fallings
-        {
-            *self.location_mut(x, y) = Tile::Falling(falling);
+        let mut dy = 0;
+        while is_free(dy + 1) {
+            dy += 1;
         }
+        Some(cells.map(|(x, y)| Location::new(x as usize, (y + dy) as usize)))
     }
 
-    pub fn remove_falling_hint(&mut self) {
-        for y in 0..self.y_len() {
-            for x in 0..self.x_len() {
-                match self.location(x, y) {
-                    Tile::Hint(_) => *self.location_mut(x, y) = Tile::Empty,
-                    _ => {}
+    /// [`Board::view`] 의 빈 칸에 고스트를 `Tile::Hint` 로 겹친 화면용 칸
+    pub fn view_with_ghost(&self) -> Vec<Vec<Tile>> {
+        let mut view = self.view();
+        if let (Some(piece), Some(ghost)) = (self.active, self.ghost_position()) {
+            for Location { x, y } in ghost {
+                let tile = &mut view[y][x];
+                if *tile == Tile::Empty {
+                    *tile = Tile::Hint(piece.kind as u8);
                 }
             }
         }
+        view
+    }
+
+    /// 스폰할 칸에 이미 블록이 있는지
+    pub fn is_block_out(&self, tiles: &[TileAt]) -> bool {
+        tiles
+            .iter()
+            .any(|t| self.get(t.location.x, t.location.y) != Some(&Tile::Empty))
+    }
+
+    /// 지금 떨어지는 블록을 고정하면 전부 보이는 줄 위에 남는지
+    pub fn is_lock_out(&self) -> bool {
+        let fallings = self.get_falling_blocks();
+        !fallings.is_empty() && fallings.iter().all(|f| f.location.y < self.buffer_height())
     }
 
     pub fn has_placed_above(&self, y: usize) -> bool {
-        for y in 0..y {
-            for x in 0..self.x_len() {
-                match self.location(x, y) {
-                    Tile::Placed(_) => return true,
-                    _ => {}
-                }
-            }
-        }
-        return false;
+        self.tiles
+            .iter()
+            .take(y)
+            .flatten()
+            .any(|t| matches!(t, Tile::Placed(_)))
     }
 
-    pub fn push_garbage_line(&mut self, empty_x: usize) -> bool {
-        let mut garbage_blocks = vec![Tile::Placed(GARBAGE_TILE); 10];
-        garbage_blocks[empty_x] = Tile::Empty;
+    pub fn push_garbage_line(&mut self, empty_x: usize) -> Result<(), EngineError> {
+        self.push_garbage(&[empty_x])
+    }
 
-        let fallings: Vec<FallingBlockAt> = self.get_falling_blocks();
+    /// `holes` 만 비운 방해 줄을 맨 아래에 넣는다, 비어 있으면 꽉 찬 줄
+    ///
+    /// 맨 윗줄에 고정된 블록이 있으면 넣지 않고 [`TopOut::GarbageOut`] 을 돌려준다.
+    pub fn push_garbage(&mut self, holes: &[usize]) -> Result<(), EngineError> {
+        if holes.iter().any(|x| *x >= self.x_len()) {
+            return Err(EngineError::OutOfBounds);
+        }
+        let mut garbage_blocks = vec![Tile::Placed(GARBAGE_TILE); self.x_len()];
+        for x in holes {
+            garbage_blocks[*x] = Tile::Empty;
+        }
 
-        for ceil_tile in self.line(0) {
-            if matches!(ceil_tile, Tile::Falling(_)) {
-                // 천장에 닿은경우
-                return false;
-            }
+        if self.has_placed_above(1) {
+            return Err(EngineError::TopOut(TopOut::GarbageOut));
         }
 
-        for FallingBlockAt { location, .. } in &fallings {
-            *self.location_mut(location.x, location.y) = Tile::Empty;
+        let fallings: Vec<FallingBlockAt> = self.get_falling_blocks();
+
+        if let Some(ceil) = fallings.iter().find(|f| f.location.y == 0) {
+            // 천장에 닿은경우
+            return Err(EngineError::Blocked(ceil.location.clone()));
         }
 
         let mut is_falling_collide = false;
         for FallingBlockAt { location, .. } in &fallings {
             // 밑에 있는지 체크
-            if location.y + 1 >= self.y_len() {
-                is_falling_collide = true;
-                break;
-            }
-            match self.location(location.x, location.y + 1) {
-                Tile::Placed(_) => {
+            match self.get(location.x, location.y + 1) {
+                None | Some(Tile::Placed(_)) => {
                     is_falling_collide = true;
                     break;
                 }
@@ -927,19 +1119,13 @@ impl Board {
             };
         }
 
-        self.0.remove(0);
-        self.0.push(garbage_blocks);
+        self.tiles.remove(0);
+        self.tiles.push(garbage_blocks);
 
-        if is_falling_collide {
-            for FallingBlockAt { location, falling } in fallings {
-                *self.location_mut(location.x, location.y - 1) = Tile::Falling(falling);
-            }
-        } else {
-            for FallingBlockAt { location, falling } in fallings {
-                *self.location_mut(location.x, location.y) = Tile::Falling(falling);
-            }
+        if is_falling_collide && let Some(active) = &mut self.active {
+            active.origin.1 -= 1;
         }
 
-        true
+        Ok(())
     }
 }
@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Board;
+
+/// 바닥에 닿은 블록의 고정 타이머를 언제 되돌리는지
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub enum LockReset {
+    /// 이동, 회전할 때마다 끝없이 되돌린다
+    Infinity,
+    /// 이동, 회전으로 `limit` 번까지 되돌리고, 다 쓰면 바닥에 닿자마자 고정한다
+    ///
+    /// 가장 낮은 줄을 새로 내려가면 횟수가 다시 찬다.
+    MoveReset { limit: u32 },
+    /// 가장 낮은 줄을 새로 내려갈 때만 되돌린다
+    StepReset,
+}
+
+impl Default for LockReset {
+    fn default() -> Self {
+        Self::MoveReset { limit: 15 }
+    }
+}
+
+/// 가이드라인 락 딜레이
+///
+/// 서버와 클라이언트가 같은 보드에 같은 순서로 부르면 같은 tick 에 고정된다.
+/// 블록이 나오면 [`LockDelay::spawn`], 이동이나 회전에 성공하면 [`LockDelay::on_move`],
+/// 중력까지 적용한 뒤 tick 마다 [`LockDelay::tick`] 을 부른다.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct LockDelay {
+    /// 바닥에 닿은 뒤 고정되기까지의 tick
+    delay: u32,
+    reset: LockReset,
+    tick: u32,
+    resets: u32,
+    /// 지금 블록이 내려간 가장 낮은 줄, 블록 아래쪽 칸 기준
+    lowest: Option<usize>,
+}
+
+impl LockDelay {
+    pub fn new(delay: u32, reset: LockReset) -> Self {
+        Self {
+            delay,
+            reset,
+            tick: 0,
+            resets: 0,
+            lowest: None,
+        }
+    }
+
+    pub fn delay(&self) -> u32 {
+        self.delay
+    }
+
+    pub fn reset(&self) -> LockReset {
+        self.reset
+    }
+
+    /// 바닥에 닿아서 흐른 tick
+    pub fn tick_count(&self) -> u32 {
+        self.tick
+    }
+
+    /// 남은 되돌리기 횟수, 제한이 없으면 None
+    pub fn resets_left(&self) -> Option<u32> {
+        match self.reset {
+            LockReset::MoveReset { limit } => Some(limit.saturating_sub(self.resets)),
+            LockReset::Infinity | LockReset::StepReset => None,
+        }
+    }
+
+    /// 새 블록이 나왔을 때
+    pub fn spawn(&mut self, board: &Board) {
+        self.tick = 0;
+        self.resets = 0;
+        self.lowest = bottom(board);
+    }
+
+    /// 이동이나 회전에 성공한 뒤
+    pub fn on_move(&mut self, board: &Board) {
+        if self.update_lowest(board) {
+            return;
+        }
+        match self.reset {
+            LockReset::Infinity => self.tick = 0,
+            LockReset::MoveReset { limit } => {
+                // 바닥에 닿기 전 움직임은 세지 않는다
+                if (self.tick > 0 || is_grounded(board)) && self.resets < limit {
+                    self.resets += 1;
+                    self.tick = 0;
+                }
+            }
+            LockReset::StepReset => {}
+        }
+    }
+
+    /// 한 tick 진행하고 지금 고정해야 하면 true
+    pub fn tick(&mut self, board: &Board) -> bool {
+        if board.active_piece().is_none() {
+            return false;
+        }
+        self.update_lowest(board);
+        if !is_grounded(board) {
+            if self.reset != LockReset::StepReset {
+                self.tick = 0;
+            }
+            return false;
+        }
+        if self.resets_left() == Some(0) {
+            return true;
+        }
+        self.tick += 1;
+        self.tick >= self.delay
+    }
+
+    /// 가장 낮은 줄을 새로 내려갔으면 타이머와 횟수를 되돌린다
+    fn update_lowest(&mut self, board: &Board) -> bool {
+        let Some(y) = bottom(board) else {
+            return false;
+        };
+        if self.lowest.is_some_and(|lowest| y <= lowest) {
+            return false;
+        }
+        self.lowest = Some(y);
+        self.tick = 0;
+        self.resets = 0;
+        true
+    }
+}
+
+fn is_grounded(board: &Board) -> bool {
+    board.try_step().is_err()
+}
+
+fn bottom(board: &Board) -> Option<usize> {
+    board
+        .get_falling_blocks()
+        .iter()
+        .map(|f| f.location.y)
+        .max()
+}
@@ -0,0 +1,228 @@
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ActivePiece, BitBoard, Board, GameInput, RotateDirection, TSpin, Tetrimino};
+
+/// 탐색에 쓰는 입력, 같은 길이면 앞쪽 입력을 먼저 쓴다
+const INPUTS: [GameInput; 6] = [
+    GameInput::MoveLeft,
+    GameInput::MoveRight,
+    GameInput::RotateRight,
+    GameInput::RotateLeft,
+    GameInput::RotateHalf,
+    GameInput::SoftDrop,
+];
+
+/// 블록을 고정할 수 있는 자리 하나와 그 자리까지 가는 가장 짧은 입력
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct Placement {
+    pub piece: ActivePiece,
+    pub tspin: TSpin,
+    /// 마지막은 항상 `HardDrop`
+    pub inputs: Vec<GameInput>,
+}
+
+/// 스폰 지점에서 시작해 도달할 수 있는 모든 고정 자리
+///
+/// 보드의 떨어지는 블록은 무시한다, 스폰 지점이 막혀 있거나 [`BitBoard`] 로 옮길 수 없으면 빈 목록.
+pub fn placements(board: &Board, kind: Tetrimino) -> Vec<Placement> {
+    let Ok(bitboard) = BitBoard::try_from(board) else {
+        return vec![];
+    };
+    match bitboard.spawn(kind) {
+        Some(piece) => placements_from(&bitboard, piece),
+        None => vec![],
+    }
+}
+
+/// `start` 에서 시작해 도달할 수 있는 모든 고정 자리
+///
+/// 입력 하나를 한 칸으로 보고 너비 우선으로 찾아서 입력 수가 적은 순서로 돌려준다.
+/// 중력은 없는 것으로 보고, 칸이 같아도 T-스핀 판정이 다르면 따로 돌려준다.
+pub fn placements_from(board: &BitBoard, start: ActivePiece) -> Vec<Placement> {
+    if board.collides(&start) {
+        return vec![];
+    }
+
+    let mut visited = Visited::new(board);
+    let mut locked = Locked::default();
+    let mut queue = VecDeque::new();
+    visited.insert(&start, None);
+    queue.push_back(start);
+
+    while let Some(piece) = queue.pop_front() {
+        let dist = visited.dist(&piece);
+
+        // 하드 드롭으로 한 칸이라도 내려가면 마지막 동작이 회전이 아니게 된다
+        let dropped = board.hard_drop(&piece);
+        if dropped != piece || dist == 0 {
+            locked.insert(board, &visited, dropped, TSpin::None, (piece, None));
+        }
+
+        for input in INPUTS {
+            let Some((next, last_rotation)) = apply_input(board, &piece, input) else {
+                continue;
+            };
+            // 바닥에 닿은 자리는 어떤 입력으로 들어왔는지에 따라 판정이 달라진다
+            if board.try_move(&next, 0, 1).is_none() {
+                let tspin = board.detect_tspin(&next, last_rotation.as_ref());
+                locked.insert(board, &visited, next, tspin, (piece, Some(input)));
+            }
+            if visited.insert(&next, Some((piece, input))) {
+                queue.push_back(next);
+            }
+        }
+    }
+    locked.into_placements()
+}
+
+/// 한 번 가본 블록 위치와 거기까지 온 길
+///
+/// 위치가 보드 크기에 묶여 있어서 해시 대신 평평한 배열로 찾는다.
+struct Visited {
+    width: usize,
+    height: usize,
+    nodes: Vec<Option<Visit>>,
+}
+
+#[derive(Clone, Copy)]
+struct Visit {
+    /// 시작 위치에서 온 입력 수
+    dist: usize,
+    /// 바로 앞 위치와 거기서 누른 입력
+    from: Option<(ActivePiece, GameInput)>,
+}
+
+impl Visited {
+    /// 블록 칸은 기준점에서 이만큼 넘게 떨어지지 않는다
+    const MARGIN: isize = 4;
+
+    fn new(board: &BitBoard) -> Self {
+        let width = board.width() + 2 * Self::MARGIN as usize;
+        let height = board.height() + 2 * Self::MARGIN as usize;
+        Self {
+            width,
+            height,
+            nodes: vec![None; width * height * 4],
+        }
+    }
+
+    /// 여백 밖의 기준점이면 None
+    fn index(&self, piece: &ActivePiece) -> Option<usize> {
+        let x = usize::try_from(piece.origin.0 + Self::MARGIN).ok()?;
+        let y = usize::try_from(piece.origin.1 + Self::MARGIN).ok()?;
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        Some((piece.rotation as usize * self.height + y) * self.width + x)
+    }
+
+    /// 처음 온 위치면 기록하고 true, 기록할 수 없는 위치면 false
+    fn insert(&mut self, piece: &ActivePiece, from: Option<(ActivePiece, GameInput)>) -> bool {
+        let dist = from.map_or(0, |(parent, _)| self.dist(&parent) + 1);
+        let Some(idx) = self.index(piece) else {
+            return false;
+        };
+        if self.nodes[idx].is_some() {
+            return false;
+        }
+        self.nodes[idx] = Some(Visit { dist, from });
+        true
+    }
+
+    fn visit(&self, piece: &ActivePiece) -> Option<Visit> {
+        self.nodes[self.index(piece)?]
+    }
+
+    fn dist(&self, piece: &ActivePiece) -> usize {
+        self.visit(piece).map_or(0, |visit| visit.dist)
+    }
+
+    fn inputs_to(&self, piece: &ActivePiece) -> Vec<GameInput> {
+        let mut inputs = vec![];
+        let mut piece = *piece;
+        while let Some(Visit {
+            from: Some((parent, input)),
+            ..
+        }) = self.visit(&piece)
+        {
+            inputs.push(input);
+            piece = parent;
+        }
+        inputs.reverse();
+        inputs
+    }
+}
+
+/// 칸과 T-스핀 판정이 같은 자리는 입력이 가장 짧은 것 하나만 남긴다
+#[derive(Default)]
+struct Locked {
+    index: HashMap<([(isize, isize); 4], TSpin), usize>,
+    placements: Vec<Placement>,
+}
+
+impl Locked {
+    /// `from` 은 하드 드롭 전에 마지막으로 거친 위치와, 거기서 한 번 더 누른 입력
+    fn insert(
+        &mut self,
+        board: &BitBoard,
+        visited: &Visited,
+        piece: ActivePiece,
+        tspin: TSpin,
+        from: (ActivePiece, Option<GameInput>),
+    ) {
+        let (before, last) = from;
+        let len = visited.dist(&before) + usize::from(last.is_some()) + 1;
+        let mut cells = board.cells(&piece);
+        cells.sort_unstable();
+        let existing = self.index.get(&(cells, tspin)).copied();
+        if let Some(idx) = existing
+            && self.placements[idx].inputs.len() <= len
+        {
+            return;
+        }
+
+        let mut inputs = visited.inputs_to(&before);
+        inputs.extend(last);
+        inputs.push(GameInput::HardDrop);
+        let placement = Placement {
+            piece,
+            tspin,
+            inputs,
+        };
+        match existing {
+            Some(idx) => self.placements[idx] = placement,
+            None => {
+                self.index.insert((cells, tspin), self.placements.len());
+                self.placements.push(placement);
+            }
+        }
+    }
+
+    fn into_placements(mut self) -> Vec<Placement> {
+        self.placements.sort_by_key(|p| p.inputs.len());
+        self.placements
+    }
+}
+
+/// 움직인 블록과, 회전했으면 그 방향과 월킥 테스트 번호
+fn apply_input(
+    board: &BitBoard,
+    piece: &ActivePiece,
+    input: GameInput,
+) -> Option<(ActivePiece, Option<(RotateDirection, usize)>)> {
+    let dir = match input {
+        GameInput::MoveLeft => return board.try_move(piece, -1, 0).map(|p| (p, None)),
+        GameInput::MoveRight => return board.try_move(piece, 1, 0).map(|p| (p, None)),
+        GameInput::SoftDrop => return board.try_move(piece, 0, 1).map(|p| (p, None)),
+        GameInput::RotateLeft => RotateDirection::Left,
+        GameInput::RotateRight => RotateDirection::Right,
+        GameInput::RotateHalf => RotateDirection::Half,
+        GameInput::HardDrop | GameInput::Hold => return None,
+    };
+    let (rotated, kick_idx) = board.try_rotate(piece, dir.clone())?;
+    Some((rotated, Some((dir, kick_idx))))
+}
@@ -0,0 +1,231 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ActivePiece, BitBoard, Game, GameInput, HoldMode, Placement, Tetrimino, movegen};
+
+/// 퍼펙트 클리어까지 놓는 블록 하나
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct PerfectClearStep {
+    /// 먼저 홀드하고 나온 블록을 놓는다
+    pub hold: bool,
+    pub placement: Placement,
+}
+
+impl PerfectClearStep {
+    /// `Game::tick` 에 그대로 넘길 입력
+    pub fn inputs(&self) -> Vec<GameInput> {
+        let hold = self.hold.then_some(GameInput::Hold);
+        hold.into_iter()
+            .chain(self.placement.inputs.iter().copied())
+            .collect()
+    }
+}
+
+/// 지금 게임에서 `max_lines` 줄 안에 퍼펙트 클리어하는 순서를 찾는다
+///
+/// 떨어지는 블록, 홀드, 보이는 넥스트만 쓴다. 없거나 [`BitBoard`] 로 옮길 수 없는 보드면 None.
+pub fn solve(game: &Game, max_lines: usize) -> Option<Vec<PerfectClearStep>> {
+    let current = game.current()?;
+    let next = game.next().iter().copied().collect::<Vec<_>>();
+    let board = BitBoard::try_from(game.board()).ok()?;
+    let mut solver = Solver::new(board, current, &next, game.hold());
+    solver.first = game.board().active_piece();
+    solver.can_hold = game.is_can_hold();
+    solver.hold_mode = game.config().hold_mode;
+    solver.solve(max_lines)
+}
+
+/// 보드와 블록 순서만으로 찾는다, `current` 는 스폰 지점에서 시작한다
+pub fn solve_from(
+    board: &BitBoard,
+    current: Tetrimino,
+    next: &[Tetrimino],
+    hold: Option<Tetrimino>,
+    max_lines: usize,
+) -> Option<Vec<PerfectClearStep>> {
+    Solver::new(board.clone(), current, next, hold).solve(max_lines)
+}
+
+/// 블록을 하나씩 놓아보는 깊이 우선 탐색
+///
+/// 남은 줄 위로는 놓지 않고, 빈 칸 덩어리가 4의 배수가 아니면 더 보지 않는다.
+struct Solver {
+    board: BitBoard,
+    /// 떨어지는 블록과 그 뒤 넥스트
+    queue: Vec<Tetrimino>,
+    hold: Option<Tetrimino>,
+    /// 첫 블록이 이미 움직였으면 그 위치에서 시작한다
+    first: Option<ActivePiece>,
+    /// 첫 블록에서 홀드할 수 있는지
+    can_hold: bool,
+    /// 홀드를 쓰지 않는 규칙이면 두 번째 블록부터도 홀드하지 않는다
+    hold_mode: HoldMode,
+    /// 이미 해봤는데 안 된 (보드, 남은 줄 수, 블록 순서 번호, 홀드)
+    failed: HashSet<(Vec<u16>, usize, usize, Option<Tetrimino>)>,
+}
+
+impl Solver {
+    fn new(
+        board: BitBoard,
+        current: Tetrimino,
+        next: &[Tetrimino],
+        hold: Option<Tetrimino>,
+    ) -> Self {
+        Self {
+            board,
+            queue: std::iter::once(current)
+                .chain(next.iter().copied())
+                .collect(),
+            hold,
+            first: None,
+            can_hold: true,
+            hold_mode: HoldMode::default(),
+            failed: HashSet::new(),
+        }
+    }
+
+    fn solve(&mut self, max_lines: usize) -> Option<Vec<PerfectClearStep>> {
+        let width = self.board.width();
+        let height = self.board.height();
+        let filled = self
+            .board
+            .rows()
+            .iter()
+            .map(|row| row.count_ones() as usize)
+            .sum::<usize>();
+        let stack = self
+            .board
+            .rows()
+            .iter()
+            .position(|row| *row != 0)
+            .map_or(0, |top| height - top);
+        let pieces = self.queue.len() + usize::from(self.hold.is_some());
+
+        for lines in stack.max(1)..=max_lines.min(height) {
+            let empty = width * lines - filled;
+            if !empty.is_multiple_of(4) || empty / 4 > pieces {
+                continue;
+            }
+            let board = self.board.clone();
+            let mut steps = vec![];
+            if self.search(&board, lines, 0, self.hold, &mut steps) {
+                return Some(steps);
+            }
+        }
+        None
+    }
+
+    /// `idx` 는 지금 떨어지는 블록의 순서 번호
+    fn search(
+        &mut self,
+        board: &BitBoard,
+        lines: usize,
+        idx: usize,
+        hold: Option<Tetrimino>,
+        steps: &mut Vec<PerfectClearStep>,
+    ) -> bool {
+        if board.is_perfect_clear() && !steps.is_empty() {
+            return true;
+        }
+        let Some(current) = self.queue.get(idx).copied() else {
+            return false;
+        };
+        let key = (board.rows().to_vec(), lines, idx, hold);
+        if self.failed.contains(&key) {
+            return false;
+        }
+
+        // (홀드 여부, 놓을 블록, 다음 순서 번호, 놓은 뒤 홀드)
+        let mut choices = vec![(false, current, idx + 1, hold)];
+        if self.hold_mode != HoldMode::Disabled && (idx > 0 || self.can_hold) {
+            match hold {
+                Some(held) if held != current => {
+                    choices.push((true, held, idx + 1, Some(current)));
+                }
+                Some(_) => {}
+                None => {
+                    if let Some(next) = self.queue.get(idx + 1) {
+                        choices.push((true, *next, idx + 2, Some(current)));
+                    }
+                }
+            }
+        }
+
+        for (is_hold, kind, next_idx, next_hold) in choices {
+            let start = match self.first {
+                Some(piece) if idx == 0 && !is_hold => Some(piece),
+                _ => board.spawn(kind),
+            };
+            let Some(start) = start else {
+                continue;
+            };
+            let mut seen = HashSet::new();
+            for placement in movegen::placements_from(board, start) {
+                let mut cells = board.cells(&placement.piece);
+                if cells
+                    .iter()
+                    .any(|(_, y)| (*y as usize) < board.height() - lines)
+                {
+                    continue;
+                }
+                cells.sort_unstable();
+                if !seen.insert(cells) {
+                    continue;
+                }
+                let mut after = board.clone();
+                let cleared = after.lock(&placement.piece) as usize;
+                if !is_fillable(&after, lines - cleared) {
+                    continue;
+                }
+                steps.push(PerfectClearStep {
+                    hold: is_hold,
+                    placement,
+                });
+                if self.search(&after, lines - cleared, next_idx, next_hold, steps) {
+                    return true;
+                }
+                steps.pop();
+            }
+        }
+        self.failed.insert(key);
+        false
+    }
+}
+
+/// 아래 `lines` 줄의 빈 칸 덩어리가 모두 블록 4칸으로 채울 수 있는 크기인지
+fn is_fillable(board: &BitBoard, lines: usize) -> bool {
+    let width = board.width() as isize;
+    let height = board.height() as isize;
+    let top = height - lines as isize;
+    let mut visited = vec![0u16; lines];
+    for y in top..height {
+        for x in 0..width {
+            if board.is_filled(x, y) || visited[(y - top) as usize] & (1 << x) != 0 {
+                continue;
+            }
+            let mut size = 0;
+            let mut stack = vec![(x, y)];
+            visited[(y - top) as usize] |= 1 << x;
+            while let Some((cx, cy)) = stack.pop() {
+                size += 1;
+                for (nx, ny) in [(cx - 1, cy), (cx + 1, cy), (cx, cy - 1), (cx, cy + 1)] {
+                    if ny < top || board.is_filled(nx, ny) {
+                        continue;
+                    }
+                    let row = &mut visited[(ny - top) as usize];
+                    if *row & (1 << nx) == 0 {
+                        *row |= 1 << nx;
+                        stack.push((nx, ny));
+                    }
+                }
+            }
+            if size % 4 != 0 {
+                return false;
+            }
+        }
+    }
+    true
+}
@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    FallingBlock, FallingBlockAt, Location, PieceSet, Rotate, RotationSystem, Tetrimino,
+    randomizer::TETRIMINOS,
+};
+
+/// 보드 칸에 쓰지 않고 따로 들고 있는 떨어지는 블록
+///
+/// `origin` 은 스폰 지점 기준이고, 칸 위치는 회전 방식의 모양에서 계산한다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct ActivePiece {
+    pub kind: Tetrimino,
+    pub rotation: Rotate,
+    pub origin: (isize, isize),
+}
+
+/// [블록][회전 상태][블록 번호] 스폰 지점 기준 칸 위치
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PieceShapes([[[(i8, i8); 4]; 4]; 8]);
+
+impl PieceShapes {
+    /// 회전 방식을 옮긴 [`PieceSet`] 에서 네 방향 모양을 가져온다
+    pub(crate) fn new(system: &impl RotationSystem) -> Self {
+        let set = PieceSet::from_rotation_system(system);
+        let mut shapes = [[[(0, 0); 4]; 4]; 8];
+        for (kind, piece) in TETRIMINOS.into_iter().zip(set.pieces()) {
+            for (rotation, cells) in piece.shapes.iter().enumerate() {
+                shapes[kind as usize][rotation] = std::array::from_fn(|id| cells[id]);
+            }
+        }
+        Self(shapes)
+    }
+
+    /// 블록 번호 순서의 칸 위치
+    pub(crate) fn cells(&self, piece: &ActivePiece) -> [(isize, isize); 4] {
+        let (ox, oy) = piece.origin;
+        self.0[piece.kind as usize][piece.rotation as usize]
+            .map(|(x, y)| (ox + x as isize, oy + y as isize))
+    }
+
+    /// 예전처럼 블록 번호가 붙은 칸 목록으로 펼친다, 보드 밖 칸은 뺀다
+    pub(crate) fn fallings(&self, piece: &ActivePiece) -> Vec<FallingBlockAt> {
+        self.cells(piece)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(id, (x, y))| {
+                Some(FallingBlockAt {
+                    falling: FallingBlock {
+                        kind: piece.kind,
+                        rotation: piece.rotation,
+                        id: id as u8,
+                    },
+                    location: Location::new(usize::try_from(x).ok()?, usize::try_from(y).ok()?),
+                })
+            })
+            .collect()
+    }
+
+    /// 블록 하나의 위치에서 [`ActivePiece`] 를 되찾는다
+    pub(crate) fn piece_of(
+        &self,
+        falling: &FallingBlock,
+        location: &Location,
+    ) -> Option<ActivePiece> {
+        let (dx, dy) =
+            *self.0[falling.kind as usize][falling.rotation as usize].get(falling.id as usize)?;
+        Some(ActivePiece {
+            kind: falling.kind,
+            rotation: falling.rotation,
+            origin: (
+                isize::try_from(location.x).ok()? - dx as isize,
+                isize::try_from(location.y).ok()? - dy as isize,
+            ),
+        })
+    }
+
+    /// 칸 4개가 모양과 맞는 블록, 회전 상태는 `rotations` 순서로 찾는다
+    pub(crate) fn piece_at(
+        &self,
+        kind: Tetrimino,
+        rotations: impl IntoIterator<Item = Rotate>,
+        cells: [(isize, isize); 4],
+    ) -> Option<ActivePiece> {
+        let mut target = cells;
+        target.sort_unstable();
+        rotations.into_iter().find_map(|rotation| {
+            let mut shape =
+                self.0[kind as usize][rotation as usize].map(|(x, y)| (x as isize, y as isize));
+            shape.sort_unstable();
+            let (dx, dy) = (target[0].0 - shape[0].0, target[0].1 - shape[0].1);
+            shape
+                .iter()
+                .zip(&target)
+                .all(|((sx, sy), (tx, ty))| sx + dx == *tx && sy + dy == *ty)
+                .then_some(ActivePiece {
+                    kind,
+                    rotation,
+                    origin: (dx, dy),
+                })
+        })
+    }
+}
@@ -0,0 +1,438 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BitBoard, Rotate, RotateDirection, RotationSystem, RotationSystemKind, SeededRng, Tetrimino,
+    randomizer::TETRIMINOS, spawn_origin_of,
+};
+
+/// 블록 하나를 데이터로 적은 것
+///
+/// 좌표는 보드와 같이 y 가 아래로 증가하고, 회전 상태마다 칸 순서가 같아야 한다.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct PieceDef {
+    pub name: String,
+    pub spawn_rotation: Rotate,
+    /// [회전 상태] 스폰 지점 기준 칸 위치
+    pub shapes: Vec<Vec<(i8, i8)>>,
+    pub kicks: KickTable,
+}
+
+impl PieceDef {
+    /// 스폰 모양을 `pivot` 을 중심으로 돌려서 네 방향 모양을 만든다
+    ///
+    /// `pivot` 은 두 배 좌표라서 칸 중심이면 짝수, 칸 모서리면 홀수다.
+    pub fn rotating(
+        name: &str,
+        cells: &[(i8, i8)],
+        pivot: (i8, i8),
+        kicks: KickTable,
+    ) -> Result<Self, PieceSetError> {
+        let (px, py) = (pivot.0 as i16, pivot.1 as i16);
+        if (px - py) % 2 != 0 {
+            return Err(PieceSetError::InvalidPivot);
+        }
+        let mut shapes = vec![cells.to_vec()];
+        for _ in 1..4 {
+            let prev = shapes.last().unwrap();
+            let next = prev
+                .iter()
+                .map(|(x, y)| {
+                    let rx = (px + py) / 2 - *y as i16;
+                    let ry = *x as i16 + (py - px) / 2;
+                    match (i8::try_from(rx), i8::try_from(ry)) {
+                        (Ok(rx), Ok(ry)) => Ok((rx, ry)),
+                        _ => Err(PieceSetError::InvalidPivot),
+                    }
+                })
+                .collect::<Result<_, _>>()?;
+            shapes.push(next);
+        }
+        Ok(Self {
+            name: name.to_string(),
+            spawn_rotation: Rotate::D0,
+            shapes,
+            kicks,
+        })
+    }
+
+    /// 회전 상태가 모자란 블록이면 빈 목록
+    pub fn cells(&self, rotation: Rotate) -> &[(i8, i8)] {
+        self.shapes
+            .get(rotation as usize)
+            .map_or(&[], Vec::as_slice)
+    }
+}
+
+/// 회전 방향별 [회전 전 상태][테스트] 월킥, 빈 목록이면 그 방향으로 돌지 않는다
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct KickTable {
+    pub right: Vec<Vec<(i8, i8)>>,
+    pub left: Vec<Vec<(i8, i8)>>,
+    pub half: Vec<Vec<(i8, i8)>>,
+}
+
+impl KickTable {
+    /// 회전 방식의 월킥을 테스트 순서대로 옮겨 적는다
+    pub fn from_rotation_system(system: &impl RotationSystem, kind: Tetrimino) -> Self {
+        let table = |dir: RotateDirection| {
+            ROTATIONS
+                .iter()
+                .map(|from| {
+                    (0..)
+                        .map_while(|test_idx| system.wall_kick(kind, *from, &dir, test_idx))
+                        .collect()
+                })
+                .collect()
+        };
+        Self {
+            right: table(RotateDirection::Right),
+            left: table(RotateDirection::Left),
+            half: table(RotateDirection::Half),
+        }
+    }
+
+    pub fn kicks(&self, from: Rotate, dir: &RotateDirection) -> &[(i8, i8)] {
+        let table = match dir {
+            RotateDirection::Right => &self.right,
+            RotateDirection::Left => &self.left,
+            RotateDirection::Half => &self.half,
+        };
+        table.get(from as usize).map_or(&[], Vec::as_slice)
+    }
+
+    fn scaled(&self, scale: i8) -> Self {
+        let scale_table = |table: &Vec<Vec<(i8, i8)>>| {
+            table
+                .iter()
+                .map(|kicks| kicks.iter().map(|(x, y)| (x * scale, y * scale)).collect())
+                .collect()
+        };
+        Self {
+            right: scale_table(&self.right),
+            left: scale_table(&self.left),
+            half: scale_table(&self.half),
+        }
+    }
+}
+
+const ROTATIONS: [Rotate; 4] = [Rotate::D0, Rotate::D90, Rotate::D180, Rotate::D270];
+
+/// 블록 세트를 만들다 실패한 이유
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub enum PieceSetError {
+    /// 블록이 하나도 없다
+    Empty,
+    /// 회전 상태가 4개가 아니거나 상태마다 칸 수가 다른 블록
+    InvalidShape(usize),
+    /// 방향마다 회전 상태 4개의 월킥이 없는 블록
+    InvalidKicks(usize),
+    /// 칸 중심이나 모서리가 아니거나, 돌린 칸이 좌표 범위를 넘는 회전 중심
+    InvalidPivot,
+}
+
+impl std::fmt::Display for PieceSetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PieceSetError::Empty => write!(f, "Piece set has no pieces"),
+            PieceSetError::InvalidShape(kind) => write!(f, "Piece {kind} has an invalid shape"),
+            PieceSetError::InvalidKicks(kind) => write!(f, "Piece {kind} has invalid kicks"),
+            PieceSetError::InvalidPivot => write!(f, "Pivot must be a cell center or a corner"),
+        }
+    }
+}
+
+impl std::error::Error for PieceSetError {}
+
+/// 게임에서 쓰는 블록 모음, 내장 7개 테트로미노도 세트 하나다
+///
+/// 세트 블록은 [`BitBoard`] 위에서만 움직인다. [`crate::Board`] 와 [`crate::Game`] 은 아직 [`Tetrimino`] 만 다룬다.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct PieceSet {
+    pieces: Vec<PieceDef>,
+}
+
+/// 세트 안의 떨어지는 블록, `kind` 는 세트 안의 순서
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct SetPiece {
+    pub kind: usize,
+    pub rotation: Rotate,
+    pub origin: (isize, isize),
+}
+
+impl PieceSet {
+    pub fn new(pieces: Vec<PieceDef>) -> Result<Self, PieceSetError> {
+        if pieces.is_empty() {
+            return Err(PieceSetError::Empty);
+        }
+        for (kind, piece) in pieces.iter().enumerate() {
+            let size = piece.shapes.first().map_or(0, Vec::len);
+            if piece.shapes.len() != 4 || size == 0 || piece.shapes.iter().any(|s| s.len() != size)
+            {
+                return Err(PieceSetError::InvalidShape(kind));
+            }
+            let kicks = &piece.kicks;
+            if [&kicks.right, &kicks.left, &kicks.half]
+                .iter()
+                .any(|table| table.len() != 4)
+            {
+                return Err(PieceSetError::InvalidKicks(kind));
+            }
+        }
+        Ok(Self { pieces })
+    }
+
+    /// 회전 방식의 모양과 월킥을 그대로 옮긴 7개 테트로미노, 순서는 I O T J L S Z
+    pub fn from_rotation_system(system: &impl RotationSystem) -> Self {
+        let pieces = TETRIMINOS
+            .into_iter()
+            .map(|kind| {
+                let (spawn_rotation, spawn) = system.spawn(kind);
+                let mut shapes = vec![vec![]; 4];
+                let (mut rotation, mut cells) = (spawn_rotation, spawn);
+                for _ in 0..4 {
+                    shapes[rotation as usize] = cells.to_vec();
+                    cells = std::array::from_fn(|id| {
+                        let (x, y) = cells[id];
+                        let (dx, dy) =
+                            system.rotate_offset(kind, rotation, &RotateDirection::Right, id as u8);
+                        (x + dx, y + dy)
+                    });
+                    rotation = rotation.next_cw();
+                }
+                PieceDef {
+                    name: String::from(kind),
+                    spawn_rotation,
+                    shapes,
+                    kicks: KickTable::from_rotation_system(system, kind),
+                }
+            })
+            .collect();
+        Self { pieces }
+    }
+
+    /// SRS 테트로미노
+    pub fn tetrominoes() -> Self {
+        Self::from_rotation_system(&RotationSystemKind::Srs)
+    }
+
+    /// 3칸짜리 I, L
+    pub fn trominoes() -> Self {
+        let srs = RotationSystemKind::Srs;
+        Self {
+            pieces: vec![
+                PieceDef::rotating(
+                    "I3",
+                    &[(0, 1), (1, 1), (2, 1)],
+                    (2, 2),
+                    KickTable::from_rotation_system(&srs, Tetrimino::I),
+                )
+                .expect("built-in pivot"),
+                PieceDef::rotating(
+                    "L3",
+                    &[(0, 0), (0, 1), (1, 1)],
+                    (1, 1),
+                    KickTable::from_rotation_system(&srs, Tetrimino::T),
+                )
+                .expect("built-in pivot"),
+            ],
+        }
+    }
+
+    /// 뒤집기를 따로 센 18개 펜토미노, 월킥은 SRS 를 빌려 쓴다
+    pub fn pentominoes() -> Self {
+        let srs = RotationSystemKind::Srs;
+        let kicks = KickTable::from_rotation_system(&srs, Tetrimino::T);
+        let i_kicks = KickTable::from_rotation_system(&srs, Tetrimino::I);
+        Self {
+            pieces: PENTOMINOES
+                .iter()
+                .map(|(name, cells, pivot)| {
+                    let kicks = if *name == "I" { &i_kicks } else { &kicks };
+                    PieceDef::rotating(name, cells, *pivot, kicks.clone()).expect("built-in pivot")
+                })
+                .collect(),
+        }
+    }
+
+    /// 칸 하나를 2x2 로 키운 세트, 월킥 거리도 두 배가 된다
+    pub fn big(&self) -> Self {
+        let pieces = self
+            .pieces
+            .iter()
+            .map(|piece| PieceDef {
+                name: piece.name.clone(),
+                spawn_rotation: piece.spawn_rotation,
+                shapes: piece
+                    .shapes
+                    .iter()
+                    .map(|cells| {
+                        cells
+                            .iter()
+                            .flat_map(|(x, y)| {
+                                [(0, 0), (1, 0), (0, 1), (1, 1)]
+                                    .map(|(dx, dy)| (x * 2 + dx, y * 2 + dy))
+                            })
+                            .collect()
+                    })
+                    .collect(),
+                kicks: piece.kicks.scaled(2),
+            })
+            .collect();
+        Self { pieces }
+    }
+
+    pub fn pieces(&self) -> &[PieceDef] {
+        &self.pieces
+    }
+
+    pub fn len(&self) -> usize {
+        self.pieces.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pieces.is_empty()
+    }
+
+    /// 블록 순서의 칸 위치, 세트에 없는 블록이면 None
+    pub fn cells(&self, piece: &SetPiece) -> Option<Vec<(isize, isize)>> {
+        let (ox, oy) = piece.origin;
+        let def = self.pieces.get(piece.kind)?;
+        Some(
+            def.cells(piece.rotation)
+                .iter()
+                .map(|(x, y)| (ox + *x as isize, oy + *y as isize))
+                .collect(),
+        )
+    }
+
+    /// 세트에 없는 블록은 놓을 수 없으니 겹친 것으로 본다
+    pub fn collides(&self, board: &BitBoard, piece: &SetPiece) -> bool {
+        self.cells(piece)
+            .is_none_or(|cells| cells.iter().any(|(x, y)| board.is_filled(*x, *y)))
+    }
+
+    /// 테트로미노와 같은 규칙의 스폰 지점, 막혀 있으면 None
+    pub fn spawn(&self, board: &BitBoard, kind: usize) -> Option<SetPiece> {
+        let def = self.pieces.get(kind)?;
+        let (x, y) = spawn_origin_of(def.cells(def.spawn_rotation), board.width(), board.height());
+        let piece = SetPiece {
+            kind,
+            rotation: def.spawn_rotation,
+            origin: (x as isize, y as isize),
+        };
+        (!self.collides(board, &piece)).then_some(piece)
+    }
+
+    pub fn try_move(
+        &self,
+        board: &BitBoard,
+        piece: &SetPiece,
+        dx: isize,
+        dy: isize,
+    ) -> Option<SetPiece> {
+        let moved = SetPiece {
+            origin: (piece.origin.0 + dx, piece.origin.1 + dy),
+            ..*piece
+        };
+        (!self.collides(board, &moved)).then_some(moved)
+    }
+
+    /// 회전한 블록과 성공한 월킥 테스트 번호
+    pub fn try_rotate(
+        &self,
+        board: &BitBoard,
+        piece: &SetPiece,
+        dir: RotateDirection,
+    ) -> Option<(SetPiece, usize)> {
+        let rotation = piece.rotation.next(&dir);
+        let kicks = self
+            .pieces
+            .get(piece.kind)?
+            .kicks
+            .kicks(piece.rotation, &dir);
+        kicks.iter().enumerate().find_map(|(test_idx, (kx, ky))| {
+            let rotated = SetPiece {
+                kind: piece.kind,
+                rotation,
+                origin: (piece.origin.0 + *kx as isize, piece.origin.1 + *ky as isize),
+            };
+            (!self.collides(board, &rotated)).then_some((rotated, test_idx))
+        })
+    }
+
+    pub fn hard_drop(&self, board: &BitBoard, piece: &SetPiece) -> SetPiece {
+        let mut dropped = *piece;
+        while let Some(next) = self.try_move(board, &dropped, 0, 1) {
+            dropped = next;
+        }
+        dropped
+    }
+
+    /// 고정하고 지운 줄 수를 돌려준다, 세트에 없는 블록이면 None
+    pub fn lock(&self, board: &mut BitBoard, piece: &SetPiece) -> Option<u8> {
+        board.place_cells(&self.cells(piece)?);
+        Some(board.clear_lines())
+    }
+}
+
+/// (이름, 스폰 모양, 두 배 좌표 회전 중심)
+type Pentomino = (&'static str, [(i8, i8); 5], (i8, i8));
+
+#[rustfmt::skip]
+const PENTOMINOES: [Pentomino; 18] = [
+    ("F", [(1, 0), (2, 0), (0, 1), (1, 1), (1, 2)], (2, 2)),
+    ("F'", [(0, 0), (1, 0), (1, 1), (2, 1), (1, 2)], (2, 2)),
+    ("I", [(0, 1), (1, 1), (2, 1), (3, 1), (4, 1)], (4, 2)),
+    ("L", [(3, 0), (0, 1), (1, 1), (2, 1), (3, 1)], (3, 3)),
+    ("J", [(0, 0), (0, 1), (1, 1), (2, 1), (3, 1)], (3, 3)),
+    ("N", [(2, 0), (3, 0), (0, 1), (1, 1), (2, 1)], (3, 3)),
+    ("N'", [(0, 0), (1, 0), (1, 1), (2, 1), (3, 1)], (3, 3)),
+    ("P", [(1, 0), (2, 0), (0, 1), (1, 1), (2, 1)], (2, 2)),
+    ("P'", [(0, 0), (1, 0), (0, 1), (1, 1), (2, 1)], (2, 2)),
+    ("T", [(1, 0), (1, 1), (0, 2), (1, 2), (2, 2)], (2, 2)),
+    ("U", [(0, 0), (2, 0), (0, 1), (1, 1), (2, 1)], (2, 2)),
+    ("V", [(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)], (2, 2)),
+    ("W", [(0, 0), (0, 1), (1, 1), (1, 2), (2, 2)], (2, 2)),
+    ("X", [(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)], (2, 2)),
+    ("Y", [(1, 0), (0, 1), (1, 1), (2, 1), (3, 1)], (3, 3)),
+    ("Y'", [(2, 0), (0, 1), (1, 1), (2, 1), (3, 1)], (3, 3)),
+    ("Z", [(0, 0), (1, 0), (1, 1), (1, 2), (2, 2)], (2, 2)),
+    ("S", [(1, 0), (2, 0), (1, 1), (0, 2), (1, 2)], (2, 2)),
+];
+
+/// 세트 블록을 전부 한 번씩 섞어서 꺼낸다
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetBag {
+    rng: SeededRng,
+    len: usize,
+    bag: Vec<usize>,
+}
+
+impl SetBag {
+    pub fn new(set: &PieceSet, seed: u64) -> Self {
+        Self {
+            rng: SeededRng::new(seed),
+            len: set.len(),
+            bag: vec![],
+        }
+    }
+
+    /// 다음 블록의 세트 안 순서
+    pub fn next_kind(&mut self) -> usize {
+        if self.bag.is_empty() {
+            self.bag.extend(0..self.len);
+        }
+        let idx = self.rng.below(self.bag.len() as u32) as usize;
+        self.bag.swap_remove(idx)
+    }
+}
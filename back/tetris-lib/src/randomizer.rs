@@ -0,0 +1,202 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Tetrimino;
+
+pub const TETRIMINOS: [Tetrimino; 7] = [
+    Tetrimino::I,
+    Tetrimino::O,
+    Tetrimino::T,
+    Tetrimino::J,
+    Tetrimino::L,
+    Tetrimino::S,
+    Tetrimino::Z,
+];
+
+/// RNG 크레이트 없이 쓰는 SplitMix64
+///
+/// 정수 연산만 쓰기 때문에 wasm 과 native 에서 같은 시드면 같은 값이 나온다.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// `0..n` 범위의 값, 모듈로 편향 없이 뽑는다
+    pub fn below(&mut self, n: u32) -> u32 {
+        assert!(n > 0, "below(0)");
+        let threshold = n.wrapping_neg() % n;
+        loop {
+            let r = self.next_u32();
+            if r >= threshold {
+                return r % n;
+            }
+        }
+    }
+
+    /// `0.0..1.0` 범위의 값
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+pub trait Randomizer {
+    fn next_tetrimino(&mut self) -> Tetrimino;
+}
+
+/// 7개(또는 14개) 묶음을 섞어서 하나씩 꺼낸다
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bag {
+    rng: SeededRng,
+    copies: u8,
+    bag: Vec<Tetrimino>,
+}
+
+impl Bag {
+    pub fn seven(seed: u64) -> Self {
+        Self::with_copies(seed, 1)
+    }
+
+    pub fn fourteen(seed: u64) -> Self {
+        Self::with_copies(seed, 2)
+    }
+
+    fn with_copies(seed: u64, copies: u8) -> Self {
+        Self {
+            rng: SeededRng::new(seed),
+            copies,
+            bag: vec![],
+        }
+    }
+}
+
+impl Randomizer for Bag {
+    fn next_tetrimino(&mut self) -> Tetrimino {
+        if self.bag.is_empty() {
+            for _ in 0..self.copies {
+                self.bag.extend(TETRIMINOS);
+            }
+        }
+        let idx = self.rng.below(self.bag.len() as u32) as usize;
+        self.bag.swap_remove(idx)
+    }
+}
+
+/// 이전 결과와 상관없이 매번 7개 중에 하나
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Memoryless {
+    rng: SeededRng,
+}
+
+impl Memoryless {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: SeededRng::new(seed),
+        }
+    }
+}
+
+impl Randomizer for Memoryless {
+    fn next_tetrimino(&mut self) -> Tetrimino {
+        TETRIMINOS[self.rng.below(TETRIMINOS.len() as u32) as usize]
+    }
+}
+
+/// TGM 방식, 최근 4개와 겹치면 정해진 횟수만큼 다시 뽑는다
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History4 {
+    rng: SeededRng,
+    history: [Tetrimino; 4],
+    tries: u8,
+    is_first: bool,
+}
+
+impl History4 {
+    /// TGM2 설정: 6번 재시도, 시작 기록 ZSSZ
+    pub fn tgm(seed: u64) -> Self {
+        Self {
+            rng: SeededRng::new(seed),
+            history: [Tetrimino::Z, Tetrimino::S, Tetrimino::S, Tetrimino::Z],
+            tries: 6,
+            is_first: true,
+        }
+    }
+}
+
+impl Randomizer for History4 {
+    fn next_tetrimino(&mut self) -> Tetrimino {
+        let picked = if self.is_first {
+            // 첫 블록은 S, Z, O 가 나오지 않는다
+            self.is_first = false;
+            let first = [Tetrimino::I, Tetrimino::T, Tetrimino::J, Tetrimino::L];
+            first[self.rng.below(first.len() as u32) as usize]
+        } else {
+            let mut picked = TETRIMINOS[self.rng.below(TETRIMINOS.len() as u32) as usize];
+            for _ in 1..self.tries {
+                if !self.history.contains(&picked) {
+                    break;
+                }
+                picked = TETRIMINOS[self.rng.below(TETRIMINOS.len() as u32) as usize];
+            }
+            picked
+        };
+        self.history.rotate_left(1);
+        self.history[3] = picked;
+        picked
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub enum RandomizerKind {
+    #[default]
+    SevenBag,
+    FourteenBag,
+    Memoryless,
+    History4,
+}
+
+impl RandomizerKind {
+    pub fn build(self, seed: u64) -> PieceRandomizer {
+        match self {
+            Self::SevenBag => PieceRandomizer::Bag(Bag::seven(seed)),
+            Self::FourteenBag => PieceRandomizer::Bag(Bag::fourteen(seed)),
+            Self::Memoryless => PieceRandomizer::Memoryless(Memoryless::new(seed)),
+            Self::History4 => PieceRandomizer::History4(History4::tgm(seed)),
+        }
+    }
+}
+
+/// 직렬화 가능한 내장 randomizer 모음
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PieceRandomizer {
+    Bag(Bag),
+    Memoryless(Memoryless),
+    History4(History4),
+}
+
+impl Randomizer for PieceRandomizer {
+    fn next_tetrimino(&mut self) -> Tetrimino {
+        match self {
+            Self::Bag(r) => r.next_tetrimino(),
+            Self::Memoryless(r) => r.next_tetrimino(),
+            Self::History4(r) => r.next_tetrimino(),
+        }
+    }
+}
@@ -0,0 +1,288 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Board, Game, GameConfig, GameEvent, GameInput, Tetrimino};
+
+/// 지금 쓰는 리플레이 형식 버전, 형식이 바뀌면 올린다
+pub const REPLAY_VERSION: u32 = 2;
+
+/// 시드와 규칙, tick 별 입력만으로 게임을 다시 돌릴 수 있게 저장한 것
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct Replay {
+    pub version: u32,
+    pub config: GameConfig,
+    /// `Game::with_board` 로 시작한 보드, None 이면 빈 보드
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub board: Option<Board>,
+    /// 전체 tick 수
+    pub ticks: u32,
+    /// 입력이 있는 tick 만, tick 순서대로
+    pub frames: Vec<ReplayFrame>,
+    /// 기록이 끝났을 때의 결과, 다시 돌렸을 때 같은지 확인한다
+    pub result: Option<ReplayResult>,
+}
+
+/// tick 하나에 들어간 입력
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct ReplayFrame {
+    /// 첫 tick 이 1
+    pub tick: u32,
+    /// 이 tick 전에 한 조작, 적은 순서대로 다시 한다
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<ReplayAction>,
+    pub inputs: Vec<GameInput>,
+}
+
+/// tick 사이에 `Game` 에 직접 한 조작
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub enum ReplayAction {
+    /// `push_next` 로 넣은 블록, 랜더마이저가 없을 때만 쓴다
+    PushNext(Tetrimino),
+    Undo,
+    Redo,
+    /// `push_garbage` 로 넣은 방해 줄
+    Garbage(Vec<Vec<usize>>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct ReplayResult {
+    pub score: u32,
+    pub lines: u32,
+    pub level: u32,
+    pub is_over: bool,
+}
+
+impl From<&Game> for ReplayResult {
+    fn from(game: &Game) -> Self {
+        Self {
+            score: game.score(),
+            lines: game.lines(),
+            level: game.level(),
+            is_over: game.is_over(),
+        }
+    }
+}
+
+/// 리플레이를 읽거나 검증하다 실패한 이유
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub enum ReplayError {
+    UnsupportedVersion(u32),
+    /// tick 순서가 어긋났거나 `ticks` 를 넘는 프레임
+    InvalidFrame(u32),
+    Mismatch {
+        expected: ReplayResult,
+        actual: ReplayResult,
+    },
+}
+
+impl std::fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported replay version {version}")
+            }
+            ReplayError::InvalidFrame(tick) => write!(f, "Invalid replay frame at tick {tick}"),
+            ReplayError::Mismatch { expected, actual } => {
+                write!(f, "Replay result mismatch {expected:?} != {actual:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl Replay {
+    pub fn new(config: GameConfig) -> Self {
+        Self {
+            version: REPLAY_VERSION,
+            config,
+            board: None,
+            ticks: 0,
+            frames: vec![],
+            result: None,
+        }
+    }
+
+    /// `Game::with_board` 로 넘긴 보드에서 시작한다
+    pub fn with_board(mut self, board: Board) -> Self {
+        self.board = Some(board);
+        self
+    }
+
+    /// 다음 tick 전에 넣은 블록을 기록한다
+    pub fn record_next(&mut self, tetrimino: Tetrimino) {
+        self.record_action(ReplayAction::PushNext(tetrimino));
+    }
+
+    /// 다음 tick 전에 한 `Game::undo` 를 기록한다
+    pub fn record_undo(&mut self) {
+        self.record_action(ReplayAction::Undo);
+    }
+
+    /// 다음 tick 전에 한 `Game::redo` 를 기록한다
+    pub fn record_redo(&mut self) {
+        self.record_action(ReplayAction::Redo);
+    }
+
+    /// 다음 tick 전에 넣은 방해 줄을 기록한다
+    pub fn record_garbage(&mut self, lines: &[Vec<usize>]) {
+        self.record_action(ReplayAction::Garbage(lines.to_vec()));
+    }
+
+    fn record_action(&mut self, action: ReplayAction) {
+        let tick = self.ticks + 1;
+        self.frame_mut(tick).actions.push(action);
+    }
+
+    /// `Game::tick` 한 번에 넘긴 입력을 기록한다
+    pub fn record(&mut self, inputs: &[GameInput]) {
+        self.ticks += 1;
+        if !inputs.is_empty() {
+            self.frame_mut(self.ticks).inputs.extend_from_slice(inputs);
+        }
+    }
+
+    /// 기록을 마치고 결과를 남긴다
+    pub fn finish(&mut self, game: &Game) {
+        self.result = Some(game.into());
+    }
+
+    fn frame_mut(&mut self, tick: u32) -> &mut ReplayFrame {
+        if self.frames.last().is_none_or(|f| f.tick != tick) {
+            self.frames.push(ReplayFrame {
+                tick,
+                actions: vec![],
+                inputs: vec![],
+            });
+        }
+        self.frames.last_mut().unwrap()
+    }
+}
+
+/// 다시 돌린 tick 하나
+#[derive(Debug, Clone)]
+pub struct ReplayStep {
+    pub tick: u32,
+    pub events: Vec<GameEvent>,
+    pub board: Board,
+}
+
+/// 리플레이를 tick 단위로 다시 돌린다
+///
+/// `Game` 은 시드와 입력이 같으면 같은 결과가 나오므로 저장된 게임을 그대로 재현한다.
+#[derive(Debug, Clone)]
+pub struct ReplayPlayer {
+    replay: Replay,
+    game: Game,
+    /// 게임이 끝나면 `Game::tick_count` 가 멈춰서 따로 센다
+    tick: u32,
+    frame_idx: usize,
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay) -> Result<Self, ReplayError> {
+        if replay.version != REPLAY_VERSION {
+            return Err(ReplayError::UnsupportedVersion(replay.version));
+        }
+        let mut last_tick = 0;
+        for frame in &replay.frames {
+            if frame.tick <= last_tick || frame.tick > replay.ticks {
+                return Err(ReplayError::InvalidFrame(frame.tick));
+            }
+            last_tick = frame.tick;
+        }
+        let mut game = Game::new(replay.config.clone());
+        if let Some(board) = &replay.board {
+            game = game.with_board(board.clone());
+        }
+        Ok(Self {
+            game,
+            replay,
+            tick: 0,
+            frame_idx: 0,
+        })
+    }
+
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.tick >= self.replay.ticks
+    }
+
+    /// 한 tick 진행한다, 끝났으면 None
+    pub fn step(&mut self) -> Option<Vec<GameEvent>> {
+        if self.is_finished() {
+            return None;
+        }
+        self.tick += 1;
+        let tick = self.tick;
+        let frame = self
+            .replay
+            .frames
+            .get(self.frame_idx)
+            .filter(|f| f.tick == tick);
+        let events = match frame {
+            Some(frame) => {
+                self.frame_idx += 1;
+                for action in &frame.actions {
+                    match action {
+                        ReplayAction::PushNext(tetrimino) => self.game.push_next(*tetrimino),
+                        ReplayAction::Undo => {
+                            self.game.undo();
+                        }
+                        ReplayAction::Redo => {
+                            self.game.redo();
+                        }
+                        ReplayAction::Garbage(lines) => {
+                            self.game.push_garbage(lines);
+                        }
+                    }
+                }
+                self.game.tick(&frame.inputs)
+            }
+            None => self.game.tick(&[]),
+        };
+        Some(events)
+    }
+
+    /// 끝까지 돌린 결과가 저장된 결과와 같은지 확인한다
+    pub fn verify(mut self) -> Result<Game, ReplayError> {
+        while self.step().is_some() {}
+        let actual = ReplayResult::from(&self.game);
+        match &self.replay.result {
+            Some(expected) if *expected != actual => Err(ReplayError::Mismatch {
+                expected: expected.clone(),
+                actual,
+            }),
+            _ => Ok(self.game),
+        }
+    }
+}
+
+impl Iterator for ReplayPlayer {
+    type Item = ReplayStep;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let events = self.step()?;
+        Some(ReplayStep {
+            tick: self.tick,
+            events,
+            board: self.game.board().clone(),
+        })
+    }
+}
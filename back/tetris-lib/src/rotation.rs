@@ -0,0 +1,473 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Rotate, RotateDirection, Tetrimino};
+
+/// 회전 방식
+///
+/// 블록 번호(id)별로 회전할 때 움직일 칸, 막혔을 때 시도할 월킥, 스폰 모양을 정한다.
+/// 좌표는 보드와 같이 y 가 아래로 증가한다.
+pub trait RotationSystem {
+    /// 스폰 방향과 스폰 지점 기준 블록 번호 순서의 칸 위치
+    fn spawn(&self, kind: Tetrimino) -> (Rotate, [(i8, i8); 4]);
+
+    /// `from` 상태에서 `dir` 로 회전할 때 `id` 블록이 움직일 칸
+    fn rotate_offset(
+        &self,
+        kind: Tetrimino,
+        from: Rotate,
+        dir: &RotateDirection,
+        id: u8,
+    ) -> (i8, i8);
+
+    /// `test_idx` 번째 월킥, 더 시도할 게 없으면 None
+    fn wall_kick(
+        &self,
+        kind: Tetrimino,
+        from: Rotate,
+        dir: &RotateDirection,
+        test_idx: usize,
+    ) -> Option<(i8, i8)>;
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub enum RotationSystemKind {
+    /// 가이드라인 SRS
+    #[default]
+    Srs,
+    /// SRS 에서 I 월킥만 좌우 대칭으로 바꾼 것
+    SrsPlus,
+    /// 아리카(TGM) 방식, 좌우 1칸 킥만 있고 I 는 킥이 없다
+    ///
+    /// 가운데 열 규칙은 적용하지 않는다.
+    Ars,
+    /// 클래식 NES, 킥이 없다
+    Nes,
+}
+
+impl RotationSystem for RotationSystemKind {
+    fn spawn(&self, kind: Tetrimino) -> (Rotate, [(i8, i8); 4]) {
+        match self {
+            Self::Srs | Self::SrsPlus => (Rotate::D0, SPAWN_TABLE[kind as usize]),
+            Self::Ars => shape_spawn(&ARS_SHAPE_TABLE, kind),
+            Self::Nes => shape_spawn(&NES_SHAPE_TABLE, kind),
+        }
+    }
+
+    fn rotate_offset(
+        &self,
+        kind: Tetrimino,
+        from: Rotate,
+        dir: &RotateDirection,
+        id: u8,
+    ) -> (i8, i8) {
+        match self {
+            Self::Srs | Self::SrsPlus => match dir {
+                RotateDirection::Left => srs_left_rotate_reference(kind, from, id),
+                RotateDirection::Right => srs_right_rotate_reference(kind, from, id),
+                RotateDirection::Half => {
+                    let (x1, y1) = srs_right_rotate_reference(kind, from, id);
+                    let (x2, y2) = srs_right_rotate_reference(kind, from.next_cw(), id);
+                    (x1 + x2, y1 + y2)
+                }
+            },
+            Self::Ars => shape_rotate_offset(&ARS_SHAPE_TABLE, kind, from, dir, id),
+            Self::Nes => shape_rotate_offset(&NES_SHAPE_TABLE, kind, from, dir, id),
+        }
+    }
+
+    fn wall_kick(
+        &self,
+        kind: Tetrimino,
+        from: Rotate,
+        dir: &RotateDirection,
+        test_idx: usize,
+    ) -> Option<(i8, i8)> {
+        match self {
+            Self::SrsPlus if kind == Tetrimino::I && *dir != RotateDirection::Half => {
+                let dir_idx = match dir {
+                    RotateDirection::Left => 1,
+                    _ => 0,
+                };
+                SRS_PLUS_WALL_KICK_I_TABLE[dir_idx][from as usize]
+                    .get(test_idx)
+                    .copied()
+            }
+            Self::Srs | Self::SrsPlus => srs_wall_kick_reference(kind, from, dir, test_idx),
+            Self::Ars => match kind {
+                Tetrimino::I | Tetrimino::O => ARS_WALL_KICK_TABLE[..1].get(test_idx).copied(),
+                _ => ARS_WALL_KICK_TABLE.get(test_idx).copied(),
+            },
+            Self::Nes => (test_idx == 0).then_some((0, 0)),
+        }
+    }
+}
+
+const RIGHT_ROTATE_TABLE: [[[(i8, i8); 4]; 4]; 8] = [
+    // Empty
+    [
+        [(0, 0), (0, 0), (0, 0), (0, 0)],
+        [(0, 0), (0, 0), (0, 0), (0, 0)],
+        [(0, 0), (0, 0), (0, 0), (0, 0)],
+        [(0, 0), (0, 0), (0, 0), (0, 0)],
+    ],
+    // Tetrimino::I
+    [
+        // id 0, id 1, id 2, id 3
+        [(2, -1), (1, 0), (0, 1), (-1, 2)],   // D0 (D0 -> D90)
+        [(1, 2), (0, 1), (-1, 0), (-2, -1)],  // D90 (D90 -> D180)
+        [(-2, 1), (-1, 0), (0, -1), (1, -2)], // D180 (D180 -> D270)
+        [(-1, -2), (0, -1), (1, 0), (2, 1)],  // D270 (D270 -> D0)
+    ],
+    // Tetrimino::O
+    [
+        [(0, 0), (0, 0), (0, 0), (0, 0)],
+        [(0, 0), (0, 0), (0, 0), (0, 0)],
+        [(0, 0), (0, 0), (0, 0), (0, 0)],
+        [(0, 0), (0, 0), (0, 0), (0, 0)],
+    ],
+    // Tetrimino::T
+    [
+        [(1, 1), (1, -1), (0, 0), (-1, 1)],
+        [(-1, 1), (1, 1), (0, 0), (-1, -1)],
+        [(-1, -1), (-1, 1), (0, 0), (1, -1)],
+        [(1, -1), (-1, -1), (0, 0), (1, 1)],
+    ],
+    // Tetrimino::J
+    [
+        [(2, 0), (1, -1), (0, 0), (-1, 1)],
+        [(0, 2), (1, 1), (0, 0), (-1, -1)],
+        [(-2, 0), (-1, 1), (0, 0), (1, -1)],
+        [(0, -2), (-1, -1), (0, 0), (1, 1)],
+    ],
+    // Tetrimino::L
+    [
+        [(0, 2), (1, -1), (0, 0), (-1, 1)],
+        [(-2, 0), (1, 1), (0, 0), (-1, -1)],
+        [(0, -2), (-1, 1), (0, 0), (1, -1)],
+        [(2, 0), (-1, -1), (0, 0), (1, 1)],
+    ],
+    // Tetrimino::S
+    [
+        [(1, 1), (0, 2), (1, -1), (0, 0)],
+        [(-1, 1), (-2, 0), (1, 1), (0, 0)],
+        [(-1, -1), (0, -2), (-1, 1), (0, 0)],
+        [(1, -1), (2, 0), (-1, -1), (0, 0)],
+    ],
+    // Tetrimino::Z
+    [
+        [(2, 0), (1, 1), (0, 0), (-1, 1)],
+        [(0, 2), (-1, 1), (0, 0), (-1, -1)],
+        [(-2, 0), (-1, -1), (0, 0), (1, -1)],
+        [(0, -2), (1, -1), (0, 0), (1, 1)],
+    ],
+];
+
+const SPAWN_TABLE: [[(i8, i8); 4]; 8] = [
+    // Empty
+    [(0, 0), (0, 0), (0, 0), (0, 0)],
+    // Tetrimino::I
+    // 01234
+    [(0, 1), (1, 1), (2, 1), (3, 1)],
+    // Tetrimino::O
+    // 01
+    // 23
+    [(0, 0), (1, 0), (0, 1), (1, 1)],
+    // Tetrimino::T
+    //  0
+    // 123
+    [(0, 0), (-1, 1), (0, 1), (1, 1)],
+    // Tetrimino::J
+    // 0
+    // 123
+    [(0, 0), (0, 1), (1, 1), (2, 1)],
+    // Tetrimino::L
+    //   0
+    // 123
+    [(0, 0), (-2, 1), (-1, 1), (0, 1)],
+    // Tetrimino::S
+    //  01
+    // 23
+    [(0, 0), (1, 0), (-1, 1), (0, 1)],
+    // Tetrimino::Z
+    // 01
+    //  23
+    [(0, 0), (1, 0), (1, 1), (2, 1)],
+];
+
+const WALL_KICK_JLSTZ_TABLE: [[(i8, i8); 5]; 4] = [
+    // test1 ... test5
+    [(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)], // D0  (D0 -> D90)
+    [(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)],   // D90 (D90 -> D180)
+    [(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)],    // D180 (D180 -> D270)
+    [(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)], // D270 (D270 -> D0)
+];
+
+const WALL_KICK_I_TABLE: [[(i8, i8); 5]; 4] = [
+    // test1 ... test5
+    [(0, 0), (-2, 0), (1, 0), (-2, 1), (1, -2)], // D0  (D0 -> D90)
+    [(0, 0), (-1, 0), (2, 0), (-1, -2), (2, 1)], // D90 (D90 -> D180)
+    [(0, 0), (2, 0), (-1, 0), (2, -1), (-1, 2)], // D180 (D180 -> D270)
+    [(0, 0), (1, 0), (-2, 0), (1, 2), (-2, -1)], // D270 (D270 -> D0)
+];
+
+fn srs_wall_kick_reference(
+    kind: Tetrimino,
+    rotate: Rotate,
+    dir: &RotateDirection,
+    test_idx: usize,
+) -> Option<(i8, i8)> {
+    match dir {
+        RotateDirection::Half if kind == Tetrimino::O => (test_idx == 0).then_some((0, 0)),
+        RotateDirection::Half => WALL_KICK_HALF_TABLE[rotate as usize].get(test_idx).copied(),
+        _ if test_idx >= 5 => None,
+        RotateDirection::Left => Some(srs_left_rotate_wall_kick_reference(kind, rotate, test_idx)),
+        RotateDirection::Right => {
+            Some(srs_right_rotate_wall_kick_reference(kind, rotate, test_idx))
+        }
+    }
+}
+
+fn srs_right_rotate_wall_kick_reference(
+    kind: Tetrimino,
+    rotate: Rotate,
+    test_idx: usize,
+) -> (i8, i8) {
+    let rot_idx = rotate as usize;
+    if kind == Tetrimino::I {
+        WALL_KICK_I_TABLE[rot_idx][test_idx]
+    } else {
+        WALL_KICK_JLSTZ_TABLE[rot_idx][test_idx]
+    }
+}
+
+fn srs_left_rotate_wall_kick_reference(
+    kind: Tetrimino,
+    rotate: Rotate,
+    test_idx: usize,
+) -> (i8, i8) {
+    let next = rotate.next_ccw();
+    let (dx, dy) = srs_right_rotate_wall_kick_reference(kind, next, test_idx);
+    (-dx, -dy)
+}
+
+fn srs_right_rotate_reference(kind: Tetrimino, rotate: Rotate, id: u8) -> (i8, i8) {
+    let kind_idx = kind as usize;
+    let rot_idx = rotate as usize;
+    let id_idx = id as usize;
+
+    RIGHT_ROTATE_TABLE[kind_idx][rot_idx][id_idx]
+}
+
+fn srs_left_rotate_reference(kind: Tetrimino, rotate: Rotate, id: u8) -> (i8, i8) {
+    let next = rotate.next_ccw();
+    let (dx, dy) = srs_right_rotate_reference(kind, next, id);
+    (-dx, -dy)
+}
+
+// SRS+ I 월킥, SRS 와 같은 순서로 [방향][회전 전 상태][테스트]
+// 왼쪽 회전은 오른쪽 회전을 좌우로 뒤집은 값이다
+const SRS_PLUS_WALL_KICK_I_TABLE: [[[(i8, i8); 5]; 4]; 2] = [
+    // Right
+    [
+        [(0, 0), (1, 0), (-2, 0), (-2, 1), (1, -2)], // D0 -> D90
+        [(0, 0), (-1, 0), (2, 0), (-1, -2), (2, 1)], // D90 -> D180
+        [(0, 0), (2, 0), (-1, 0), (2, -1), (-1, 2)], // D180 -> D270
+        [(0, 0), (1, 0), (-2, 0), (1, -2), (-2, 1)], // D270 -> D0
+    ],
+    // Left
+    [
+        [(0, 0), (-1, 0), (2, 0), (2, 1), (-1, -2)], // D0 -> D270
+        [(0, 0), (-1, 0), (2, 0), (-1, -2), (2, 1)], // D90 -> D0
+        [(0, 0), (-2, 0), (1, 0), (-2, -1), (1, 2)], // D180 -> D90
+        [(0, 0), (1, 0), (-2, 0), (1, -2), (-2, 1)], // D270 -> D180
+    ],
+];
+
+// 180도 회전 월킥, TETR.IO 와 같은 값이고 O 를 뺀 모든 블록이 같이 쓴다
+const WALL_KICK_HALF_TABLE: [[(i8, i8); 6]; 4] = [
+    [(0, 0), (0, -1), (1, -1), (-1, -1), (1, 0), (-1, 0)], // D0 -> D180
+    [(0, 0), (1, 0), (1, -2), (1, -1), (0, -2), (0, -1)],  // D90 -> D270
+    [(0, 0), (0, 1), (-1, 1), (1, 1), (-1, 0), (1, 0)],    // D180 -> D0
+    [(0, 0), (-1, 0), (-1, -2), (-1, -1), (0, -2), (0, -1)], // D270 -> D90
+];
+
+// 제자리, 오른쪽 1칸, 왼쪽 1칸
+const ARS_WALL_KICK_TABLE: [(i8, i8); 3] = [(0, 0), (1, 0), (-1, 0)];
+
+/// 모양 표로 정의하는 회전 방식
+struct ShapeTable {
+    /// [블록][회전 상태][블록 번호], 3x3(I 는 4x4) 상자 안의 위치
+    shapes: [[[(i8, i8); 4]; 4]; 8],
+    /// 스폰 지점에서 상자 왼쪽 위까지
+    origins: [(i8, i8); 8],
+    spawn_rotations: [Rotate; 8],
+}
+
+fn shape_spawn(table: &ShapeTable, kind: Tetrimino) -> (Rotate, [(i8, i8); 4]) {
+    let rotation = table.spawn_rotations[kind as usize];
+    let (ox, oy) = table.origins[kind as usize];
+    let cells = table.shapes[kind as usize][rotation as usize].map(|(x, y)| (x + ox, y + oy));
+    (rotation, cells)
+}
+
+fn shape_rotate_offset(
+    table: &ShapeTable,
+    kind: Tetrimino,
+    from: Rotate,
+    dir: &RotateDirection,
+    id: u8,
+) -> (i8, i8) {
+    let to = from.next(dir);
+    let shapes = &table.shapes[kind as usize];
+    let (fx, fy) = shapes[from as usize][id as usize];
+    let (tx, ty) = shapes[to as usize][id as usize];
+    (tx - fx, ty - fy)
+}
+
+const EMPTY_SHAPE: [[(i8, i8); 4]; 4] = [[(0, 0); 4]; 4];
+const O_SHAPE: [(i8, i8); 4] = [(1, 1), (2, 1), (1, 2), (2, 2)];
+
+// T, J, L 은 평평한 면이 위인 D180 으로 스폰하고 바닥에 붙어서 회전한다
+// T 는 가운데 칸이 항상 2번 블록이다
+const ARS_SHAPE_TABLE: ShapeTable = ShapeTable {
+    shapes: [
+        EMPTY_SHAPE,
+        // I
+        [
+            [(0, 1), (1, 1), (2, 1), (3, 1)],
+            [(2, 0), (2, 1), (2, 2), (2, 3)],
+            [(0, 1), (1, 1), (2, 1), (3, 1)],
+            [(2, 0), (2, 1), (2, 2), (2, 3)],
+        ],
+        // O
+        [O_SHAPE; 4],
+        // T
+        [
+            [(1, 1), (0, 2), (1, 2), (2, 2)],
+            [(2, 1), (1, 0), (1, 1), (1, 2)],
+            [(1, 2), (0, 1), (1, 1), (2, 1)],
+            [(0, 1), (1, 0), (1, 1), (1, 2)],
+        ],
+        // J
+        [
+            [(0, 1), (0, 2), (1, 2), (2, 2)],
+            [(1, 0), (2, 0), (1, 1), (1, 2)],
+            [(0, 1), (1, 1), (2, 1), (2, 2)],
+            [(1, 0), (1, 1), (0, 2), (1, 2)],
+        ],
+        // L
+        [
+            [(2, 1), (0, 2), (1, 2), (2, 2)],
+            [(1, 0), (1, 1), (1, 2), (2, 2)],
+            [(0, 1), (1, 1), (2, 1), (0, 2)],
+            [(0, 0), (1, 0), (1, 1), (1, 2)],
+        ],
+        // S
+        [
+            [(1, 1), (2, 1), (0, 2), (1, 2)],
+            [(0, 0), (0, 1), (1, 1), (1, 2)],
+            [(1, 1), (2, 1), (0, 2), (1, 2)],
+            [(0, 0), (0, 1), (1, 1), (1, 2)],
+        ],
+        // Z
+        [
+            [(0, 1), (1, 1), (1, 2), (2, 2)],
+            [(2, 0), (1, 1), (2, 1), (1, 2)],
+            [(0, 1), (1, 1), (1, 2), (2, 2)],
+            [(2, 0), (1, 1), (2, 1), (1, 2)],
+        ],
+    ],
+    origins: [
+        (0, 0),
+        (0, 0),
+        (-1, -1),
+        (-1, -1),
+        (0, -1),
+        (-2, -1),
+        (-1, -1),
+        (0, -1),
+    ],
+    spawn_rotations: [
+        Rotate::D0,
+        Rotate::D0,
+        Rotate::D0,
+        Rotate::D180,
+        Rotate::D180,
+        Rotate::D180,
+        Rotate::D0,
+        Rotate::D0,
+    ],
+};
+
+// 가운데 칸을 중심으로 그대로 돌린다, I, S, Z 는 상태가 2개뿐이다
+const NES_SHAPE_TABLE: ShapeTable = ShapeTable {
+    shapes: [
+        EMPTY_SHAPE,
+        // I
+        [
+            [(0, 2), (1, 2), (2, 2), (3, 2)],
+            [(2, 0), (2, 1), (2, 2), (2, 3)],
+            [(0, 2), (1, 2), (2, 2), (3, 2)],
+            [(2, 0), (2, 1), (2, 2), (2, 3)],
+        ],
+        // O
+        [O_SHAPE; 4],
+        // T
+        [
+            [(1, 0), (2, 1), (1, 1), (0, 1)],
+            [(2, 1), (1, 2), (1, 1), (1, 0)],
+            [(1, 2), (0, 1), (1, 1), (2, 1)],
+            [(0, 1), (1, 0), (1, 1), (1, 2)],
+        ],
+        // J
+        [
+            [(2, 1), (1, 1), (0, 1), (0, 0)],
+            [(1, 2), (1, 1), (1, 0), (2, 0)],
+            [(0, 1), (1, 1), (2, 1), (2, 2)],
+            [(1, 0), (1, 1), (1, 2), (0, 2)],
+        ],
+        // L
+        [
+            [(2, 1), (1, 1), (0, 1), (2, 0)],
+            [(1, 2), (1, 1), (1, 0), (2, 2)],
+            [(0, 1), (1, 1), (2, 1), (0, 2)],
+            [(1, 0), (1, 1), (1, 2), (0, 0)],
+        ],
+        // S
+        [
+            [(1, 1), (2, 1), (0, 2), (1, 2)],
+            [(1, 0), (1, 1), (2, 1), (2, 2)],
+            [(1, 1), (2, 1), (0, 2), (1, 2)],
+            [(1, 0), (1, 1), (2, 1), (2, 2)],
+        ],
+        // Z
+        [
+            [(0, 1), (1, 1), (1, 2), (2, 2)],
+            [(2, 0), (1, 1), (2, 1), (1, 2)],
+            [(0, 1), (1, 1), (1, 2), (2, 2)],
+            [(2, 0), (1, 1), (2, 1), (1, 2)],
+        ],
+    ],
+    origins: [
+        (0, 0),
+        (0, -1),
+        (-1, -1),
+        (-1, -1),
+        (0, -1),
+        (-2, -1),
+        (-1, -1),
+        (0, -1),
+    ],
+    spawn_rotations: [
+        Rotate::D0,
+        Rotate::D0,
+        Rotate::D0,
+        Rotate::D180,
+        Rotate::D180,
+        Rotate::D180,
+        Rotate::D0,
+        Rotate::D0,
+    ],
+};
@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{LockResult, TSpin, Tetrimino};
+
+/// 블록 하나를 고정했을 때의 줄 삭제 결과
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct ClearResult {
+    pub kind: Tetrimino,
+    pub lines: u8,
+    pub tspin: TSpin,
+    pub is_perfect_clear: bool,
+    /// 연속 B2B 횟수, 어려운 클리어가 처음 나오면 0
+    pub b2b: Option<u32>,
+    /// 연속으로 줄을 지운 횟수, 첫 클리어가 0
+    pub combo: Option<u32>,
+}
+
+impl ClearResult {
+    /// 테트리스와 줄을 지운 T-스핀(미니 포함)은 B2B 를 이어간다
+    pub fn is_difficult(&self) -> bool {
+        self.lines >= 4 || (self.lines > 0 && self.tspin != TSpin::None)
+    }
+
+    /// 앞에 어려운 클리어가 있었고 이번에도 어려운 클리어인 경우
+    pub fn is_b2b(&self) -> bool {
+        self.is_difficult() && self.b2b.is_some_and(|c| c > 0)
+    }
+}
+
+/// 고정 사이에 이어지는 콤보와 B2B 상태
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct ClearChain {
+    pub combo: Option<u32>,
+    pub b2b: Option<u32>,
+}
+
+impl ClearChain {
+    pub fn clear(&mut self, lock: LockResult) -> ClearResult {
+        let LockResult {
+            kind,
+            tspin,
+            lines,
+            is_perfect_clear,
+        } = lock;
+        let mut result = ClearResult {
+            kind,
+            lines,
+            tspin,
+            is_perfect_clear,
+            b2b: None,
+            combo: None,
+        };
+
+        if lines == 0 {
+            // 줄을 안 지운 T-스핀은 B2B 를 끊지 않는다
+            self.combo = None;
+            result.b2b = self.b2b;
+            return result;
+        }
+        self.combo = Some(self.combo.map_or(0, |c| c + 1));
+        if result.is_difficult() {
+            self.b2b = Some(self.b2b.map_or(0, |c| c + 1));
+        } else {
+            self.b2b = None;
+        }
+        result.combo = self.combo;
+        result.b2b = self.b2b;
+        result
+    }
+}
+
+/// 점수표, 값은 레벨 1 기준이다
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct ScoreTable {
+    /// 0 ~ 4 줄
+    pub normal: [u32; 5],
+    pub tspin_mini: [u32; 3],
+    pub tspin: [u32; 4],
+    /// 1 ~ 4 줄, 0 번은 B2B 테트리스 퍼펙트 클리어
+    pub perfect_clear: [u32; 5],
+    pub combo: u32,
+    /// B2B 일 때 곱하는 비율 (분자, 분모)
+    pub b2b_ratio: (u32, u32),
+}
+
+impl Default for ScoreTable {
+    fn default() -> Self {
+        Self {
+            normal: [0, 100, 300, 500, 800],
+            tspin_mini: [100, 200, 400],
+            tspin: [400, 800, 1200, 1600],
+            perfect_clear: [3200, 800, 1200, 1800, 2000],
+            combo: 50,
+            b2b_ratio: (3, 2),
+        }
+    }
+}
+
+impl ScoreTable {
+    pub fn score(&self, result: &ClearResult, level: u32) -> u32 {
+        let lines = result.lines as usize;
+        let mut score = match result.tspin {
+            TSpin::Full => self.tspin[lines.min(3)],
+            TSpin::Mini => self.tspin_mini[lines.min(2)],
+            TSpin::None => self.normal[lines.min(4)],
+        };
+        if result.is_b2b() {
+            score = score * self.b2b_ratio.0 / self.b2b_ratio.1;
+        }
+        if result.is_perfect_clear && lines > 0 {
+            score += if lines >= 4 && result.is_b2b() {
+                self.perfect_clear[0]
+            } else {
+                self.perfect_clear[lines.min(4)]
+            };
+        }
+        score += self.combo * result.combo.unwrap_or(0);
+        score * level
+    }
+}
+
+/// 대전에서 상대에게 보내는 방해줄 표
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub struct AttackTable {
+    /// 0 ~ 4 줄
+    pub normal: [u32; 5],
+    pub tspin_mini: [u32; 3],
+    pub tspin: [u32; 4],
+    pub perfect_clear: u32,
+    pub b2b_bonus: u32,
+    /// 콤보 횟수별 추가 줄, 넘어가면 마지막 값
+    pub combo: Vec<u32>,
+}
+
+impl Default for AttackTable {
+    fn default() -> Self {
+        Self {
+            normal: [0, 0, 1, 2, 4],
+            tspin_mini: [0, 0, 1],
+            tspin: [0, 2, 4, 6],
+            perfect_clear: 10,
+            b2b_bonus: 1,
+            combo: vec![0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 4, 5],
+        }
+    }
+}
+
+impl AttackTable {
+    pub fn attack(&self, result: &ClearResult) -> u32 {
+        if result.lines == 0 {
+            return 0;
+        }
+        let lines = result.lines as usize;
+        let mut attack = match result.tspin {
+            TSpin::Full => self.tspin[lines.min(3)],
+            TSpin::Mini => self.tspin_mini[lines.min(2)],
+            TSpin::None => self.normal[lines.min(4)],
+        };
+        if result.is_b2b() {
+            attack += self.b2b_bonus;
+        }
+        if let Some(combo) = result.combo {
+            attack += self
+                .combo
+                .get(combo as usize)
+                .or(self.combo.last())
+                .copied()
+                .unwrap_or(0);
+        }
+        if result.is_perfect_clear {
+            attack += self.perfect_clear;
+        }
+        attack
+    }
+}
@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ActivePiece, BitBoard, Board, FallingBlockAt, Rotate, RotateDirection, Tetrimino, Tile,
+};
+
+/// SRS 마지막 월킥 테스트, 이 킥으로 들어가면 미니 조건이어도 T-스핀이다
+const TST_KICK_IDX: usize = 4;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(ts_rs::TS))]
+#[cfg_attr(feature = "wasm", ts(export))]
+pub enum TSpin {
+    #[default]
+    None,
+    Mini,
+    Full,
+}
+
+impl Board {
+    /// 마지막으로 성공한 동작이 회전이었으면 그때 쓴 월킥 테스트 번호
+    pub fn last_kick(&self) -> Option<usize> {
+        self.last_rotation.as_ref().map(|(_, kick_idx)| *kick_idx)
+    }
+
+    /// 지금 떨어지는 블록이 고정되면 T-스핀인지 3코너 규칙으로 판정한다
+    pub fn detect_tspin(&self) -> TSpin {
+        let Some((dir, kick_idx)) = &self.last_rotation else {
+            return TSpin::None;
+        };
+        let fallings = self.get_falling_blocks();
+        // T 블록은 2번이 중심
+        let Some(FallingBlockAt { falling, location }) = fallings
+            .iter()
+            .find(|f| f.falling.kind == Tetrimino::T && f.falling.id == 2)
+        else {
+            return TSpin::None;
+        };
+        let (x, y) = (location.x as isize, location.y as isize);
+
+        // 좌상, 우상, 우하, 좌하
+        let corners = [
+            (x - 1, y - 1),
+            (x + 1, y - 1),
+            (x + 1, y + 1),
+            (x - 1, y + 1),
+        ]
+        .map(|(cx, cy)| self.is_corner_filled(cx, cy));
+        tspin_from_corners(corners, falling.rotation, dir, *kick_idx)
+    }
+
+    /// 벽과 바닥도 채워진 것으로 본다
+    fn is_corner_filled(&self, x: isize, y: isize) -> bool {
+        if x < 0 || y < 0 || x >= self.x_len() as isize || y >= self.y_len() as isize {
+            return true;
+        }
+        matches!(self.get(x as usize, y as usize), Some(Tile::Placed(_)))
+    }
+}
+
+impl BitBoard {
+    /// [`Board::detect_tspin`] 과 같은 규칙, 마지막 회전은 호출하는 쪽에서 넘긴다
+    pub fn detect_tspin(
+        &self,
+        piece: &ActivePiece,
+        last_rotation: Option<&(RotateDirection, usize)>,
+    ) -> TSpin {
+        let Some((dir, kick_idx)) = last_rotation else {
+            return TSpin::None;
+        };
+        if piece.kind != Tetrimino::T {
+            return TSpin::None;
+        }
+        let (x, y) = self.cells(piece)[2];
+        let corners = [
+            (x - 1, y - 1),
+            (x + 1, y - 1),
+            (x + 1, y + 1),
+            (x - 1, y + 1),
+        ]
+        .map(|(cx, cy)| self.is_filled(cx, cy));
+        tspin_from_corners(corners, piece.rotation, dir, *kick_idx)
+    }
+}
+
+/// 코너는 좌상, 우상, 우하, 좌하 순서
+fn tspin_from_corners(
+    corners: [bool; 4],
+    rotation: Rotate,
+    dir: &RotateDirection,
+    kick_idx: usize,
+) -> TSpin {
+    if corners.iter().filter(|c| **c).count() < 3 {
+        return TSpin::None;
+    }
+
+    // 튀어나온 쪽 두 코너
+    let (front_a, front_b) = match rotation {
+        Rotate::D0 => (0, 1),
+        Rotate::D90 => (1, 2),
+        Rotate::D180 => (2, 3),
+        Rotate::D270 => (3, 0),
+    };
+    let is_tst_kick = *dir != RotateDirection::Half && kick_idx == TST_KICK_IDX;
+    if (corners[front_a] && corners[front_b]) || is_tst_kick {
+        TSpin::Full
+    } else {
+        TSpin::Mini
+    }
+}
@@ -9,7 +9,9 @@ fn activated_rotation(board: &Board) -> Rotate {
 #[test]
 fn spawn_i() {
     let mut board = Board::new(10, 3);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::I).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::I).unwrap())
+        .unwrap();
     let expected = r#"
 ___________
 0..........
@@ -24,7 +26,9 @@ _0123456789
 #[test]
 fn spawn_o() {
     let mut board = Board::new(10, 3);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::O).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::O).unwrap())
+        .unwrap();
     let expected = r#"
 ___________
 0..........
@@ -39,7 +43,9 @@ _0123456789
 #[test]
 fn spawn_t() {
     let mut board = Board::new(10, 3);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap())
+        .unwrap();
     let expected = r#"
 ___________
 0..........
@@ -54,7 +60,9 @@ _0123456789
 #[test]
 fn spawn_t_2() {
     let mut board = Board::new(10, 26);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap())
+        .unwrap();
     println!("{board}")
     //     let expected = r#"
     // ___________
@@ -70,7 +78,9 @@ fn spawn_t_2() {
 #[test]
 fn spawn_j() {
     let mut board = Board::new(10, 3);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::J).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::J).unwrap())
+        .unwrap();
     let expected = r#"
 ___________
 0..........
@@ -85,7 +95,9 @@ _0123456789
 #[test]
 fn spawn_l() {
     let mut board = Board::new(10, 3);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::L).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::L).unwrap())
+        .unwrap();
     let expected = r#"
 ___________
 0..........
@@ -100,7 +112,9 @@ _0123456789
 #[test]
 fn spawn_s() {
     let mut board = Board::new(10, 3);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::S).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::S).unwrap())
+        .unwrap();
     let expected = r#"
 ___________
 0..........
@@ -115,7 +129,9 @@ _0123456789
 #[test]
 fn spawn_z() {
     let mut board = Board::new(10, 3);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap())
+        .unwrap();
     let expected = r#"
 ___________
 0..........
@@ -130,7 +146,9 @@ _0123456789
 #[test]
 fn rotate_right_i() {
     let mut board = Board::new(10, 5);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::I).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::I).unwrap())
+        .unwrap();
 
     let expted_origin = r#"
 ___________
@@ -144,7 +162,9 @@ _0123456789
     assert_eq!(format!("{board}"), expted_origin);
     assert_eq!(activated_rotation(&board), Rotate::D0);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
 
     let expexted_to_d0_d90 = r#"
 ___________
@@ -158,7 +178,9 @@ _0123456789
     assert_eq!(format!("{board}"), expexted_to_d0_d90);
     assert_eq!(activated_rotation(&board), Rotate::D90);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
 
     let expexted_to_d90_d180 = r#"
 ___________
@@ -172,7 +194,9 @@ _0123456789
     assert_eq!(format!("{board}"), expexted_to_d90_d180);
     assert_eq!(activated_rotation(&board), Rotate::D180);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
 
     let expexted_to_d180_d270 = r#"
 ___________
@@ -186,7 +210,9 @@ _0123456789
     assert_eq!(format!("{board}"), expexted_to_d180_d270);
     assert_eq!(activated_rotation(&board), Rotate::D270);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
     let expexted_to_d270_d0 = r#"
 ___________
 0..........
@@ -202,77 +228,51 @@ _0123456789
 
 #[test]
 fn wall_kick_j_0() {
-    let mut board = Board::new(10, 7);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::J).unwrap());
-    *board.location_mut(0, 6) = Tile::Placed(1);
-    *board.location_mut(1, 6) = Tile::Placed(1);
-    *board.location_mut(2, 6) = Tile::Placed(1);
-    *board.location_mut(3, 6) = Tile::Placed(1);
-    *board.location_mut(4, 6) = Tile::Placed(1);
-    *board.location_mut(6, 6) = Tile::Placed(1);
-    *board.location_mut(7, 6) = Tile::Placed(1);
-    *board.location_mut(8, 6) = Tile::Placed(1);
-    *board.location_mut(9, 6) = Tile::Placed(1);
-
-    *board.location_mut(0, 5) = Tile::Placed(1);
-    *board.location_mut(1, 5) = Tile::Placed(1);
-    *board.location_mut(2, 5) = Tile::Placed(1);
-    *board.location_mut(3, 5) = Tile::Placed(1);
-    *board.location_mut(6, 5) = Tile::Placed(1);
-    *board.location_mut(7, 5) = Tile::Placed(1);
-    *board.location_mut(8, 5) = Tile::Placed(1);
-    *board.location_mut(9, 5) = Tile::Placed(1);
-
-    *board.location_mut(0, 4) = Tile::Placed(1);
-    *board.location_mut(1, 4) = Tile::Placed(1);
-    *board.location_mut(6, 4) = Tile::Placed(1);
-    *board.location_mut(7, 4) = Tile::Placed(1);
-    *board.location_mut(8, 4) = Tile::Placed(1);
-    *board.location_mut(9, 4) = Tile::Placed(1);
-
-    *board.location_mut(1, 3) = Tile::Placed(1);
-    *board.location_mut(2, 3) = Tile::Placed(1);
-    *board.location_mut(3, 3) = Tile::Placed(1);
-    *board.location_mut(7, 3) = Tile::Placed(1);
-    *board.location_mut(8, 3) = Tile::Placed(1);
-    *board.location_mut(9, 3) = Tile::Placed(1);
-
-    *board.location_mut(6, 2) = Tile::Placed(1);
-    *board.location_mut(7, 2) = Tile::Placed(1);
-    *board.location_mut(8, 2) = Tile::Placed(1);
-    *board.location_mut(9, 2) = Tile::Placed(1);
-
-    *board.location_mut(5, 1) = Tile::Placed(1);
-    *board.location_mut(6, 1) = Tile::Placed(1);
-    *board.location_mut(7, 1) = Tile::Placed(1);
-
-    *board.location_mut(4, 0) = Tile::Placed(1);
-    *board.location_mut(5, 0) = Tile::Placed(1);
+    let mut board: Board = r#"
+___________
+0....ii....
+1...J.iii..
+2...JJJiiii
+3.iii...iii
+4ii....iiii
+5iiii..iiii
+6iiiii.iiii
+_0123456789
+"#
+    .parse()
+    .unwrap();
+    assert_eq!(board.active_piece(), {
+        let mut spawned = Board::new(10, 7);
+        spawned
+            .apply_spawn_falling(spawned.try_spawn_falling(Tetrimino::J).unwrap())
+            .unwrap();
+        spawned.active_piece()
+    });
     // println!("O{board}");
     let expted = r#"
 ___________
-0....PP....
-1...J.PPP..
-2...JJJPPPP
-3.PPP...PPP
-4PP....PPPP
-5PPPP..PPPP
-6PPPPP.PPPP
+0....ii....
+1...J.iii..
+2...JJJiiii
+3.iii...iii
+4ii....iiii
+5iiii..iiii
+6iiiii.iiii
 _0123456789
 "#;
     assert_eq!(format!("{board}"), expted);
     let plan = board.try_rotate_falling(RotateDirection::Left).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
     // println!("O{board}");
     let expted = r#"
 ___________
-0....PP....
-1.....PPP..
-2......PPPP
-3.PPP.J.PPP
-4PP...JPPPP
-5PPPPJJPPPP
-6PPPPP.PPPP
+0....ii....
+1.....iii..
+2......iiii
+3.iii.J.iii
+4ii...Jiiii
+5iiiiJJiiii
+6iiiii.iiii
 _0123456789
 "#;
     assert_eq!(format!("{board}"), expted);
@@ -281,20 +281,22 @@ _0123456789
 #[test]
 fn wall_kick_l_1() {
     let mut board = Board::new(10, 7);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::L).unwrap());
-    *board.location_mut(5, 3) = Tile::Placed(1);
-    *board.location_mut(2, 3) = Tile::Placed(1);
-    *board.location_mut(2, 2) = Tile::Placed(1);
-    *board.location_mut(2, 1) = Tile::Placed(1);
-    *board.location_mut(2, 0) = Tile::Placed(1);
-    *board.location_mut(3, 0) = Tile::Placed(1);
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::L).unwrap())
+        .unwrap();
+    *board.get_mut(5, 3).unwrap() = Tile::Placed(1);
+    *board.get_mut(2, 3).unwrap() = Tile::Placed(1);
+    *board.get_mut(2, 2).unwrap() = Tile::Placed(1);
+    *board.get_mut(2, 1).unwrap() = Tile::Placed(1);
+    *board.get_mut(2, 0).unwrap() = Tile::Placed(1);
+    *board.get_mut(3, 0).unwrap() = Tile::Placed(1);
     // println!("O{board}");
     let expted = r#"
 ___________
-0..PP......
-1..P..L....
-2..PLLL....
-3..P..P....
+0..ii......
+1..i..L....
+2..iLLL....
+3..i..i....
 4..........
 5..........
 6..........
@@ -302,14 +304,14 @@ _0123456789
 "#;
     assert_eq!(format!("{board}"), expted);
     let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
     // println!("O{board}");
     let expted = r#"
 ___________
-0..PP......
-1..PL......
-2..PL......
-3..PLLP....
+0..ii......
+1..iL......
+2..iL......
+3..iLLi....
 4..........
 5..........
 6..........
@@ -321,47 +323,49 @@ _0123456789
 #[test]
 fn wall_kick_l_2() {
     let mut board = Board::new(10, 7);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::L).unwrap());
-    *board.location_mut(3, 1) = Tile::Placed(1);
-    *board.location_mut(2, 1) = Tile::Placed(1);
-    *board.location_mut(2, 2) = Tile::Placed(1);
-    *board.location_mut(2, 3) = Tile::Placed(1);
-    *board.location_mut(2, 4) = Tile::Placed(1);
-    *board.location_mut(2, 5) = Tile::Placed(1);
-    *board.location_mut(5, 5) = Tile::Placed(1);
-    *board.location_mut(6, 5) = Tile::Placed(1);
-    *board.location_mut(4, 4) = Tile::Placed(1);
-    *board.location_mut(5, 4) = Tile::Placed(1);
-    *board.location_mut(6, 4) = Tile::Placed(1);
-
-    *board.location_mut(4, 3) = Tile::Placed(1);
-    *board.location_mut(5, 3) = Tile::Placed(1);
-    *board.location_mut(6, 3) = Tile::Placed(1);
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::L).unwrap())
+        .unwrap();
+    *board.get_mut(3, 1).unwrap() = Tile::Placed(1);
+    *board.get_mut(2, 1).unwrap() = Tile::Placed(1);
+    *board.get_mut(2, 2).unwrap() = Tile::Placed(1);
+    *board.get_mut(2, 3).unwrap() = Tile::Placed(1);
+    *board.get_mut(2, 4).unwrap() = Tile::Placed(1);
+    *board.get_mut(2, 5).unwrap() = Tile::Placed(1);
+    *board.get_mut(5, 5).unwrap() = Tile::Placed(1);
+    *board.get_mut(6, 5).unwrap() = Tile::Placed(1);
+    *board.get_mut(4, 4).unwrap() = Tile::Placed(1);
+    *board.get_mut(5, 4).unwrap() = Tile::Placed(1);
+    *board.get_mut(6, 4).unwrap() = Tile::Placed(1);
+
+    *board.get_mut(4, 3).unwrap() = Tile::Placed(1);
+    *board.get_mut(5, 3).unwrap() = Tile::Placed(1);
+    *board.get_mut(6, 3).unwrap() = Tile::Placed(1);
 
     // println!("O{board}");
     let expted = r#"
 ___________
 0..........
-1..PP.L....
-2..PLLL....
-3..P.PPP...
-4..P.PPP...
-5..P..PP...
+1..ii.L....
+2..iLLL....
+3..i.iii...
+4..i.iii...
+5..i..ii...
 6..........
 _0123456789
 "#;
     assert_eq!(format!("{board}"), expted);
     let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
     // println!("O{board}");
     let expted = r#"
 ___________
 0..........
-1..PP......
-2..P.......
-3..PLPPP...
-4..PLPPP...
-5..PLLPP...
+1..ii......
+2..i.......
+3..iLiii...
+4..iLiii...
+5..iLLii...
 6..........
 _0123456789
 "#;
@@ -371,48 +375,50 @@ _0123456789
 #[test]
 fn wall_kick_t_1() {
     let mut board = Board::new(10, 7);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap())
+        .unwrap();
 
-    *board.location_mut(2, 1) = Tile::Placed(1);
-    *board.location_mut(3, 1) = Tile::Placed(1);
+    *board.get_mut(2, 1).unwrap() = Tile::Placed(1);
+    *board.get_mut(3, 1).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(2, 2) = Tile::Placed(1);
+    *board.get_mut(2, 2).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(2, 3) = Tile::Placed(1);
-    *board.location_mut(4, 3) = Tile::Placed(1);
-    *board.location_mut(5, 3) = Tile::Placed(1);
+    *board.get_mut(2, 3).unwrap() = Tile::Placed(1);
+    *board.get_mut(4, 3).unwrap() = Tile::Placed(1);
+    *board.get_mut(5, 3).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(2, 4) = Tile::Placed(1);
-    *board.location_mut(5, 4) = Tile::Placed(1);
+    *board.get_mut(2, 4).unwrap() = Tile::Placed(1);
+    *board.get_mut(5, 4).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(2, 5) = Tile::Placed(1);
-    *board.location_mut(4, 5) = Tile::Placed(1);
+    *board.get_mut(2, 5).unwrap() = Tile::Placed(1);
+    *board.get_mut(4, 5).unwrap() = Tile::Placed(1);
 
     // println!("O{board}");
 
     let expted = r#"
 ___________
 0..........
-1..PPT.....
-2..PTTT....
-3..P.PP....
-4..P..P....
-5..P.P.....
+1..iiT.....
+2..iTTT....
+3..i.ii....
+4..i..i....
+5..i.i.....
 6..........
 _0123456789
 "#;
     assert_eq!(format!("{board}"), expted);
     let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
     // println!("O{board}");
     let expted = r#"
 ___________
 0..........
-1..PP......
-2..P.......
-3..PTPP....
-4..PTTP....
-5..PTP.....
+1..ii......
+2..i.......
+3..iTii....
+4..iTTi....
+5..iTi.....
 6..........
 _0123456789
 "#;
@@ -422,29 +428,31 @@ _0123456789
 #[test]
 fn wall_kick_t_2() {
     let mut board = Board::new(10, 7);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap())
+        .unwrap();
 
-    *board.location_mut(2, 0) = Tile::Placed(1);
-    *board.location_mut(3, 0) = Tile::Placed(1);
+    *board.get_mut(2, 0).unwrap() = Tile::Placed(1);
+    *board.get_mut(3, 0).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(2, 1) = Tile::Placed(1);
+    *board.get_mut(2, 1).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(2, 2) = Tile::Placed(1);
+    *board.get_mut(2, 2).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(2, 3) = Tile::Placed(1);
+    *board.get_mut(2, 3).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(4, 3) = Tile::Placed(1);
-    *board.location_mut(5, 3) = Tile::Placed(1);
-    *board.location_mut(6, 3) = Tile::Placed(1);
+    *board.get_mut(4, 3).unwrap() = Tile::Placed(1);
+    *board.get_mut(5, 3).unwrap() = Tile::Placed(1);
+    *board.get_mut(6, 3).unwrap() = Tile::Placed(1);
 
     // println!("O{board}");
 
     let expted = r#"
 ___________
-0..PP......
-1..P.T.....
-2..PTTT....
-3..P.PPP...
+0..ii......
+1..i.T.....
+2..iTTT....
+3..i.iii...
 4..........
 5..........
 6..........
@@ -452,14 +460,14 @@ _0123456789
 "#;
     assert_eq!(format!("{board}"), expted);
     let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
     // println!("O{board}");
     let expted = r#"
 ___________
-0..PP......
-1..PT......
-2..PTT.....
-3..PTPPP...
+0..ii......
+1..iT......
+2..iTT.....
+3..iTiii...
 4..........
 5..........
 6..........
@@ -471,28 +479,30 @@ _0123456789
 #[test]
 fn wall_kick_t_3() {
     let mut board = Board::new(10, 7);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap())
+        .unwrap();
     let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
 
-    *board.location_mut(2, 1) = Tile::Placed(1);
-    *board.location_mut(3, 1) = Tile::Placed(1);
+    *board.get_mut(2, 1).unwrap() = Tile::Placed(1);
+    *board.get_mut(3, 1).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(2, 2) = Tile::Placed(1);
-    *board.location_mut(6, 2) = Tile::Placed(1);
+    *board.get_mut(2, 2).unwrap() = Tile::Placed(1);
+    *board.get_mut(6, 2).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(2, 3) = Tile::Placed(1);
-    *board.location_mut(3, 3) = Tile::Placed(1);
-    *board.location_mut(5, 3) = Tile::Placed(1);
-    *board.location_mut(6, 3) = Tile::Placed(1);
+    *board.get_mut(2, 3).unwrap() = Tile::Placed(1);
+    *board.get_mut(3, 3).unwrap() = Tile::Placed(1);
+    *board.get_mut(5, 3).unwrap() = Tile::Placed(1);
+    *board.get_mut(6, 3).unwrap() = Tile::Placed(1);
 
     // println!("O{board}");
     let expted = r#"
 ___________
 0..........
-1..PPT.....
-2..P.TTP...
-3..PPTPP...
+1..iiT.....
+2..i.TTi...
+3..iiTii...
 4..........
 5..........
 6..........
@@ -500,14 +510,14 @@ _0123456789
 "#;
     assert_eq!(format!("{board}"), expted);
     let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
     // println!("O{board}");
     let expted = r#"
 ___________
 0..........
-1..PP......
-2..PTTTP...
-3..PPTPP...
+1..ii......
+2..iTTTi...
+3..iiTii...
 4..........
 5..........
 6..........
@@ -519,45 +529,47 @@ _0123456789
 #[test]
 fn wall_kick_t_4() {
     let mut board = Board::new(10, 7);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap())
+        .unwrap();
     let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
 
-    *board.location_mut(3, 2) = Tile::Placed(1);
-    *board.location_mut(6, 2) = Tile::Placed(1);
-    *board.location_mut(7, 2) = Tile::Placed(1);
+    *board.get_mut(3, 2).unwrap() = Tile::Placed(1);
+    *board.get_mut(6, 2).unwrap() = Tile::Placed(1);
+    *board.get_mut(7, 2).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(3, 3) = Tile::Placed(1);
-    *board.location_mut(7, 3) = Tile::Placed(1);
+    *board.get_mut(3, 3).unwrap() = Tile::Placed(1);
+    *board.get_mut(7, 3).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(3, 4) = Tile::Placed(1);
-    *board.location_mut(4, 4) = Tile::Placed(1);
-    *board.location_mut(6, 4) = Tile::Placed(1);
-    *board.location_mut(7, 4) = Tile::Placed(1);
+    *board.get_mut(3, 4).unwrap() = Tile::Placed(1);
+    *board.get_mut(4, 4).unwrap() = Tile::Placed(1);
+    *board.get_mut(6, 4).unwrap() = Tile::Placed(1);
+    *board.get_mut(7, 4).unwrap() = Tile::Placed(1);
 
     // println!("O{board}");
     let expted = r#"
 ___________
 0..........
 1....T.....
-2...PTTPP..
-3...PT..P..
-4...PP.PP..
+2...iTTii..
+3...iT..i..
+4...ii.ii..
 5..........
 6..........
 _0123456789
 "#;
     assert_eq!(format!("{board}"), expted);
     let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
     // println!("O{board}");
     let expted = r#"
 ___________
 0..........
 1..........
-2...P..PP..
-3...PTTTP..
-4...PPTPP..
+2...i..ii..
+3...iTTTi..
+4...iiTii..
 5..........
 6..........
 _0123456789
@@ -568,51 +580,53 @@ _0123456789
 #[test]
 fn wall_kick_t_5() {
     let mut board = Board::new(10, 7);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap())
+        .unwrap();
     let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
     let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
 
-    *board.location_mut(4, 1) = Tile::Placed(1);
-    *board.location_mut(5, 1) = Tile::Placed(1);
-    *board.location_mut(6, 1) = Tile::Placed(1);
+    *board.get_mut(4, 1).unwrap() = Tile::Placed(1);
+    *board.get_mut(5, 1).unwrap() = Tile::Placed(1);
+    *board.get_mut(6, 1).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(6, 2) = Tile::Placed(1);
+    *board.get_mut(6, 2).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(6, 3) = Tile::Placed(1);
+    *board.get_mut(6, 3).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(3, 4) = Tile::Placed(1);
-    *board.location_mut(6, 4) = Tile::Placed(1);
+    *board.get_mut(3, 4).unwrap() = Tile::Placed(1);
+    *board.get_mut(6, 4).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(3, 5) = Tile::Placed(1);
-    *board.location_mut(4, 5) = Tile::Placed(1);
-    *board.location_mut(6, 5) = Tile::Placed(1);
+    *board.get_mut(3, 5).unwrap() = Tile::Placed(1);
+    *board.get_mut(4, 5).unwrap() = Tile::Placed(1);
+    *board.get_mut(6, 5).unwrap() = Tile::Placed(1);
 
     // println!("O{board}");
     let expted = r#"
 ___________
 0..........
-1....PPP...
-2...TTTP...
-3....T.P...
-4...P..P...
-5...PP.P...
+1....iii...
+2...TTTi...
+3....T.i...
+4...i..i...
+5...ii.i...
 6..........
 _0123456789
 "#;
     assert_eq!(format!("{board}"), expted);
     let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
     // println!("O{board}");
     let expted = r#"
 ___________
 0..........
-1....PPP...
-2......P...
-3.....TP...
-4...PTTP...
-5...PPTP...
+1....iii...
+2......i...
+3.....Ti...
+4...iTTi...
+5...iiTi...
 6..........
 _0123456789
 "#;
@@ -622,54 +636,56 @@ _0123456789
 #[test]
 fn wall_kick_t_6() {
     let mut board = Board::new(10, 7);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap())
+        .unwrap();
     let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
     let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
 
-    *board.location_mut(4, 1) = Tile::Placed(1);
-    *board.location_mut(5, 1) = Tile::Placed(1);
-    *board.location_mut(6, 1) = Tile::Placed(1);
+    *board.get_mut(4, 1).unwrap() = Tile::Placed(1);
+    *board.get_mut(5, 1).unwrap() = Tile::Placed(1);
+    *board.get_mut(6, 1).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(6, 2) = Tile::Placed(1);
+    *board.get_mut(6, 2).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(5, 3) = Tile::Placed(1);
-    *board.location_mut(6, 3) = Tile::Placed(1);
+    *board.get_mut(5, 3).unwrap() = Tile::Placed(1);
+    *board.get_mut(6, 3).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(2, 4) = Tile::Placed(1);
-    *board.location_mut(5, 4) = Tile::Placed(1);
-    *board.location_mut(6, 4) = Tile::Placed(1);
+    *board.get_mut(2, 4).unwrap() = Tile::Placed(1);
+    *board.get_mut(5, 4).unwrap() = Tile::Placed(1);
+    *board.get_mut(6, 4).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(2, 5) = Tile::Placed(1);
-    *board.location_mut(3, 5) = Tile::Placed(1);
-    *board.location_mut(5, 5) = Tile::Placed(1);
-    *board.location_mut(6, 5) = Tile::Placed(1);
+    *board.get_mut(2, 5).unwrap() = Tile::Placed(1);
+    *board.get_mut(3, 5).unwrap() = Tile::Placed(1);
+    *board.get_mut(5, 5).unwrap() = Tile::Placed(1);
+    *board.get_mut(6, 5).unwrap() = Tile::Placed(1);
 
     // println!("O{board}");
     let expted = r#"
 ___________
 0..........
-1....PPP...
-2...TTTP...
-3....TPP...
-4..P..PP...
-5..PP.PP...
+1....iii...
+2...TTTi...
+3....Tii...
+4..i..ii...
+5..ii.ii...
 6..........
 _0123456789
 "#;
     assert_eq!(format!("{board}"), expted);
     let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
     // println!("O{board}");
     let expted = r#"
 ___________
 0..........
-1....PPP...
-2......P...
-3....TPP...
-4..PTTPP...
-5..PPTPP...
+1....iii...
+2......i...
+3....Tii...
+4..iTTii...
+5..iiTii...
 6..........
 _0123456789
 "#;
@@ -679,28 +695,30 @@ _0123456789
 #[test]
 fn wall_kick_t_7() {
     let mut board = Board::new(10, 7);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap())
+        .unwrap();
     let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
     let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
     let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
 
-    *board.location_mut(1, 2) = Tile::Placed(1);
-    *board.location_mut(2, 2) = Tile::Placed(1);
-    *board.location_mut(5, 2) = Tile::Placed(1);
+    *board.get_mut(1, 2).unwrap() = Tile::Placed(1);
+    *board.get_mut(2, 2).unwrap() = Tile::Placed(1);
+    *board.get_mut(5, 2).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(1, 3) = Tile::Placed(1);
-    *board.location_mut(5, 3) = Tile::Placed(1);
+    *board.get_mut(1, 3).unwrap() = Tile::Placed(1);
+    *board.get_mut(5, 3).unwrap() = Tile::Placed(1);
 
     // println!("O{board}");
     let expted = r#"
 ___________
 0..........
 1....T.....
-2.PPTTP....
-3.P..TP....
+2.iiTTi....
+3.i..Ti....
 4..........
 5..........
 6..........
@@ -708,14 +726,14 @@ _0123456789
 "#;
     assert_eq!(format!("{board}"), expted);
     let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
     // println!("O{board}");
     let expted = r#"
 ___________
 0..........
 1..........
-2.PPT.P....
-3.PTTTP....
+2.iiT.i....
+3.iTTTi....
 4..........
 5..........
 6..........
@@ -727,33 +745,35 @@ _0123456789
 #[test]
 fn wall_kick_t_8() {
     let mut board = Board::new(10, 7);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap())
+        .unwrap();
     let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
     let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
     let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
 
-    *board.location_mut(1, 1) = Tile::Placed(1);
-    *board.location_mut(2, 1) = Tile::Placed(1);
+    *board.get_mut(1, 1).unwrap() = Tile::Placed(1);
+    *board.get_mut(2, 1).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(1, 2) = Tile::Placed(1);
-    *board.location_mut(5, 2) = Tile::Placed(1);
+    *board.get_mut(1, 2).unwrap() = Tile::Placed(1);
+    *board.get_mut(5, 2).unwrap() = Tile::Placed(1);
 
-    *board.location_mut(1, 3) = Tile::Placed(1);
-    *board.location_mut(2, 3) = Tile::Placed(1);
-    *board.location_mut(3, 3) = Tile::Placed(1);
-    *board.location_mut(5, 3) = Tile::Placed(1);
+    *board.get_mut(1, 3).unwrap() = Tile::Placed(1);
+    *board.get_mut(2, 3).unwrap() = Tile::Placed(1);
+    *board.get_mut(3, 3).unwrap() = Tile::Placed(1);
+    *board.get_mut(5, 3).unwrap() = Tile::Placed(1);
 
     // println!("O{board}");
 
     let expted = r#"
 ___________
 0..........
-1.PP.T.....
-2.P.TTP....
-3.PPPTP....
+1.ii.T.....
+2.i.TTi....
+3.iiiTi....
 4..........
 5..........
 6..........
@@ -761,14 +781,14 @@ _0123456789
 "#;
     assert_eq!(format!("{board}"), expted);
     let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
-    board.apply_rotate_falling(plan);
+    board.apply_rotate_falling(plan).unwrap();
     // println!("O{board}");
     let expted = r#"
 ___________
 0..........
-1.PPT......
-2.PTTTP....
-3.PPP.P....
+1.iiT......
+2.iTTTi....
+3.iii.i....
 4..........
 5..........
 6..........
@@ -780,7 +800,9 @@ _0123456789
 #[test]
 fn rotate_right_t() {
     let mut board = Board::new(10, 4);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap())
+        .unwrap();
 
     let expted_origin = r#"
 ___________
@@ -793,7 +815,9 @@ _0123456789
     assert_eq!(format!("{board}"), expted_origin);
     assert_eq!(activated_rotation(&board), Rotate::D0);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
     let expexted_to_d0_d90 = r#"
 ___________
 0..........
@@ -805,7 +829,9 @@ _0123456789
     assert_eq!(format!("{board}"), expexted_to_d0_d90);
     assert_eq!(activated_rotation(&board), Rotate::D90);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
     let expexted_to_d90_d180 = r#"
 ___________
 0..........
@@ -817,7 +843,9 @@ _0123456789
     assert_eq!(format!("{board}"), expexted_to_d90_d180);
     assert_eq!(activated_rotation(&board), Rotate::D180);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
     let expexted_to_d180_d270 = r#"
 ___________
 0..........
@@ -829,7 +857,9 @@ _0123456789
     assert_eq!(format!("{board}"), expexted_to_d180_d270);
     assert_eq!(activated_rotation(&board), Rotate::D270);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
     let expexted_to_d270_d0 = r#"
 ___________
 0..........
@@ -844,7 +874,9 @@ _0123456789
 #[test]
 fn rotate_right_o() {
     let mut board = Board::new(10, 4);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::O).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::O).unwrap())
+        .unwrap();
 
     let expted_origin = r#"
 ___________
@@ -857,22 +889,30 @@ _0123456789
     assert_eq!(format!("{board}"), expted_origin);
     assert_eq!(activated_rotation(&board), Rotate::D0);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
 
     assert_eq!(format!("{board}"), expted_origin);
     assert_eq!(activated_rotation(&board), Rotate::D90);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
 
     assert_eq!(format!("{board}"), expted_origin);
     assert_eq!(activated_rotation(&board), Rotate::D180);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
 
     assert_eq!(format!("{board}"), expted_origin);
     assert_eq!(activated_rotation(&board), Rotate::D270);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
 
     assert_eq!(format!("{board}"), expted_origin);
     assert_eq!(activated_rotation(&board), Rotate::D0);
@@ -881,7 +921,9 @@ _0123456789
 #[test]
 fn rotate_right_j() {
     let mut board = Board::new(10, 4);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::J).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::J).unwrap())
+        .unwrap();
 
     let expted_origin = r#"
 ___________
@@ -894,7 +936,9 @@ _0123456789
     assert_eq!(format!("{board}"), expted_origin);
     assert_eq!(activated_rotation(&board), Rotate::D0);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
     let expexted_to_d0_d90 = r#"
 ___________
 0..........
@@ -906,7 +950,9 @@ _0123456789
     assert_eq!(format!("{board}"), expexted_to_d0_d90);
     assert_eq!(activated_rotation(&board), Rotate::D90);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
     let expexted_to_d90_d180 = r#"
 ___________
 0..........
@@ -918,7 +964,9 @@ _0123456789
     assert_eq!(format!("{board}"), expexted_to_d90_d180);
     assert_eq!(activated_rotation(&board), Rotate::D180);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
     let expexted_to_d180_d270 = r#"
 ___________
 0..........
@@ -930,7 +978,9 @@ _0123456789
     assert_eq!(format!("{board}"), expexted_to_d180_d270);
     assert_eq!(activated_rotation(&board), Rotate::D270);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
     let expexted_to_d270_d0 = r#"
 ___________
 0..........
@@ -946,7 +996,9 @@ _0123456789
 #[test]
 fn rotate_right_l() {
     let mut board = Board::new(10, 4);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::L).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::L).unwrap())
+        .unwrap();
 
     let expted_origin = r#"
 ___________
@@ -959,7 +1011,9 @@ _0123456789
     assert_eq!(format!("{board}"), expted_origin);
     assert_eq!(activated_rotation(&board), Rotate::D0);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
     let expexted_to_d0_d90 = r#"
 ___________
 0..........
@@ -971,7 +1025,9 @@ _0123456789
     assert_eq!(format!("{board}"), expexted_to_d0_d90);
     assert_eq!(activated_rotation(&board), Rotate::D90);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
     let expexted_to_d90_d180 = r#"
 ___________
 0..........
@@ -983,7 +1039,9 @@ _0123456789
     assert_eq!(format!("{board}"), expexted_to_d90_d180);
     assert_eq!(activated_rotation(&board), Rotate::D180);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
     let expexted_to_d180_d270 = r#"
 ___________
 0..........
@@ -995,7 +1053,9 @@ _0123456789
     assert_eq!(format!("{board}"), expexted_to_d180_d270);
     assert_eq!(activated_rotation(&board), Rotate::D270);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
     let expexted_to_d270_d0 = r#"
 ___________
 0..........
@@ -1011,7 +1071,9 @@ _0123456789
 #[test]
 fn rotate_right_z() {
     let mut board = Board::new(10, 4);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap())
+        .unwrap();
 
     let expted_origin = r#"
 ___________
@@ -1024,7 +1086,9 @@ _0123456789
     assert_eq!(format!("{board}"), expted_origin);
     assert_eq!(activated_rotation(&board), Rotate::D0);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
     let expexted_to_d0_d90 = r#"
 ___________
 0..........
@@ -1036,7 +1100,9 @@ _0123456789
     assert_eq!(format!("{board}"), expexted_to_d0_d90);
     assert_eq!(activated_rotation(&board), Rotate::D90);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
     let expexted_to_d90_d180 = r#"
 ___________
 0..........
@@ -1048,7 +1114,9 @@ _0123456789
     assert_eq!(format!("{board}"), expexted_to_d90_d180);
     assert_eq!(activated_rotation(&board), Rotate::D180);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
     let expexted_to_d180_d270 = r#"
 ___________
 0..........
@@ -1060,7 +1128,9 @@ _0123456789
     assert_eq!(format!("{board}"), expexted_to_d180_d270);
     assert_eq!(activated_rotation(&board), Rotate::D270);
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
     let expexted_to_d270_d0 = r#"
 ___________
 0..........
@@ -1075,7 +1145,9 @@ _0123456789
 #[test]
 fn rotate_right_s() {
     let mut board = Board::new(10, 4);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::S).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::S).unwrap())
+        .unwrap();
 
     let expted_origin = r#"
 ___________
@@ -1089,7 +1161,7 @@ _0123456789
     assert_eq!(activated_rotation(&board), Rotate::D0);
 
     let res = board.try_rotate_falling(RotateDirection::Right);
-    board.apply_rotate_falling(res.unwrap());
+    board.apply_rotate_falling(res.unwrap()).unwrap();
     let expexted_to_d0_d90 = r#"
 ___________
 0..........
@@ -1102,7 +1174,7 @@ _0123456789
     assert_eq!(activated_rotation(&board), Rotate::D90);
 
     let res = board.try_rotate_falling(RotateDirection::Right);
-    board.apply_rotate_falling(res.unwrap());
+    board.apply_rotate_falling(res.unwrap()).unwrap();
     let expexted_to_d90_d180 = r#"
 ___________
 0..........
@@ -1115,7 +1187,7 @@ _0123456789
     assert_eq!(activated_rotation(&board), Rotate::D180);
 
     let res = board.try_rotate_falling(RotateDirection::Right);
-    board.apply_rotate_falling(res.unwrap());
+    board.apply_rotate_falling(res.unwrap()).unwrap();
     let expexted_to_d180_d270 = r#"
 ___________
 0..........
@@ -1128,7 +1200,7 @@ _0123456789
     assert_eq!(activated_rotation(&board), Rotate::D270);
 
     let res = board.try_rotate_falling(RotateDirection::Right);
-    board.apply_rotate_falling(res.unwrap());
+    board.apply_rotate_falling(res.unwrap()).unwrap();
     let expexted_to_d270_d0 = r#"
 ___________
 0..........
@@ -1144,7 +1216,9 @@ _0123456789
 #[test]
 fn right() {
     let mut board = Board::new(10, 4);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap())
+        .unwrap();
 
     let expted_origin = r#"
 ___________
@@ -1156,7 +1230,9 @@ _0123456789
 "#;
     assert_eq!(format!("{board}"), expted_origin);
 
-    board.apply_move_falling(board.try_move_falling(MoveDirection::Right).unwrap());
+    board
+        .apply_move_falling(board.try_move_falling(MoveDirection::Right).unwrap())
+        .unwrap();
     let expected_right = r#"
 ___________
 0..........
@@ -1171,27 +1247,31 @@ _0123456789
 #[test]
 fn right_block() {
     let mut board = Board::new(10, 4);
-    *board.location_mut(6, 2) = Tile::Placed(1);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap());
+    *board.get_mut(6, 2).unwrap() = Tile::Placed(1);
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap())
+        .unwrap();
 
     let expted_origin = r#"
 ___________
 0..........
 1...ZZ.....
-2....ZZP...
+2....ZZi...
 3..........
 _0123456789
 "#;
     assert_eq!(format!("{board}"), expted_origin);
 
     let res = board.try_move_falling(MoveDirection::Right);
-    assert!(matches!(res, Err(MoveError::Blocked(_, _))));
+    assert!(matches!(res, Err(EngineError::Blocked(_))));
 }
 
 #[test]
 fn left() {
     let mut board = Board::new(10, 4);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap())
+        .unwrap();
     let expted_origin = r#"
 ___________
 0..........
@@ -1202,16 +1282,13 @@ _0123456789
 "#;
     assert_eq!(format!("{board}"), expted_origin);
     let res = board.try_move_falling(MoveDirection::Left);
-    board.apply_move_falling(res.unwrap());
+    board.apply_move_falling(res.unwrap()).unwrap();
     let res = board.try_move_falling(MoveDirection::Left);
-    board.apply_move_falling(res.unwrap());
+    board.apply_move_falling(res.unwrap()).unwrap();
     let res = board.try_move_falling(MoveDirection::Left);
-    board.apply_move_falling(res.unwrap());
+    board.apply_move_falling(res.unwrap()).unwrap();
     let res = board.try_move_falling(MoveDirection::Left);
-    assert!(matches!(
-        res,
-        Err(MoveError::OutOfBounds(MoveDirection::Left))
-    ));
+    assert!(matches!(res, Err(EngineError::OutOfBounds)));
 
     let expected = r#"
 ___________
@@ -1227,27 +1304,33 @@ _0123456789
 #[test]
 fn left_block() {
     let mut board = Board::new(10, 4);
-    *board.location_mut(1, 1) = Tile::Placed(1);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap());
+    *board.get_mut(1, 1).unwrap() = Tile::Placed(1);
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap())
+        .unwrap();
 
     let expted_origin = r#"
 ___________
 0..........
-1.P.ZZ.....
+1.i.ZZ.....
 2....ZZ....
 3..........
 _0123456789
 "#;
     assert_eq!(format!("{board}"), expted_origin);
-    board.apply_rotate_falling(board.try_move_falling(MoveDirection::Left).unwrap());
+    board
+        .apply_move_falling(board.try_move_falling(MoveDirection::Left).unwrap())
+        .unwrap();
     let res = board.try_move_falling(MoveDirection::Left);
-    assert!(matches!(res, Err(MoveError::Blocked(_, _))));
+    assert!(matches!(res, Err(EngineError::Blocked(_))));
 }
 
 #[test]
 fn step() {
     let mut board = Board::new(10, 5);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap())
+        .unwrap();
 
     let expted_origin = r#"
 ___________
@@ -1260,7 +1343,7 @@ _0123456789
 "#;
     assert_eq!(format!("{board}"), expted_origin);
     let res = board.try_step();
-    board.apply_step(res.unwrap());
+    board.apply_step(res.unwrap()).unwrap();
     let expted = r#"
 ___________
 0..........
@@ -1276,7 +1359,9 @@ _0123456789
 #[test]
 fn step_err() {
     let mut board = Board::new(10, 5);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap())
+        .unwrap();
 
     let expted = r#"
 ___________
@@ -1289,46 +1374,48 @@ _0123456789
 "#;
     assert_eq!(format!("{board}"), expted);
 
-    board.apply_step(board.try_step().unwrap());
-    board.apply_step(board.try_step().unwrap());
+    board.apply_step(board.try_step().unwrap()).unwrap();
+    board.apply_step(board.try_step().unwrap()).unwrap();
 
     let res = board.try_step();
-    assert!(matches!(res, Err(StepError::OutOfBounds)));
+    assert!(matches!(res, Err(EngineError::OutOfBounds)));
 }
 
 #[test]
 fn step3() {
     let mut board = Board::new(10, 5);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap())
+        .unwrap();
 
-    *board.location_mut(5, 4) = Tile::Placed(0);
+    *board.get_mut(5, 4).unwrap() = Tile::Placed(0);
     let expted = r#"
 ___________
 0..........
 1...ZZ.....
 2....ZZ....
 3..........
-4.....P....
+4.....g....
 _0123456789
 "#;
     assert_eq!(format!("{board}"), expted);
 
-    board.apply_step(board.try_step().unwrap());
+    board.apply_step(board.try_step().unwrap()).unwrap();
 
     let res = board.try_step();
-    assert!(matches!(res, Err(StepError::Blocked(_))));
+    assert!(matches!(res, Err(EngineError::Blocked(_))));
 }
 #[test]
 fn line_clear() {
     let mut board = Board::new(10, 5);
     for x in 0..board.x_len() {
-        *board.location_mut(x, 2) = Tile::Placed(0);
+        *board.get_mut(x, 2).unwrap() = Tile::Placed(0);
     }
     let expted = r#"
 ___________
 0..........
 1..........
-2PPPPPPPPPP
+2gggggggggg
 3..........
 4..........
 _0123456789
@@ -1337,7 +1424,7 @@ _0123456789
 
     let clearlines = board.try_line_clear();
 
-    board.apply_line_clear(clearlines);
+    board.apply_line_clear(clearlines).unwrap();
 
     let expted = r#"
 ___________
@@ -1354,26 +1441,26 @@ _0123456789
 fn line_clear2() {
     let mut board = Board::new(10, 5);
     for x in 0..board.x_len() {
-        *board.location_mut(x, 2) = Tile::Placed(0);
+        *board.get_mut(x, 2).unwrap() = Tile::Placed(0);
     }
-    *board.location_mut(4, 2) = Tile::Empty;
+    *board.get_mut(4, 2).unwrap() = Tile::Empty;
 
     let expted = r#"
 ___________
 0..........
 1..........
-2PPPP.PPPPP
+2gggg.ggggg
 3..........
 4..........
 _0123456789
 "#;
     assert_eq!(format!("{board}"), expted);
 
-    *board.location_mut(4, 2) = Tile::Empty;
+    *board.get_mut(4, 2).unwrap() = Tile::Empty;
 
     let clearlines = board.try_line_clear();
 
-    board.apply_line_clear(clearlines);
+    board.apply_line_clear(clearlines).unwrap();
 
     assert_eq!(format!("{board}"), expted);
 }
@@ -1382,34 +1469,34 @@ _0123456789
 fn line_clear3() {
     let mut board = Board::new(10, 5);
     for x in 0..board.x_len() {
-        *board.location_mut(x, 2) = Tile::Placed(0);
-        *board.location_mut(x, 4) = Tile::Placed(0);
+        *board.get_mut(x, 2).unwrap() = Tile::Placed(0);
+        *board.get_mut(x, 4).unwrap() = Tile::Placed(0);
     }
-    *board.location_mut(2, 1) = Tile::Placed(0);
-    *board.location_mut(6, 3) = Tile::Placed(0);
+    *board.get_mut(2, 1).unwrap() = Tile::Placed(0);
+    *board.get_mut(6, 3).unwrap() = Tile::Placed(0);
 
     let expted = r#"
 ___________
 0..........
-1..P.......
-2PPPPPPPPPP
-3......P...
-4PPPPPPPPPP
+1..g.......
+2gggggggggg
+3......g...
+4gggggggggg
 _0123456789
 "#;
     assert_eq!(format!("{board}"), expted);
 
     let clearlines = board.try_line_clear();
 
-    board.apply_line_clear(clearlines);
+    board.apply_line_clear(clearlines).unwrap();
 
     let expted = r#"
 ___________
 0..........
 1..........
 2..........
-3..P.......
-4......P...
+3..g.......
+4......g...
 _0123456789
 "#;
     assert_eq!(format!("{board}"), expted);
@@ -1418,8 +1505,10 @@ _0123456789
 #[test]
 fn hard_drop() {
     let mut board = Board::new(10, 8);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap());
-    *board.location_mut(3, 6) = Tile::Placed(1);
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap())
+        .unwrap();
+    *board.get_mut(3, 6).unwrap() = Tile::Placed(1);
     let expted = r#"
 ___________
 0..........
@@ -1428,7 +1517,7 @@ ___________
 3..........
 4..........
 5..........
-6...P......
+6...i......
 7..........
 _0123456789
 "#;
@@ -1443,7 +1532,7 @@ ___________
 3..........
 4..........
 5...ZZ.....
-6...PZZ....
+6...iZZ....
 7..........
 _0123456789
 "#;
@@ -1452,7 +1541,9 @@ _0123456789
 #[test]
 fn show_hint() {
     let mut board = Board::new(10, 8);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap())
+        .unwrap();
     let expted = r#"
 ___________
 0..........
@@ -1466,7 +1557,6 @@ ___________
 _0123456789
 "#;
     assert_eq!(format!("{board}"), expted);
-    board.show_falling_hint();
     let expted = r#"
 ___________
 0..........
@@ -1479,16 +1569,15 @@ ___________
 7....HH....
 _0123456789
 "#;
-    assert_eq!(format!("{board}"), expted);
+    assert_eq!(format!("{board:#}"), expted);
 }
 
 #[test]
 fn remove_hint() {
     let mut board = Board::new(10, 8);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap());
-
-    // let res = board.hard_drop();
-    board.show_falling_hint();
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap())
+        .unwrap();
 
     let expted = r#"
 ___________
@@ -1502,8 +1591,8 @@ ___________
 7....HH....
 _0123456789
 "#;
-    assert_eq!(format!("{board}"), expted);
-    board.remove_falling_hint();
+    assert_eq!(format!("{board:#}"), expted);
+    // 고스트는 칸에 쓰지 않는다
     let expted = r#"
 ___________
 0..........
@@ -1522,14 +1611,16 @@ _0123456789
 #[test]
 fn game_over() {
     let mut board = Board::new(10, 8);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap());
-    board.place_falling();
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap())
+        .unwrap();
+    board.place_falling().unwrap();
 
     let expted = r#"
 ___________
 0..........
-1...PP.....
-2....PP....
+1...zz.....
+2....zz....
 3..........
 4..........
 5..........
@@ -1541,16 +1632,18 @@ _0123456789
     assert_eq!(board.has_placed_above(2), true);
 
     let mut board = Board::new(10, 8);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap());
-    board.apply_step(board.try_step().unwrap());
-    board.place_falling();
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap())
+        .unwrap();
+    board.apply_step(board.try_step().unwrap()).unwrap();
+    board.place_falling().unwrap();
 
     let expted = r#"
 ___________
 0..........
 1..........
-2...PP.....
-3....PP....
+2...zz.....
+3....zz....
 4..........
 5..........
 6..........
@@ -1564,7 +1657,9 @@ _0123456789
 #[test]
 fn remove_falling_block() {
     let mut board = Board::new(10, 4);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::Z).unwrap())
+        .unwrap();
     let expted = r#"
 ___________
 0..........
@@ -1589,31 +1684,41 @@ _0123456789
 #[test]
 fn test_t() {
     let mut board = Board::new(10, 4);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap())
+        .unwrap();
     println!("{board}");
     for f in board.get_falling_blocks() {
         println!("loc: {:?}, id:{:?}", f.location, f.falling.id);
     }
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
     println!("{board}");
     for f in board.get_falling_blocks() {
         println!("loc: {:?}, id:{:?}", f.location, f.falling.id);
     }
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
     println!("{board}");
     for f in board.get_falling_blocks() {
         println!("loc: {:?}, id:{:?}", f.location, f.falling.id);
     }
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
     println!("{board}");
     for f in board.get_falling_blocks() {
         println!("loc: {:?}, id:{:?}", f.location, f.falling.id);
     }
 
-    board.apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap());
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
     println!("{board}");
     for f in board.get_falling_blocks() {
         println!("loc: {:?}, id:{:?}", f.location, f.falling.id);
@@ -1628,43 +1733,2111 @@ fn testlen() {
 #[test]
 fn testspawn() {
     let mut board = Board::new(10, 26);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::L).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::L).unwrap())
+        .unwrap();
     println!("{board}");
 }
 
 #[test]
 fn test_garbage1() {
     let mut board = Board::new(10, 10);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::L).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::L).unwrap())
+        .unwrap();
     println!("{board}");
-    board.push_garbage_line(2);
+    board.push_garbage_line(2).unwrap();
     println!("{board}");
 }
 
 #[test]
 fn test_garbage2() {
     let mut board = Board::new(10, 10);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::L).unwrap());
-    board.hard_drop();
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::L).unwrap())
+        .unwrap();
+    board.hard_drop().unwrap();
     println!("{board}");
-    board.push_garbage_line(2);
+    board.push_garbage_line(2).unwrap();
     println!("{board}");
 }
 
 #[test]
 fn test_garbage3() {
     let mut board = Board::new(10, 10);
-    board.apply_spawn_falling(board.try_spawn_falling(Tetrimino::L).unwrap());
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::L).unwrap())
+        .unwrap();
 
     println!("{board}");
-    board.push_garbage_line(2);
-    board.push_garbage_line(2);
-    board.push_garbage_line(2);
-    board.push_garbage_line(2);
-    board.push_garbage_line(2);
-    board.push_garbage_line(2);
-    board.push_garbage_line(2);
-    println!("{}", board.push_garbage_line(2));
-    println!("{}", board.push_garbage_line(2));
+    board.push_garbage_line(2).unwrap();
+    board.push_garbage_line(2).unwrap();
+    board.push_garbage_line(2).unwrap();
+    board.push_garbage_line(2).unwrap();
+    board.push_garbage_line(2).unwrap();
+    board.push_garbage_line(2).unwrap();
+    board.push_garbage_line(2).unwrap();
+    println!("{:?}", board.push_garbage_line(2));
+    println!("{:?}", board.push_garbage_line(2));
     println!("{board}");
 }
+
+fn game_with_next(next: &[Tetrimino]) -> Game {
+    let mut game = Game::new(GameConfig {
+        width: 10,
+        height: 6,
+        randomizer: None,
+        ..Default::default()
+    });
+    for t in next {
+        game.push_next(*t);
+    }
+    game
+}
+
+#[test]
+fn game_spawn_and_gravity() {
+    let mut game = game_with_next(&[Tetrimino::T, Tetrimino::O]);
+    let events = game.tick(&[]);
+    assert_eq!(
+        events,
+        vec![GameEvent::Spawn {
+            spawn: Tetrimino::T
+        }]
+    );
+    assert_eq!(game.current(), Some(Tetrimino::T));
+    assert_eq!(game.next().len(), 1);
+
+    let before = game.board().get_falling_blocks();
+    for _ in 1..game::level_to_gravity_tick(1) {
+        game.tick(&[]);
+    }
+    let after = game.board().get_falling_blocks();
+    for (b, a) in before.iter().zip(after.iter()) {
+        assert_eq!(b.location.y + 1, a.location.y);
+    }
+}
+
+#[test]
+fn game_hard_drop_lock() {
+    let mut game = game_with_next(&[Tetrimino::O, Tetrimino::T]);
+    game.tick(&[]);
+    let events = game.tick(&[GameInput::HardDrop]);
+    assert_eq!(
+        events,
+        vec![
+            GameEvent::Lock {
+                result: ClearResult {
+                    kind: Tetrimino::O,
+                    lines: 0,
+                    tspin: TSpin::None,
+                    is_perfect_clear: false,
+                    b2b: None,
+                    combo: None,
+                },
+                score: 0,
+                attack: 0,
+            },
+            GameEvent::Spawn {
+                spawn: Tetrimino::T
+            },
+        ]
+    );
+    // O 는 1,2 행에서 4,5 행으로 3칸 떨어진다
+    assert_eq!(game.score(), 6);
+    let expected = r#"
+___________
+0..........
+1....T.....
+2...TTT....
+3..........
+4....oo....
+5....oo....
+_0123456789
+"#;
+    assert_eq!(format!("{}", game.board()), expected);
+}
+
+#[test]
+fn game_line_clear_score_and_combo() {
+    let mut board = Board::new(10, 6);
+    for y in 4..6 {
+        for x in 0..10 {
+            if x != 4 && x != 5 {
+                *board.get_mut(x, y).unwrap() = Tile::Placed(1);
+            }
+        }
+    }
+    let mut game = game_with_next(&[Tetrimino::O, Tetrimino::O]).with_board(board);
+    game.tick(&[]);
+    let events = game.tick(&[GameInput::HardDrop]);
+    // 더블 300 + 퍼펙트 클리어 1200
+    assert!(events.contains(&GameEvent::Lock {
+        result: ClearResult {
+            kind: Tetrimino::O,
+            lines: 2,
+            tspin: TSpin::None,
+            is_perfect_clear: true,
+            b2b: None,
+            combo: Some(0),
+        },
+        score: 1500,
+        attack: 11,
+    }));
+    assert_eq!(game.lines(), 2);
+    assert_eq!(game.combo(), Some(0));
+    assert_eq!(game.b2b(), None);
+
+    game.tick(&[GameInput::HardDrop]);
+    assert_eq!(game.combo(), None);
+}
+
+#[test]
+fn game_hold_once_per_piece() {
+    let mut game = game_with_next(&[Tetrimino::T, Tetrimino::I, Tetrimino::O]);
+    game.tick(&[]);
+    let events = game.tick(&[GameInput::Hold, GameInput::Hold]);
+    assert_eq!(
+        events,
+        vec![
+            GameEvent::Hold { hold: Tetrimino::T },
+            GameEvent::Spawn {
+                spawn: Tetrimino::I
+            },
+        ]
+    );
+    assert_eq!(game.hold(), Some(Tetrimino::T));
+    assert!(!game.is_can_hold());
+
+    game.tick(&[GameInput::HardDrop]);
+    assert!(game.is_can_hold());
+    let events = game.tick(&[GameInput::Hold]);
+    assert_eq!(
+        events,
+        vec![
+            GameEvent::Hold { hold: Tetrimino::O },
+            GameEvent::Spawn {
+                spawn: Tetrimino::T
+            },
+        ]
+    );
+}
+
+#[test]
+fn game_hold_modes() {
+    let game_with_hold = |hold_mode: HoldMode, initial_hold: Option<Tetrimino>| {
+        let mut game = Game::new(GameConfig {
+            width: 10,
+            height: 6,
+            randomizer: None,
+            hold_mode,
+            initial_hold,
+            ..Default::default()
+        });
+        for t in [Tetrimino::T, Tetrimino::I, Tetrimino::O] {
+            game.push_next(t);
+        }
+        game.tick(&[]);
+        game
+    };
+
+    let mut game = game_with_hold(HoldMode::Disabled, None);
+    assert!(!game.is_can_hold());
+    assert!(game.tick(&[GameInput::Hold]).is_empty());
+    assert_eq!(game.current(), Some(Tetrimino::T));
+
+    // 무한 홀드는 같은 블록에서 계속 바꿀 수 있다
+    let mut game = game_with_hold(HoldMode::Infinite, None);
+    game.tick(&[GameInput::Hold, GameInput::Hold, GameInput::Hold]);
+    assert_eq!(game.current(), Some(Tetrimino::I));
+    assert_eq!(game.hold(), Some(Tetrimino::T));
+    assert!(game.is_can_hold());
+
+    // 처음 홀드 칸의 블록과 바로 바꾼다
+    let mut game = game_with_hold(HoldMode::Once, Some(Tetrimino::Z));
+    assert_eq!(game.hold(), Some(Tetrimino::Z));
+    game.tick(&[GameInput::Hold]);
+    assert_eq!(game.current(), Some(Tetrimino::Z));
+    assert_eq!(game.hold(), Some(Tetrimino::T));
+    assert_eq!(game.next().len(), 2);
+
+    // 보드만 있어도 같은 규칙으로 홀드한다
+    let mut board = Board::new_common();
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::S).unwrap())
+        .unwrap();
+    let mut slot = HoldSlot::new(HoldMode::Once, None);
+    assert_eq!(slot.hold(&mut board), Ok(None));
+    assert!(board.active_piece().is_none());
+    assert_eq!(slot.piece(), Some(Tetrimino::S));
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::L).unwrap())
+        .unwrap();
+    assert_eq!(slot.hold(&mut board), Err(HoldError::AlreadyHeld));
+    slot.on_lock();
+    assert_eq!(slot.hold(&mut board), Ok(Some(Tetrimino::S)));
+}
+
+#[test]
+fn game_over_when_spawn_blocked() {
+    let mut board = Board::new(10, 6);
+    *board.get_mut(4, 1).unwrap() = Tile::Placed(1);
+    let mut game = game_with_next(&[Tetrimino::T]).with_board(board);
+    let events = game.tick(&[]);
+    assert_eq!(
+        events,
+        vec![GameEvent::GameOver {
+            reason: TopOut::BlockOut
+        }]
+    );
+    assert!(game.is_over());
+    assert!(game.tick(&[GameInput::HardDrop]).is_empty());
+}
+
+#[test]
+fn undo_redo_placements() {
+    let state = |game: &Game| {
+        (
+            game.board().view(),
+            game.current(),
+            game.hold(),
+            game.next().clone(),
+            game.score(),
+        )
+    };
+    let mut game = Game::new(GameConfig {
+        seed: 7,
+        history_len: 2,
+        ..Default::default()
+    });
+    game.tick(&[]);
+    assert!(!game.can_undo());
+    assert!(!game.undo());
+
+    let mut states = vec![];
+    for _ in 0..3 {
+        states.push(state(&game));
+        game.tick(&[GameInput::Hold, GameInput::HardDrop]);
+    }
+    states.push(state(&game));
+
+    // 기록은 2개까지만 남는다
+    assert!(game.undo());
+    assert_eq!(state(&game), states[2]);
+    assert!(game.undo());
+    assert_eq!(state(&game), states[1]);
+    assert!(!game.undo());
+
+    assert!(game.redo());
+    assert!(game.redo());
+    assert!(!game.can_redo());
+    assert_eq!(state(&game), states[3]);
+
+    // 되돌린 뒤 새로 놓으면 다시하기 기록은 사라진다
+    game.undo();
+    game.tick(&[GameInput::HardDrop]);
+    assert!(!game.can_redo());
+}
+
+#[test]
+fn undo_after_empty_next_and_game_over() {
+    let state = |game: &Game| {
+        (
+            game.board().view(),
+            game.current(),
+            game.next().clone(),
+            game.score(),
+        )
+    };
+    let mut game = Game::new(GameConfig {
+        randomizer: None,
+        history_len: 4,
+        ..Default::default()
+    });
+    // 꺼낼 블록이 없는 동안은 기록하지 않는다
+    for _ in 0..10 {
+        game.tick(&[]);
+    }
+    assert!(!game.can_undo());
+
+    game.push_next(Tetrimino::T);
+    game.push_next(Tetrimino::O);
+    game.tick(&[]);
+    let before_t = state(&game);
+    game.tick(&[GameInput::HardDrop]);
+    let before_o = state(&game);
+    game.tick(&[GameInput::HardDrop]);
+    for _ in 0..10 {
+        game.tick(&[]);
+    }
+    assert!(game.undo());
+    assert_eq!(state(&game), before_o);
+    assert!(game.undo());
+    assert_eq!(state(&game), before_t);
+    assert!(!game.undo());
+    assert!(game.redo());
+    assert_eq!(state(&game), before_o);
+    assert!(!game.can_redo());
+
+    // 게임 오버는 기록하지 않고, 되돌리면 마지막 블록을 놓기 전으로 간다
+    let mut board = Board::new_common();
+    for y in board.buffer_height()..board.y_len() {
+        for x in 0..9 {
+            *board.get_mut(x, y).unwrap() = Tile::Placed(1);
+        }
+    }
+    let mut game = Game::new(GameConfig {
+        randomizer: None,
+        history_len: 4,
+        ..Default::default()
+    })
+    .with_board(board);
+    game.push_next(Tetrimino::T);
+    game.push_next(Tetrimino::T);
+    game.tick(&[]);
+    let before = state(&game);
+    game.tick(&[GameInput::HardDrop]);
+    assert!(game.is_over());
+    assert!(game.can_undo());
+    assert!(game.undo());
+    assert!(!game.is_over());
+    assert_eq!(state(&game), before);
+    assert!(!game.can_undo());
+}
+
+#[test]
+fn top_out_reasons() {
+    // 보이는 줄을 한 칸만 비우고 채우면 다음 블록은 버퍼 안에서 고정된다
+    let mut board = Board::new_common();
+    for y in board.buffer_height()..board.y_len() {
+        for x in 0..9 {
+            *board.get_mut(x, y).unwrap() = Tile::Placed(1);
+        }
+    }
+    let mut game = Game::new(GameConfig {
+        randomizer: None,
+        ..Default::default()
+    })
+    .with_board(board.clone());
+    game.push_next(Tetrimino::T);
+    game.push_next(Tetrimino::T);
+    game.tick(&[]);
+    let events = game.tick(&[GameInput::HardDrop]);
+    assert_eq!(
+        events.last(),
+        Some(&GameEvent::GameOver {
+            reason: TopOut::LockOut
+        })
+    );
+    assert!(game.is_over());
+
+    // 맨 윗줄에 블록이 있으면 방해 줄을 넣지 않는다
+    *board.get_mut(0, 0).unwrap() = Tile::Placed(1);
+    let before = board.view();
+    assert_eq!(
+        board.push_garbage(&[0]),
+        Err(EngineError::TopOut(TopOut::GarbageOut))
+    );
+    assert_eq!(board.view(), before);
+}
+
+fn take(randomizer: &mut impl Randomizer, n: usize) -> Vec<Tetrimino> {
+    (0..n).map(|_| randomizer.next_tetrimino()).collect()
+}
+
+#[test]
+fn seeded_rng_is_stable() {
+    // wasm 과 native 에서 같은 값이 나와야 한다
+    let mut rng = SeededRng::new(1234567);
+    assert_eq!(rng.next_u64(), 6457827717110365317);
+    assert_eq!(rng.next_u64(), 3203168211198807973);
+    let mut rng = SeededRng::new(42);
+    for _ in 0..1000 {
+        assert!(rng.below(7) < 7);
+        assert!((0.0..1.0).contains(&rng.next_f64()));
+    }
+}
+
+#[test]
+fn randomizer_same_seed_same_sequence() {
+    for kind in [
+        RandomizerKind::SevenBag,
+        RandomizerKind::FourteenBag,
+        RandomizerKind::Memoryless,
+        RandomizerKind::History4,
+    ] {
+        let a = take(&mut kind.build(7), 100);
+        let b = take(&mut kind.build(7), 100);
+        let c = take(&mut kind.build(8), 100);
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+}
+
+#[test]
+fn seven_bag_contains_every_piece() {
+    let mut randomizer = RandomizerKind::SevenBag.build(99);
+    for _ in 0..20 {
+        let mut bag = take(&mut randomizer, 7);
+        bag.sort_by_key(|t| *t as usize);
+        assert_eq!(bag.to_vec(), randomizer::TETRIMINOS.to_vec());
+    }
+}
+
+#[test]
+fn fourteen_bag_contains_every_piece_twice() {
+    let mut randomizer = RandomizerKind::FourteenBag.build(99);
+    for _ in 0..20 {
+        let bag = take(&mut randomizer, 14);
+        for t in randomizer::TETRIMINOS {
+            assert_eq!(bag.iter().filter(|b| **b == t).count(), 2);
+        }
+    }
+}
+
+#[test]
+fn history4_first_piece_not_s_z_o() {
+    for seed in 0..50 {
+        let first = RandomizerKind::History4.build(seed).next_tetrimino();
+        assert!(!matches!(first, Tetrimino::S | Tetrimino::Z | Tetrimino::O));
+    }
+}
+
+#[test]
+fn game_fills_next_from_randomizer() {
+    let config = GameConfig {
+        seed: 5,
+        ..Default::default()
+    };
+    let mut game = Game::new(config.clone());
+    assert_eq!(game.next().len(), 5);
+
+    let mut expected = take(&mut RandomizerKind::SevenBag.build(5), 7);
+    let events = game.tick(&[]);
+    assert_eq!(
+        events,
+        vec![GameEvent::Spawn {
+            spawn: expected.remove(0)
+        }]
+    );
+    assert_eq!(
+        game.next().iter().cloned().collect::<Vec<_>>(),
+        expected[..5]
+    );
+    assert_eq!(
+        Game::new(config).next().iter().collect::<Vec<_>>(),
+        Game::new(GameConfig {
+            seed: 5,
+            ..Default::default()
+        })
+        .next()
+        .iter()
+        .collect::<Vec<_>>()
+    );
+}
+
+/// T 를 (1, 2) 중심 D0 로 바로 놓는다, 맨 아랫줄은 x=1 만 비어있다
+fn tspin_board(filled: &[(usize, usize)]) -> Board {
+    let mut board = Board::new(10, 4);
+    for x in 0..10 {
+        if x != 1 {
+            *board.get_mut(x, 3).unwrap() = Tile::Placed(1);
+        }
+    }
+    for (x, y) in filled {
+        *board.get_mut(*x, *y).unwrap() = Tile::Placed(1);
+    }
+    board
+        .apply_spawn_falling(board.try_spawn_falling_at(Tetrimino::T, 1, 1).unwrap())
+        .unwrap();
+    board
+}
+
+#[test]
+fn tspin_full_after_rotation() {
+    let mut board = tspin_board(&[(2, 1)]);
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
+    assert_eq!(board.last_kick(), Some(0));
+    assert_eq!(board.detect_tspin(), TSpin::Full);
+    assert_eq!(
+        board.lock_falling(),
+        Ok(LockResult {
+            kind: Tetrimino::T,
+            tspin: TSpin::Full,
+            lines: 1,
+            is_perfect_clear: false,
+        })
+    );
+}
+
+#[test]
+fn tspin_mini_when_front_corner_open() {
+    let mut board = tspin_board(&[(0, 1)]);
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
+    assert_eq!(board.detect_tspin(), TSpin::Mini);
+}
+
+#[test]
+fn tspin_none_without_rotation() {
+    let board = tspin_board(&[(2, 1)]);
+    assert_eq!(board.last_kick(), None);
+    assert_eq!(board.detect_tspin(), TSpin::None);
+
+    // 회전 뒤에 이동하면 기록이 지워진다
+    let mut board = Board::new(10, 6);
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap())
+        .unwrap();
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
+    assert_eq!(board.last_kick(), Some(0));
+    board
+        .apply_move_falling(board.try_move_falling(MoveDirection::Right).unwrap())
+        .unwrap();
+    assert_eq!(board.last_kick(), None);
+    assert_eq!(board.detect_tspin(), TSpin::None);
+}
+
+fn lock_result(tspin: TSpin, lines: u8) -> LockResult {
+    LockResult {
+        kind: Tetrimino::T,
+        tspin,
+        lines,
+        is_perfect_clear: false,
+    }
+}
+
+#[test]
+fn clear_chain_b2b_and_combo() {
+    let mut chain = ClearChain::default();
+    let first = chain.clear(lock_result(TSpin::None, 4));
+    assert_eq!((first.b2b, first.combo), (Some(0), Some(0)));
+    assert!(!first.is_b2b());
+
+    let second = chain.clear(lock_result(TSpin::Full, 2));
+    assert_eq!((second.b2b, second.combo), (Some(1), Some(1)));
+    assert!(second.is_b2b());
+
+    // 줄을 안 지운 T-스핀은 B2B 를 유지하고 콤보만 끊는다
+    let zero = chain.clear(lock_result(TSpin::Full, 0));
+    assert_eq!((zero.b2b, zero.combo), (Some(1), None));
+
+    let single = chain.clear(lock_result(TSpin::None, 1));
+    assert_eq!((single.b2b, single.combo), (None, Some(0)));
+}
+
+#[test]
+fn score_table_guideline_values() {
+    let table = ScoreTable::default();
+    let mut chain = ClearChain::default();
+    let tsd = chain.clear(lock_result(TSpin::Full, 2));
+    assert_eq!(table.score(&tsd, 2), 1200 * 2);
+
+    // B2B 1.5 배 + 1 콤보 50
+    let tst = chain.clear(lock_result(TSpin::Full, 3));
+    assert_eq!(table.score(&tst, 1), 1600 * 3 / 2 + 50);
+
+    let mini = ClearChain::default().clear(lock_result(TSpin::Mini, 0));
+    assert_eq!(table.score(&mini, 1), 100);
+}
+
+#[test]
+fn attack_table_guideline_values() {
+    let table = AttackTable::default();
+    let mut chain = ClearChain::default();
+    assert_eq!(table.attack(&chain.clear(lock_result(TSpin::None, 4))), 4);
+    // B2B +1, 콤보 1 은 0
+    assert_eq!(table.attack(&chain.clear(lock_result(TSpin::Full, 2))), 5);
+    assert_eq!(table.attack(&chain.clear(lock_result(TSpin::None, 1))), 1);
+
+    let mut pc = lock_result(TSpin::None, 2);
+    pc.is_perfect_clear = true;
+    assert_eq!(table.attack(&ClearChain::default().clear(pc)), 11);
+}
+
+#[test]
+fn ars_spawn_flat_side_up() {
+    let mut board = Board::new(10, 3).with_rotation_system(RotationSystemKind::Ars);
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap())
+        .unwrap();
+    let expected = r#"
+___________
+0..........
+1...TTT....
+2....T.....
+_0123456789
+"#;
+    assert_eq!(format!("{board}"), expected);
+    assert_eq!(activated_rotation(&board), Rotate::D180);
+}
+
+#[test]
+fn ars_kicks_one_column_nes_does_not() {
+    let board_with = |system: RotationSystemKind| {
+        let mut board = Board::new(10, 4).with_rotation_system(system);
+        *board.get_mut(4, 0).unwrap() = Tile::Placed(1);
+        board
+            .apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap())
+            .unwrap();
+        board
+    };
+
+    let mut board = board_with(RotationSystemKind::Ars);
+    let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
+    assert_eq!(plan.kick_idx, 1);
+    board.apply_rotate_falling(plan).unwrap();
+    let expected = r#"
+___________
+0....iT....
+1....TT....
+2.....T....
+3..........
+_0123456789
+"#;
+    assert_eq!(format!("{board}"), expected);
+
+    let board = board_with(RotationSystemKind::Nes);
+    assert!(board.try_rotate_falling(RotateDirection::Right).is_err());
+}
+
+#[test]
+fn rotation_systems_full_turn_returns_to_spawn() {
+    for system in [
+        RotationSystemKind::Srs,
+        RotationSystemKind::SrsPlus,
+        RotationSystemKind::Ars,
+        RotationSystemKind::Nes,
+    ] {
+        for tetrimino in randomizer::TETRIMINOS {
+            let mut board = Board::new(10, 8).with_rotation_system(system);
+            board
+                .apply_spawn_falling(board.try_spawn_falling(tetrimino).unwrap())
+                .unwrap();
+            let spawned = board.get_falling_blocks();
+            for dir in [RotateDirection::Right, RotateDirection::Left] {
+                for _ in 0..4 {
+                    let plan = board.try_rotate_falling(dir.clone()).unwrap();
+                    assert_eq!(plan.kick_idx, 0, "{system:?} {tetrimino:?}");
+                    board.apply_rotate_falling(plan).unwrap();
+                }
+                assert_eq!(
+                    board.get_falling_blocks(),
+                    spawned,
+                    "{system:?} {tetrimino:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn srs_plus_i_kicks_are_mirrored() {
+    let system = RotationSystemKind::SrsPlus;
+    let mirror = |rotate: Rotate| match rotate {
+        Rotate::D90 => Rotate::D270,
+        Rotate::D270 => Rotate::D90,
+        r => r,
+    };
+    for from in [Rotate::D0, Rotate::D90, Rotate::D180, Rotate::D270] {
+        for test_idx in 0..5 {
+            let left = system.wall_kick(Tetrimino::I, from, &RotateDirection::Left, test_idx);
+            let right = system.wall_kick(
+                Tetrimino::I,
+                mirror(from),
+                &RotateDirection::Right,
+                test_idx,
+            );
+            assert_eq!(left, right.map(|(x, y)| (-x, y)));
+        }
+    }
+}
+
+#[test]
+fn rotate_half_floor_kick() {
+    let mut board = Board::new(10, 3);
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap())
+        .unwrap();
+    let plan = board.try_rotate_falling(RotateDirection::Half).unwrap();
+    // 그대로 돌리면 바닥 밖이라 한칸 위로 올린다
+    assert_eq!(plan.kick_idx, 1);
+    board.apply_rotate_falling(plan).unwrap();
+    let expected = r#"
+___________
+0..........
+1...TTT....
+2....T.....
+_0123456789
+"#;
+    assert_eq!(format!("{board}"), expected);
+    assert_eq!(activated_rotation(&board), Rotate::D180);
+}
+
+#[test]
+fn rotate_half_twice_returns_to_spawn() {
+    for tetrimino in randomizer::TETRIMINOS {
+        let mut board = Board::new(10, 8);
+        board
+            .apply_spawn_falling(board.try_spawn_falling(tetrimino).unwrap())
+            .unwrap();
+        let spawned = board.get_falling_blocks();
+        for _ in 0..2 {
+            board
+                .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Half).unwrap())
+                .unwrap();
+        }
+        assert_eq!(board.get_falling_blocks(), spawned, "{tetrimino:?}");
+    }
+}
+
+fn falling_cells(board: &Board) -> Vec<(isize, isize)> {
+    let mut cells = board
+        .get_falling_blocks()
+        .iter()
+        .map(|f| (f.location.x as isize, f.location.y as isize))
+        .collect::<Vec<_>>();
+    cells.sort();
+    cells
+}
+
+#[test]
+fn bitboard_follows_board() {
+    let systems = [
+        RotationSystemKind::Srs,
+        RotationSystemKind::SrsPlus,
+        RotationSystemKind::Ars,
+        RotationSystemKind::Nes,
+    ];
+    let kinds = [
+        Tetrimino::I,
+        Tetrimino::O,
+        Tetrimino::T,
+        Tetrimino::J,
+        Tetrimino::L,
+        Tetrimino::S,
+        Tetrimino::Z,
+    ];
+    let dirs = [
+        RotateDirection::Right,
+        RotateDirection::Half,
+        RotateDirection::Left,
+        RotateDirection::Left,
+        RotateDirection::Right,
+    ];
+    for system in systems {
+        for kind in kinds {
+            let mut board = Board::new(10, 22).with_rotation_system(system);
+            for x in [0, 1, 2, 7, 8, 9] {
+                *board.get_mut(x, 21).unwrap() = Tile::Placed(1);
+            }
+            board
+                .apply_spawn_falling(board.try_spawn_falling(kind).unwrap())
+                .unwrap();
+            let bitboard = BitBoard::try_from(&board).unwrap();
+            let mut piece = board.active_piece().unwrap();
+            assert_eq!(bitboard.spawn(kind), Some(piece));
+
+            for dir in dirs.clone() {
+                board.apply_step(board.try_step().unwrap()).unwrap();
+                piece = bitboard.try_move(&piece, 0, 1).unwrap();
+                let plan = board.try_rotate_falling(dir.clone()).unwrap();
+                let (rotated, kick_idx) = bitboard.try_rotate(&piece, dir).unwrap();
+                assert_eq!(plan.kick_idx, kick_idx);
+                board.apply_rotate_falling(plan).unwrap();
+                piece = rotated;
+                let mut cells = bitboard.cells(&piece).to_vec();
+                cells.sort();
+                assert_eq!(falling_cells(&board), cells, "{system:?} {kind:?}");
+            }
+
+            let dropped = bitboard.hard_drop(&piece);
+            board.hard_drop().unwrap();
+            let mut cells = bitboard.cells(&dropped).to_vec();
+            cells.sort();
+            assert_eq!(falling_cells(&board), cells, "{system:?} {kind:?}");
+        }
+    }
+}
+
+#[test]
+fn bitboard_lock_clears_lines() {
+    let mut bitboard = BitBoard::new(4, 4).unwrap();
+    let o = ActivePiece {
+        kind: Tetrimino::O,
+        rotation: Rotate::D0,
+        origin: (0, 2),
+    };
+    bitboard.place(&o);
+    assert_eq!(bitboard.rows(), &[0, 0, 0b0011, 0b0011]);
+    assert!(bitboard.is_filled(-1, 0));
+    assert!(!bitboard.is_filled(2, 3));
+    assert_eq!(bitboard.try_move(&o, 1, 0), None);
+
+    let dropped = bitboard.hard_drop(&ActivePiece {
+        origin: (2, 0),
+        ..o
+    });
+    assert_eq!(dropped.origin, (2, 2));
+    assert_eq!(bitboard.lock(&dropped), 2);
+    assert!(bitboard.is_perfect_clear());
+}
+
+#[test]
+fn bitboard_rejects_wide_board() {
+    // 비트보드로 옮길 수 없는 보드에서는 탐색하는 쪽이 아무것도 돌려주지 않는다
+    let board = Board::new(20, 20);
+    assert_eq!(BitBoard::try_from(&board), Err(EngineError::OutOfBounds));
+    assert_eq!(BitBoard::new(0, 20), Err(EngineError::OutOfBounds));
+    assert_eq!(BitBoard::new(17, 20), Err(EngineError::OutOfBounds));
+    assert!(movegen::placements(&board, Tetrimino::T).is_empty());
+    assert_eq!(finesse::analyze(&board, Tetrimino::T, &[]), None);
+
+    let mut game = Game::new(GameConfig {
+        width: 20,
+        seed: 7,
+        ..Default::default()
+    });
+    game.tick(&[]);
+    assert!(game.current().is_some());
+    assert_eq!(perfect_clear::solve(&game, 4), None);
+    let mut bot = Bot::new(BotConfig {
+        pps: None,
+        ..BotConfig::normal()
+    });
+    assert!(bot.think(&game).is_none());
+    assert!(bot.tick(&game).is_empty());
+}
+
+#[test]
+fn active_piece_kept_out_of_grid() {
+    let mut board = Board::new(10, 5);
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap())
+        .unwrap();
+    board
+        .apply_rotate_falling(board.try_rotate_falling(RotateDirection::Right).unwrap())
+        .unwrap();
+
+    assert!(
+        board
+            .board()
+            .iter()
+            .flatten()
+            .all(|t| !matches!(t, Tile::Falling(_)))
+    );
+    let piece = board.active_piece().unwrap();
+    assert_eq!(piece.rotation, Rotate::D90);
+    assert_eq!(
+        board.view_location(4, 2),
+        Some(Tile::Falling(FallingBlock {
+            kind: Tetrimino::T,
+            rotation: Rotate::D90,
+            id: 2,
+        }))
+    );
+
+    // 예전 칸 배열로 주고받아도 떨어지는 블록이 살아난다
+    let json = serde_json::to_string(&board).unwrap();
+    let restored: Board = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.active_piece(), Some(piece));
+    assert_eq!(format!("{restored}"), format!("{board}"));
+
+    board.set_location(4, 2, Tile::Empty).unwrap();
+    assert_eq!(board.active_piece(), None);
+}
+
+#[test]
+fn spawn_centered_on_any_width() {
+    for (width, i_x, t_x) in [(4, 0, 1), (10, 3, 4), (12, 4, 5)] {
+        let mut board = Board::new(width, 26);
+        board
+            .apply_spawn_falling(board.try_spawn_falling(Tetrimino::I).unwrap())
+            .unwrap();
+        let cells = falling_cells(&board);
+        assert_eq!(cells.first(), Some(&(i_x, 4)), "{width}");
+        board.remove_falling_blocks();
+
+        board
+            .apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap())
+            .unwrap();
+        // 0번 블록이 튀어나온 칸
+        let top = &board.get_falling_blocks()[0].location;
+        assert_eq!((top.x, top.y), (t_x, 3), "{width}");
+        board.remove_falling_blocks();
+
+        assert_eq!(board.push_garbage_line(width - 1), Ok(()));
+        assert_eq!(
+            board.push_garbage_line(width),
+            Err(EngineError::OutOfBounds)
+        );
+        assert_eq!(board.line(25).map(|l| l.len()), Some(width));
+    }
+    assert!(matches!(
+        Board::new(3, 26).try_spawn_falling(Tetrimino::I),
+        Err(EngineError::OutOfBounds)
+    ));
+}
+
+#[test]
+fn visible_height_from_board_height() {
+    assert_eq!(Board::new(10, 26).visible_height(), 20);
+    assert_eq!(Board::new(10, 26).buffer_height(), 6);
+    assert_eq!(Board::new(10, 40).visible_height(), 34);
+    assert_eq!(Board::new(10, 5).buffer_height(), 3);
+    assert_eq!(Board::new(10, 2).visible_height(), 0);
+
+    let mut board = Board::new(10, 40);
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::O).unwrap())
+        .unwrap();
+    assert_eq!(falling_cells(&board)[0], (4, 3));
+}
+
+#[test]
+fn tall_board_piece_coordinates() {
+    // 좌표가 i8 을 넘는 보드에서도 끝까지 떨어진다
+    let mut board = Board::new(10, 200);
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::T).unwrap())
+        .unwrap();
+    assert_eq!(board.hard_drop(), Ok(196));
+    assert!(falling_cells(&board).iter().all(|(_, y)| *y >= 198));
+    board.lock_falling().unwrap();
+    assert_eq!(
+        board.line(199).unwrap()[4],
+        Tile::Placed(Tetrimino::T as u8)
+    );
+}
+
+#[test]
+fn engine_errors_instead_of_panics() {
+    let mut board = Board::new(10, 26);
+    assert_eq!(board.get(10, 0), None);
+    assert_eq!(board.get(0, 26), None);
+    assert_eq!(board.line(26), None);
+    assert_eq!(
+        board.set_location(10, 0, Tile::Placed(0)),
+        Err(EngineError::OutOfBounds)
+    );
+
+    // 떨어지는 블록이 없을 때
+    assert_eq!(
+        board.try_move_falling(MoveDirection::Left),
+        Err(EngineError::NoActivePiece)
+    );
+    assert_eq!(
+        board.try_rotate_falling(RotateDirection::Right).err(),
+        Some(EngineError::NoActivePiece)
+    );
+    assert_eq!(board.try_step(), Err(EngineError::NoActivePiece));
+    assert_eq!(board.place_falling(), Err(EngineError::NoActivePiece));
+    assert_eq!(board.hard_drop(), Err(EngineError::NoActivePiece));
+    assert!(board.lock_falling().is_err());
+
+    let spawn = board.try_spawn_falling(Tetrimino::T).unwrap();
+    board.apply_spawn_falling(spawn.clone()).unwrap();
+    assert_eq!(
+        board.try_spawn_falling(Tetrimino::T),
+        Err(EngineError::ActivePieceExists)
+    );
+    assert_eq!(
+        board.apply_spawn_falling(spawn),
+        Err(EngineError::ActivePieceExists)
+    );
+
+    // 오래된 계획은 적용 전에 다시 검사한다
+    let plan = board.try_move_falling(MoveDirection::Left).unwrap();
+    let to_be = plan[0].to_be.location.clone();
+    board
+        .set_location(to_be.x, to_be.y, Tile::Placed(0))
+        .unwrap();
+    assert!(matches!(
+        board.apply_move_falling(plan),
+        Err(EngineError::Blocked(_))
+    ));
+    assert_eq!(
+        board.apply_line_clear(vec![26]),
+        Err(EngineError::OutOfBounds)
+    );
+}
+
+/// 입력을 보드 API 로 그대로 눌러보고 하드 드롭 직전 상태를 돌려준다
+fn replay_placement(board: &Board, kind: Tetrimino, inputs: &[GameInput]) -> Board {
+    let mut board = board.clone();
+    board
+        .apply_spawn_falling(board.try_spawn_falling(kind).unwrap())
+        .unwrap();
+    for input in inputs {
+        let res = match input {
+            GameInput::MoveLeft => board
+                .try_move_falling(MoveDirection::Left)
+                .and_then(|plan| board.apply_move_falling(plan)),
+            GameInput::MoveRight => board
+                .try_move_falling(MoveDirection::Right)
+                .and_then(|plan| board.apply_move_falling(plan)),
+            GameInput::RotateLeft => board
+                .try_rotate_falling(RotateDirection::Left)
+                .and_then(|plan| board.apply_rotate_falling(plan)),
+            GameInput::RotateRight => board
+                .try_rotate_falling(RotateDirection::Right)
+                .and_then(|plan| board.apply_rotate_falling(plan)),
+            GameInput::RotateHalf => board
+                .try_rotate_falling(RotateDirection::Half)
+                .and_then(|plan| board.apply_rotate_falling(plan)),
+            GameInput::SoftDrop => board.try_step().and_then(|plan| board.apply_step(plan)),
+            GameInput::HardDrop => board.hard_drop().map(|_| ()),
+            GameInput::Hold => unreachable!(),
+        };
+        assert_eq!(res, Ok(()), "{input:?} in {inputs:?}");
+    }
+    board
+}
+
+#[test]
+fn movegen_empty_board() {
+    let board = Board::new(10, 26);
+    let expected = [
+        (Tetrimino::I, 17),
+        (Tetrimino::O, 9),
+        (Tetrimino::T, 34),
+        (Tetrimino::J, 34),
+        (Tetrimino::L, 34),
+        (Tetrimino::S, 17),
+        (Tetrimino::Z, 17),
+    ];
+    let bitboard = BitBoard::try_from(&board).unwrap();
+    for (kind, count) in expected {
+        let placements = movegen::placements(&board, kind);
+        let cells = placements
+            .iter()
+            .map(|p| {
+                let mut cells = bitboard.cells(&p.piece);
+                cells.sort();
+                cells
+            })
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(cells.len(), count, "{kind:?}");
+        assert_eq!(placements[0].inputs, vec![GameInput::HardDrop]);
+    }
+
+    let placements = movegen::placements(&board, Tetrimino::O);
+    let leftmost = placements
+        .iter()
+        .find(|p| bitboard.cells(&p.piece).iter().any(|(x, _)| *x == 0))
+        .unwrap();
+    assert_eq!(
+        leftmost.inputs,
+        [[GameInput::MoveLeft; 4].as_slice(), &[GameInput::HardDrop]].concat()
+    );
+}
+
+#[test]
+fn movegen_tuck_and_kick_spin() {
+    let mut board = Board::new(10, 26);
+    // 왼쪽 지붕 아래로 밀어 넣는 자리
+    for x in 0..4 {
+        *board.get_mut(x, 21).unwrap() = Tile::Placed(1);
+    }
+    // 오른쪽 T-스핀 더블 자리, (6, 23) 이 덮고 있어서 하드 드롭으로는 못 들어간다
+    for x in 4..10 {
+        if x != 7 {
+            *board.get_mut(x, 25).unwrap() = Tile::Placed(1);
+        }
+        if !(6..=8).contains(&x) {
+            *board.get_mut(x, 24).unwrap() = Tile::Placed(1);
+        }
+    }
+    *board.get_mut(6, 23).unwrap() = Tile::Placed(1);
+    let bitboard = BitBoard::try_from(&board).unwrap();
+
+    let tuck = movegen::placements(&board, Tetrimino::O)
+        .into_iter()
+        .find(|p| {
+            let mut cells = bitboard.cells(&p.piece);
+            cells.sort();
+            cells == [(0, 24), (0, 25), (1, 24), (1, 25)]
+        })
+        .unwrap();
+    assert!(tuck.inputs.contains(&GameInput::SoftDrop));
+
+    let placements = movegen::placements(&board, Tetrimino::T);
+    let tsd = placements
+        .iter()
+        .find(|p| p.tspin == TSpin::Full && bitboard.cells(&p.piece).contains(&(7, 25)))
+        .unwrap();
+    assert_eq!(tsd.inputs.last(), Some(&GameInput::HardDrop));
+
+    // 모든 입력이 실제 보드에서 같은 자리, 같은 판정으로 끝난다
+    for kind in [Tetrimino::O, Tetrimino::T, Tetrimino::I, Tetrimino::S] {
+        for placement in movegen::placements(&board, kind) {
+            let replayed = replay_placement(&board, kind, &placement.inputs);
+            let mut cells = bitboard.cells(&placement.piece).to_vec();
+            cells.sort();
+            assert_eq!(falling_cells(&replayed), cells, "{placement:?}");
+            assert_eq!(replayed.detect_tspin(), placement.tspin, "{placement:?}");
+        }
+    }
+}
+
+#[test]
+fn bot_weights_prefer_flat_board_without_holes() {
+    let weights = BotWeights::default();
+    let mut flat = BitBoard::new(10, 26).unwrap();
+    let mut holed = BitBoard::new(10, 26).unwrap();
+    let mut tower = BitBoard::new(10, 26).unwrap();
+    for x in 0..8 {
+        flat.place(&ActivePiece {
+            kind: Tetrimino::O,
+            rotation: Rotate::D0,
+            origin: (x, 24),
+        });
+    }
+    holed.clone_from(&flat);
+    holed.place(&ActivePiece {
+        kind: Tetrimino::I,
+        rotation: Rotate::D0,
+        origin: (0, 22),
+    });
+    for y in 18..26 {
+        tower.place(&ActivePiece {
+            kind: Tetrimino::O,
+            rotation: Rotate::D0,
+            origin: (0, y),
+        });
+    }
+    assert!(weights.evaluate(&flat) > weights.evaluate(&holed));
+    assert!(weights.evaluate(&flat) > weights.evaluate(&tower));
+}
+
+#[test]
+fn bot_plays_without_topping_out() {
+    let mut game = Game::new(GameConfig {
+        seed: 7,
+        ..Default::default()
+    });
+    let mut bot = Bot::new(BotConfig {
+        pps: None,
+        ..BotConfig::normal()
+    });
+    // 첫 블록은 tick 안에서 나온다
+    game.tick(&[]);
+    let mut locks = 0;
+    while locks < 60 {
+        let inputs = bot.tick(&game);
+        assert!(!inputs.is_empty());
+        let events = game.tick(&inputs);
+        locks += events
+            .iter()
+            .filter(|e| matches!(e, GameEvent::Lock { .. }))
+            .count();
+        assert!(!game.is_over(), "topped out after {locks} pieces");
+    }
+    assert!(game.lines() >= 15, "{}", game.lines());
+}
+
+#[test]
+fn bot_pps_cap_and_tspin_slot() {
+    let mut board = Board::new(10, 26);
+    for x in 0..10 {
+        if x != 1 {
+            *board.get_mut(x, 25).unwrap() = Tile::Placed(1);
+        }
+        if x > 2 {
+            *board.get_mut(x, 24).unwrap() = Tile::Placed(1);
+        }
+    }
+    *board.get_mut(2, 23).unwrap() = Tile::Placed(1);
+    let mut game = Game::new(GameConfig {
+        randomizer: None,
+        ..Default::default()
+    })
+    .with_board(board);
+    for kind in [Tetrimino::T, Tetrimino::O, Tetrimino::I] {
+        game.push_next(kind);
+    }
+    game.tick(&[]);
+
+    let mut bot = Bot::new(BotConfig {
+        pps: Some(2.0),
+        use_hold: false,
+        ..BotConfig::hard()
+    });
+    let best = bot.think(&game).unwrap();
+    assert_eq!(best.placement.tspin, TSpin::Full);
+    assert!(!best.hold);
+
+    // 초당 2개면 30 tick 마다 한 번 놓는다
+    let waits = (0..30).take_while(|_| bot.tick(&game).is_empty()).count();
+    assert_eq!(waits, 29);
+}
+
+#[test]
+fn finesse_counts_das_as_one_input() {
+    let board = Board::new(10, 26);
+    let taps = vec![FinesseInput::Tap(MoveDirection::Left); 3];
+    let report = finesse::analyze(&board, Tetrimino::T, &taps).unwrap();
+    assert_eq!(
+        report.optimal_inputs,
+        [FinesseInput::Das(MoveDirection::Left)]
+    );
+    assert_eq!((report.used, report.optimal, report.faults), (3, 1, 2));
+
+    // 벽까지 이어진 이동은 DAS 로 본다
+    let inputs = [
+        GameInput::MoveLeft,
+        GameInput::MoveLeft,
+        GameInput::MoveLeft,
+        GameInput::HardDrop,
+    ];
+    let report = finesse::analyze_game_inputs(&board, Tetrimino::T, &inputs).unwrap();
+    assert!(report.is_perfect());
+
+    // 벽 앞에서 멈추면 탭 두 번
+    let inputs = [
+        GameInput::MoveLeft,
+        GameInput::MoveLeft,
+        GameInput::HardDrop,
+    ];
+    let report = finesse::analyze_game_inputs(&board, Tetrimino::T, &inputs).unwrap();
+    assert_eq!((report.used, report.optimal), (2, 2));
+}
+
+#[test]
+fn finesse_reports_wasted_rotations() {
+    let board = Board::new(10, 26);
+    let inputs = [
+        GameInput::RotateRight,
+        GameInput::RotateRight,
+        GameInput::RotateRight,
+        GameInput::MoveRight,
+        GameInput::HardDrop,
+    ];
+    let report = finesse::analyze_game_inputs(&board, Tetrimino::J, &inputs).unwrap();
+    assert_eq!(
+        report.optimal_inputs,
+        [
+            FinesseInput::Tap(MoveDirection::Right),
+            FinesseInput::Rotate(RotateDirection::Left)
+        ]
+    );
+    assert_eq!(report.faults, 2);
+    assert!(!report.is_perfect());
+
+    // 칸이 같으면 다른 회전 상태로 놓아도 같은 자리다
+    let report = finesse::analyze(
+        &board,
+        Tetrimino::I,
+        &[FinesseInput::Rotate(RotateDirection::Left)],
+    )
+    .unwrap();
+    assert_eq!(report.optimal, 1);
+    assert!(report.is_perfect());
+}
+
+#[test]
+fn finesse_tuck_needs_soft_drop() {
+    let mut board = Board::new(10, 26);
+    for x in 0..3 {
+        *board.get_mut(x, 23).unwrap() = Tile::Placed(1);
+    }
+    let inputs = [
+        vec![GameInput::SoftDrop; 30],
+        vec![GameInput::MoveLeft; 4],
+        vec![GameInput::HardDrop],
+    ]
+    .concat();
+    let report = finesse::analyze_game_inputs(&board, Tetrimino::O, &inputs).unwrap();
+    let mut cells = BitBoard::try_from(&board).unwrap().cells(&report.piece);
+    cells.sort();
+    assert_eq!(cells, [(0, 24), (0, 25), (1, 24), (1, 25)]);
+    assert_eq!(
+        report.optimal_inputs,
+        [
+            FinesseInput::SoftDrop,
+            FinesseInput::Das(MoveDirection::Left)
+        ]
+    );
+    assert!(report.is_perfect());
+}
+
+#[test]
+fn replay_resimulates_bot_game() {
+    let config = GameConfig {
+        seed: 3,
+        ..Default::default()
+    };
+    let mut game = Game::new(config.clone());
+    let mut replay = Replay::new(config);
+    let mut bot = Bot::new(BotConfig {
+        pps: Some(6.0),
+        ..BotConfig::easy()
+    });
+    let mut boards = vec![];
+    for _ in 0..600 {
+        let inputs = bot.tick(&game);
+        game.tick(&inputs);
+        replay.record(&inputs);
+        boards.push(game.board().view());
+    }
+    replay.finish(&game);
+    assert!(game.lines() > 0);
+
+    let json = serde_json::to_string(&replay).unwrap();
+    let replay: Replay = serde_json::from_str(&json).unwrap();
+    let player = ReplayPlayer::new(replay.clone()).unwrap();
+    let replayed = player.map(|step| step.board.view()).collect::<Vec<_>>();
+    assert_eq!(replayed, boards);
+
+    let verified = ReplayPlayer::new(replay.clone()).unwrap().verify().unwrap();
+    assert_eq!(verified.score(), game.score());
+
+    // 입력 하나를 빼면 결과가 달라진다
+    let mut tampered = replay.clone();
+    tampered.frames.remove(tampered.frames.len() / 2);
+    assert!(matches!(
+        ReplayPlayer::new(tampered).unwrap().verify(),
+        Err(ReplayError::Mismatch { .. })
+    ));
+
+    let mut future = replay.clone();
+    future.version = 99;
+    assert_eq!(
+        ReplayPlayer::new(future).err(),
+        Some(ReplayError::UnsupportedVersion(99))
+    );
+    let mut unordered = replay;
+    unordered.frames.swap(0, 1);
+    assert!(matches!(
+        ReplayPlayer::new(unordered),
+        Err(ReplayError::InvalidFrame(_))
+    ));
+}
+
+#[test]
+fn replay_records_pushed_next_and_game_over() {
+    let config = GameConfig {
+        randomizer: None,
+        ..Default::default()
+    };
+    let mut game = Game::new(config.clone());
+    let mut replay = Replay::new(config);
+    for _ in 0..40 {
+        game.push_next(Tetrimino::O);
+        replay.record_next(Tetrimino::O);
+        game.tick(&[GameInput::HardDrop]);
+        replay.record(&[GameInput::HardDrop]);
+    }
+    replay.finish(&game);
+    assert!(game.is_over());
+
+    let game_over_ticks = ReplayPlayer::new(replay.clone())
+        .unwrap()
+        .filter(|step| {
+            step.events
+                .iter()
+                .any(|e| matches!(e, GameEvent::GameOver { .. }))
+        })
+        .count();
+    assert_eq!(game_over_ticks, 1);
+    let verified = ReplayPlayer::new(replay).unwrap().verify().unwrap();
+    assert!(verified.is_over());
+}
+
+#[test]
+fn game_push_garbage_and_garbage_out() {
+    let config = GameConfig {
+        seed: 9,
+        ..Default::default()
+    };
+    let mut game = Game::new(config.clone());
+    let mut replay = Replay::new(config);
+    game.tick(&[]);
+    replay.record(&[]);
+    let bottom = game.board().y_len() - 1;
+
+    let lines = vec![vec![3], vec![3]];
+    assert!(game.push_garbage(&lines).is_empty());
+    replay.record_garbage(&lines);
+    assert_eq!(game.board().get(3, bottom), Some(&Tile::Empty));
+    assert_eq!(
+        game.board().get(0, bottom),
+        Some(&Tile::Placed(GARBAGE_TILE))
+    );
+    assert_eq!(
+        game.board().get(0, bottom - 1),
+        Some(&Tile::Placed(GARBAGE_TILE))
+    );
+
+    // 쌓인 블록이 천장에 닿을 때까지 넣으면 게임이 끝난다
+    let mut events = vec![];
+    for _ in 0..game.board().y_len() {
+        let lines = vec![vec![0]];
+        events.extend(game.push_garbage(&lines));
+        replay.record_garbage(&lines);
+        events.extend(game.tick(&[GameInput::HardDrop]));
+        replay.record(&[GameInput::HardDrop]);
+        if game.is_over() {
+            break;
+        }
+    }
+    assert!(game.is_over());
+    assert!(
+        events
+            .iter()
+            .any(|e| matches!(e, GameEvent::GameOver { .. }))
+    );
+    assert!(game.push_garbage(&[vec![0]]).is_empty());
+    replay.finish(&game);
+
+    let json = serde_json::to_string(&replay).unwrap();
+    let replay: Replay = serde_json::from_str(&json).unwrap();
+    assert!(
+        ReplayPlayer::new(replay)
+            .unwrap()
+            .verify()
+            .unwrap()
+            .is_over()
+    );
+}
+
+#[test]
+fn replay_records_undo_and_initial_board() {
+    let mut board = Board::new_common();
+    for x in 1..10 {
+        *board.get_mut(x, 25).unwrap() = Tile::Placed(GARBAGE_TILE);
+    }
+    let config = GameConfig {
+        seed: 5,
+        history_len: 4,
+        ..Default::default()
+    };
+    let mut game = Game::new(config.clone()).with_board(board.clone());
+    let mut replay = Replay::new(config).with_board(board);
+    let mut boards = vec![];
+    for i in 0..12 {
+        if i % 4 == 3 {
+            assert!(game.undo());
+            replay.record_undo();
+        }
+        if i == 7 {
+            assert!(game.undo());
+            replay.record_undo();
+            assert!(game.redo());
+            replay.record_redo();
+        }
+        let inputs = [GameInput::MoveLeft, GameInput::HardDrop];
+        game.tick(&inputs);
+        replay.record(&inputs);
+        boards.push(game.board().view());
+    }
+    replay.finish(&game);
+
+    let json = serde_json::to_string(&replay).unwrap();
+    let replay: Replay = serde_json::from_str(&json).unwrap();
+    let replayed = ReplayPlayer::new(replay.clone())
+        .unwrap()
+        .map(|step| step.board.view())
+        .collect::<Vec<_>>();
+    assert_eq!(replayed, boards);
+    assert!(ReplayPlayer::new(replay.clone()).unwrap().verify().is_ok());
+
+    // 시작 보드가 없으면 다른 게임이 된다
+    let mut empty = replay;
+    empty.board = None;
+    assert!(matches!(
+        ReplayPlayer::new(empty).unwrap().verify(),
+        Err(ReplayError::Mismatch { .. })
+    ));
+}
+
+#[test]
+fn fumen_empty_and_garbage_field() {
+    let board = Board::new_common();
+    assert_eq!(board.to_fumen().unwrap(), "v115@vhAAgH");
+    let decoded = Board::from_fumen("https://harddrop.com/fumen/?v115@vhAAgH").unwrap();
+    assert_eq!(decoded.view(), board.view());
+
+    // 맨 아랫줄 0..9 가 가비지
+    let garbage = Board::from_fumen("v115@bhI8KeAgH").unwrap();
+    let bottom = garbage.y_len() - 1;
+    for x in 0..9 {
+        assert_eq!(garbage.get(x, bottom), Some(&Tile::Placed(GARBAGE_TILE)));
+    }
+    assert_eq!(garbage.get(9, bottom), Some(&Tile::Empty));
+    assert!(garbage.active_piece().is_none());
+    assert_eq!(garbage.to_fumen().unwrap(), "v115@bhI8KeAgH");
+}
+
+#[test]
+fn fumen_pages_lock_piece_and_clear_lines() {
+    let mut board = Board::from_fumen("v115@bhI8KeAgH").unwrap();
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::I).unwrap())
+        .unwrap();
+    let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
+    board.apply_rotate_falling(plan).unwrap();
+    for _ in 0..5 {
+        if let Ok(plan) = board.try_move_falling(MoveDirection::Right) {
+            board.apply_move_falling(plan).unwrap();
+        }
+    }
+    board.hard_drop().unwrap();
+    let first = fumen::FumenPage::new(board.clone());
+
+    let mut locked = board.clone();
+    locked.lock_falling().unwrap();
+    let second = fumen::FumenPage {
+        comment: "I 세로로 한 줄".into(),
+        ..fumen::FumenPage::new(locked.clone())
+    };
+    let third = fumen::FumenPage {
+        piece: None,
+        ..second.clone()
+    };
+
+    let encoded = fumen::encode(&[first, second, third]).unwrap();
+    let pages = fumen::decode(&encoded).unwrap();
+    assert_eq!(pages.len(), 3);
+    assert_eq!(pages[0].piece, board.active_piece());
+    assert_eq!(pages[0].comment, "");
+    assert_eq!(pages[1].board.view(), locked.view());
+    assert_eq!(pages[1].comment, "I 세로로 한 줄");
+    // 필드와 주석이 같은 페이지는 앞 페이지에서 이어받는다
+    assert_eq!(pages[2].board.view(), locked.view());
+    assert_eq!(pages[2].comment, "I 세로로 한 줄");
+    assert!(pages[2].piece.is_none());
+    assert_eq!(fumen::encode(&pages).unwrap(), encoded);
+
+    let restored = Board::from_fumen(&encoded).unwrap();
+    assert_eq!(restored.view(), board.view());
+}
+
+#[test]
+fn fumen_active_piece_in_every_rotation() {
+    for kind in [
+        Tetrimino::I,
+        Tetrimino::O,
+        Tetrimino::T,
+        Tetrimino::J,
+        Tetrimino::L,
+        Tetrimino::S,
+        Tetrimino::Z,
+    ] {
+        let mut board = Board::new_common();
+        board
+            .apply_spawn_falling(board.try_spawn_falling(kind).unwrap())
+            .unwrap();
+        for _ in 0..4 {
+            let restored = Board::from_fumen(&board.to_fumen().unwrap()).unwrap();
+            assert_eq!(restored.view(), board.view(), "{kind:?}");
+            let plan = board.try_rotate_falling(RotateDirection::Right).unwrap();
+            board.apply_rotate_falling(plan).unwrap();
+        }
+    }
+}
+
+#[test]
+fn fumen_errors() {
+    assert_eq!(
+        Board::new(11, 26).to_fumen(),
+        Err(FumenError::UnsupportedBoard)
+    );
+    let mut tall = Board::new(10, 30);
+    tall.set_location(0, 0, Tile::Placed(GARBAGE_TILE)).unwrap();
+    assert_eq!(tall.to_fumen(), Err(FumenError::UnsupportedBoard));
+    assert_eq!(
+        Board::from_fumen("vhAAgH").err(),
+        Some(FumenError::UnsupportedVersion)
+    );
+    assert_eq!(
+        Board::from_fumen("v115@vh!AgH").err(),
+        Some(FumenError::InvalidData)
+    );
+    assert_eq!(
+        Board::from_fumen("v115@vhAA").err(),
+        Some(FumenError::InvalidData)
+    );
+}
+
+#[test]
+fn board_text_round_trip() {
+    let mut game = Game::new(GameConfig {
+        seed: 7,
+        ..Default::default()
+    });
+    let mut bot = Bot::new(BotConfig::hard());
+    game.tick(&[]);
+    for _ in 0..200 {
+        let inputs = bot.tick(&game);
+        game.tick(&inputs);
+    }
+    let mut board = game.board().clone();
+    board.push_garbage_line(3).unwrap();
+    let text = board.to_string();
+    let parsed: Board = text.parse().unwrap();
+    assert_eq!(parsed.view(), board.view());
+    assert_eq!(parsed.active_piece(), board.active_piece());
+    assert_eq!(parsed.to_string(), text);
+
+    assert_eq!("".parse::<Board>().err(), Some(ParseBoardError::Empty));
+    assert_eq!(
+        "0...\n1....".parse::<Board>().err(),
+        Some(ParseBoardError::UnevenLine(1))
+    );
+    assert_eq!(
+        "0..x.".parse::<Board>().err(),
+        Some(ParseBoardError::InvalidTile {
+            x: 2,
+            y: 0,
+            tile: 'x'
+        })
+    );
+    assert_eq!(
+        "0.TT.\n1.TT.".parse::<Board>().err(),
+        Some(ParseBoardError::InvalidFalling)
+    );
+
+    // i8 을 넘는 줄에 있는 떨어지는 블록도 그대로 읽는다
+    let mut tall = Board::new(10, 200);
+    tall.apply_spawn_falling(tall.try_spawn_falling(Tetrimino::T).unwrap())
+        .unwrap();
+    tall.hard_drop().unwrap();
+    let parsed: Board = tall.to_string().parse().unwrap();
+    assert_eq!(parsed.active_piece(), tall.active_piece());
+}
+
+fn perfect_clear_game(filled: &[(usize, usize)], next: &[Tetrimino]) -> Game {
+    let mut board = Board::new_common();
+    for (x, y) in filled {
+        board
+            .set_location(*x, *y, Tile::Placed(GARBAGE_TILE))
+            .unwrap();
+    }
+    let mut game = Game::new(GameConfig {
+        randomizer: None,
+        ..Default::default()
+    })
+    .with_board(board);
+    for kind in next {
+        game.push_next(*kind);
+    }
+    game.tick(&[]);
+    game
+}
+
+#[test]
+fn perfect_clear_two_lines_on_empty_board() {
+    use Tetrimino::*;
+    let mut game = perfect_clear_game(&[], &[I, O, I, O, O]);
+    let steps = perfect_clear::solve(&game, 2).unwrap();
+    assert_eq!(steps.len(), 5);
+    for step in &steps {
+        game.tick(&step.inputs());
+    }
+    assert!(
+        game.board()
+            .board()
+            .iter()
+            .flatten()
+            .all(|t| *t == Tile::Empty)
+    );
+
+    assert!(perfect_clear::solve(&perfect_clear_game(&[], &[S, Z, S, Z, S]), 2).is_none());
+}
+
+#[test]
+fn perfect_clear_uses_hold_and_kicks() {
+    use Tetrimino::*;
+    // 맨 아랫줄에 I 자리 네 칸만 비어 있다
+    let bottom = (4..10).map(|x| (x, 25)).collect::<Vec<_>>();
+    let game = perfect_clear_game(&bottom, &[O, I]);
+    let steps = perfect_clear::solve(&game, 1).unwrap();
+    assert_eq!(steps.len(), 1);
+    assert!(steps[0].hold);
+    assert_eq!(steps[0].placement.piece.kind, I);
+    assert_eq!(steps[0].inputs()[0], GameInput::Hold);
+
+    // 두 번째 블록에서 홀드해야 하는 순서는 홀드를 쓰지 않는 규칙에서 풀리지 않는다
+    let bottom = (8..10).map(|x| (x, 25)).collect::<Vec<_>>();
+    let game = perfect_clear_game(&bottom, &[I, O, I]);
+    assert!(perfect_clear::solve(&game, 1).unwrap()[1].hold);
+    let mut board = Board::new_common();
+    for (x, y) in &bottom {
+        board
+            .set_location(*x, *y, Tile::Placed(GARBAGE_TILE))
+            .unwrap();
+    }
+    let mut game = Game::new(GameConfig {
+        randomizer: None,
+        hold_mode: HoldMode::Disabled,
+        ..Default::default()
+    })
+    .with_board(board);
+    for kind in [I, O, I] {
+        game.push_next(kind);
+    }
+    game.tick(&[]);
+    assert!(perfect_clear::solve(&game, 1).is_none());
+
+    // 위에서 떨어뜨리면 (3, 24) 에 걸려서 바닥에 닿은 뒤 돌려 넣어야 하는 S 자리
+    let hole = [(4, 24), (5, 24), (3, 25), (4, 25)];
+    let filled = (24..26)
+        .flat_map(|y| (0..10).map(move |x| (x, y)))
+        .filter(|cell| !hole.contains(cell))
+        .collect::<Vec<_>>();
+    let mut game = perfect_clear_game(&filled, &[S]);
+    let steps = perfect_clear::solve(&game, 2).unwrap();
+    let inputs = &steps[0].placement.inputs;
+    let soft_drop = inputs
+        .iter()
+        .position(|i| *i == GameInput::SoftDrop)
+        .unwrap();
+    assert!(inputs[soft_drop..].contains(&GameInput::RotateRight));
+    game.tick(&steps[0].inputs());
+    assert!(
+        game.board()
+            .board()
+            .iter()
+            .flatten()
+            .all(|t| *t == Tile::Empty)
+    );
+}
+
+#[test]
+fn garbage_modes() {
+    let attack = |mode: GarbageMode| GarbageGenerator::new(mode, 10, 42).attack(4);
+
+    let clean = attack(GarbageMode::Clean);
+    assert!(
+        clean
+            .iter()
+            .all(|holes| holes.len() == 1 && *holes == clean[0])
+    );
+    assert_eq!(attack(GarbageMode::Messy { change_rate: 0.0 }), clean);
+    let messy = attack(GarbageMode::Messy { change_rate: 1.0 });
+    assert!(messy.windows(2).all(|w| w[0] != w[1]));
+    let cheese = attack(GarbageMode::Cheese);
+    assert!(cheese.windows(2).all(|w| w[0] != w[1]));
+
+    let multi = attack(GarbageMode::MultiHole { holes: 3 });
+    for holes in &multi {
+        assert_eq!(holes.len(), 3);
+        assert!(holes.windows(2).all(|w| w[0] < w[1]));
+    }
+    let wide = GarbageGenerator::new(GarbageMode::MultiHole { holes: 20 }, 10, 0).attack(1);
+    assert_eq!(wide[0].len(), 9);
+    assert_eq!(attack(GarbageMode::Solid), vec![Vec::<usize>::new(); 4]);
+
+    // 같은 시드면 같은 구멍, 공격이 바뀌면 새 구멍
+    let mut a = GarbageGenerator::new(GarbageMode::Clean, 10, 7);
+    let mut b = GarbageGenerator::new(GarbageMode::Clean, 10, 7);
+    let first = a.attack(2);
+    assert_eq!(first, b.attack(2));
+    assert_ne!(a.attack(1)[0], first[0]);
+
+    // 블록 순서와 같은 시드를 받아도 구멍은 따로 정해진다
+    let is_shared = (0..20).all(|seed| {
+        let hole = GarbageGenerator::new(GarbageMode::Cheese, 10, seed).attack(1)[0][0];
+        hole == SeededRng::new(seed).below(10) as usize
+    });
+    assert!(!is_shared);
+
+    let mut board = Board::new(4, 3);
+    board.push_garbage(&[0, 2]).unwrap();
+    board.push_garbage(&[]).unwrap();
+    assert_eq!(board.to_string(), "\n_____\n0....\n1.g.g\n2gggg\n_0123\n");
+    assert_eq!(board.push_garbage(&[4]), Err(EngineError::OutOfBounds));
+}
+
+#[test]
+fn piece_sets() {
+    // 내장 테트로미노는 회전 방식을 옮긴 세트와 같은 모양이다
+    let srs = PieceSet::tetrominoes();
+    let board = BitBoard::new(10, 26).unwrap();
+    let t = board.spawn(Tetrimino::T).unwrap();
+    let t_set = srs.spawn(&board, 2).unwrap();
+    assert_eq!(srs.cells(&t_set), Some(board.cells(&t).to_vec()));
+    let (rotated, _) = board.try_rotate(&t, RotateDirection::Right).unwrap();
+    let (rotated_set, _) = srs
+        .try_rotate(&board, &t_set, RotateDirection::Right)
+        .unwrap();
+    assert_eq!(
+        srs.cells(&rotated_set),
+        Some(board.cells(&rotated).to_vec())
+    );
+
+    // 다른 세트에서 온 블록은 패닉 없이 거절한다
+    let foreign = SetPiece { kind: 99, ..t_set };
+    assert_eq!(srs.cells(&foreign), None);
+    assert!(srs.collides(&board, &foreign));
+    assert_eq!(
+        srs.try_rotate(&board, &foreign, RotateDirection::Right),
+        None
+    );
+    assert_eq!(srs.lock(&mut board.clone(), &foreign), None);
+
+    // 펜토미노는 돌려도 서로 겹치는 모양이 없다
+    let pentominoes = PieceSet::pentominoes();
+    let normalize = |cells: &[(i8, i8)]| {
+        let min_x = cells.iter().map(|c| c.0).min().unwrap();
+        let min_y = cells.iter().map(|c| c.1).min().unwrap();
+        let mut cells = cells
+            .iter()
+            .map(|(x, y)| (x - min_x, y - min_y))
+            .collect::<Vec<_>>();
+        cells.sort_unstable();
+        cells
+    };
+    let mut seen = std::collections::HashMap::new();
+    for (kind, piece) in pentominoes.pieces().iter().enumerate() {
+        for shape in &piece.shapes {
+            let owner = *seen.entry(normalize(shape)).or_insert(kind);
+            assert_eq!(owner, kind, "{} overlaps", piece.name);
+        }
+    }
+    assert_eq!(pentominoes.len(), 18);
+
+    // 3칸 I 하나로 3칸 보드가 비워진다
+    let trominoes = PieceSet::trominoes();
+    let mut board = BitBoard::new(3, 8).unwrap();
+    let piece = trominoes.spawn(&board, 0).unwrap();
+    let dropped = trominoes.hard_drop(&board, &piece);
+    assert_eq!(trominoes.lock(&mut board, &dropped), Some(1));
+    assert!(board.is_perfect_clear());
+
+    // 큰 O 는 2줄을 채운다
+    let big = PieceSet::tetrominoes().big();
+    let mut board = BitBoard::new(4, 10).unwrap();
+    let piece = big.spawn(&board, 1).unwrap();
+    assert_eq!(big.cells(&piece).map(|cells| cells.len()), Some(16));
+    let dropped = big.hard_drop(&board, &piece);
+    assert_eq!(big.lock(&mut board, &dropped), Some(4));
+
+    let mut bag = SetBag::new(&pentominoes, 3);
+    let mut kinds = (0..18).map(|_| bag.next_kind()).collect::<Vec<_>>();
+    kinds.sort_unstable();
+    assert_eq!(kinds, (0..18).collect::<Vec<_>>());
+
+    assert_eq!(PieceSet::new(vec![]), Err(PieceSetError::Empty));
+    let mut broken = trominoes.pieces().to_vec();
+    broken[1].shapes[2].pop();
+    assert_eq!(PieceSet::new(broken), Err(PieceSetError::InvalidShape(1)));
+    let mut broken = trominoes.pieces().to_vec();
+    broken[0].kicks.half.clear();
+    assert_eq!(PieceSet::new(broken), Err(PieceSetError::InvalidKicks(0)));
+    let kicks = trominoes.pieces()[0].kicks.clone();
+    assert_eq!(
+        PieceDef::rotating("bad", &[(0, 0)], (1, 0), kicks.clone()),
+        Err(PieceSetError::InvalidPivot)
+    );
+    assert_eq!(
+        PieceDef::rotating("far", &[(-128, 0)], (0, 0), kicks),
+        Err(PieceSetError::InvalidPivot)
+    );
+}
+
+#[test]
+fn lock_delay_resets() {
+    // 바닥에 내려놓은 O 를 `inputs` 대로 움직여서 고정되기까지 걸린 tick
+    let ticks_to_lock = |lock_reset: LockReset, inputs: &dyn Fn(u32) -> Vec<GameInput>| {
+        let mut game = Game::new(GameConfig {
+            width: 10,
+            height: 6,
+            randomizer: None,
+            lock_delay: 10,
+            lock_reset,
+            ..Default::default()
+        });
+        game.push_next(Tetrimino::O);
+        game.push_next(Tetrimino::T);
+        game.tick(&[]);
+        game.tick(&[GameInput::SoftDrop; 3]);
+        (1..200)
+            .find(|n| {
+                game.tick(&inputs(*n))
+                    .iter()
+                    .any(|e| matches!(e, GameEvent::Lock { .. }))
+            })
+            .unwrap()
+    };
+    let wiggle = |n: u32| match n {
+        ..=50 if n.is_multiple_of(2) => vec![GameInput::MoveLeft],
+        ..=50 => vec![GameInput::MoveRight],
+        _ => vec![],
+    };
+
+    assert_eq!(ticks_to_lock(LockReset::StepReset, &|_| vec![]), 9);
+    assert_eq!(ticks_to_lock(LockReset::StepReset, &wiggle), 9);
+    assert_eq!(ticks_to_lock(LockReset::Infinity, &wiggle), 59);
+    // 마지막 되돌리기를 쓰면 바닥에 있는 블록은 바로 고정된다
+    assert_eq!(ticks_to_lock(LockReset::MoveReset { limit: 3 }, &wiggle), 3);
+    assert_eq!(
+        ticks_to_lock(LockReset::MoveReset { limit: 100 }, &wiggle),
+        59
+    );
+
+    // 더 낮은 줄로 내려가면 횟수가 다시 찬다
+    let mut board = Board::new(10, 6);
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::O).unwrap())
+        .unwrap();
+    let mut lock_delay = LockDelay::new(10, LockReset::MoveReset { limit: 3 });
+    lock_delay.spawn(&board);
+    board
+        .apply_move_falling(board.try_move_falling(MoveDirection::Left).unwrap())
+        .unwrap();
+    lock_delay.on_move(&board);
+    assert_eq!(lock_delay.resets_left(), Some(3));
+    board.hard_drop().unwrap();
+    assert!(!lock_delay.tick(&board));
+    board
+        .apply_move_falling(board.try_move_falling(MoveDirection::Left).unwrap())
+        .unwrap();
+    lock_delay.on_move(&board);
+    assert_eq!(lock_delay.resets_left(), Some(2));
+    assert_eq!(lock_delay.tick_count(), 0);
+}
+
+#[test]
+fn input_handler_das_arr_sdf() {
+    use GameInput::*;
+    let run = |handler: &mut InputHandler, ticks: usize| {
+        (0..ticks)
+            .map(|_| handler.tick(12, 10, 26))
+            .collect::<Vec<_>>()
+    };
+
+    let mut handler = InputHandler::new(HandlingConfig {
+        das: 3,
+        arr: 2,
+        sdf: 6,
+        das_cut: 0,
+    });
+    handler.press(MoveLeft);
+    assert_eq!(
+        run(&mut handler, 6),
+        vec![
+            vec![MoveLeft],
+            vec![],
+            vec![MoveLeft],
+            vec![],
+            vec![MoveLeft],
+            vec![],
+        ]
+    );
+    // 나중에 누른 방향이 이기고, 떼면 남은 방향이 DAS 부터 다시 시작한다
+    handler.press(MoveRight);
+    assert_eq!(run(&mut handler, 1), vec![vec![MoveRight]]);
+    handler.release(MoveRight);
+    assert_eq!(run(&mut handler, 3), vec![vec![], vec![], vec![MoveLeft]]);
+    handler.release(MoveLeft);
+    assert_eq!(run(&mut handler, 2), vec![vec![], vec![]]);
+
+    // SDF 6 에 중력 12 면 2 tick 에 한 칸
+    handler.handle(HandlingEvent::Press(SoftDrop));
+    assert_eq!(
+        run(&mut handler, 3),
+        vec![vec![SoftDrop], vec![SoftDrop], vec![]]
+    );
+    handler.handle(HandlingEvent::Release(SoftDrop));
+    assert_eq!(run(&mut handler, 2), vec![vec![], vec![]]);
+
+    // DAS 가 찬 뒤 회전하면 das_cut 만큼 쉰다
+    let mut handler = InputHandler::new(HandlingConfig {
+        das: 2,
+        arr: 1,
+        sdf: 6,
+        das_cut: 2,
+    });
+    handler.press(MoveRight);
+    run(&mut handler, 2);
+    handler.press(RotateRight);
+    assert_eq!(
+        run(&mut handler, 3),
+        vec![vec![RotateRight], vec![], vec![MoveRight]]
+    );
+
+    // ARR 0 이면 DAS 가 찬 tick 에 벽까지 간다
+    let mut game = game_with_next(&[Tetrimino::T, Tetrimino::O]);
+    let mut handler = InputHandler::new(HandlingConfig {
+        das: 2,
+        arr: 0,
+        sdf: 0,
+        das_cut: 0,
+    });
+    game.tick(&[]);
+    handler.press(MoveLeft);
+    let (width, height) = (game.board().x_len(), game.board().y_len());
+    for _ in 0..2 {
+        let gravity_tick = game::level_to_gravity_tick(game.level());
+        game.tick(&handler.tick(gravity_tick, width, height));
+    }
+    let min_x = game
+        .board()
+        .get_falling_blocks()
+        .iter()
+        .map(|f| f.location.x)
+        .min();
+    assert_eq!(min_x, Some(0));
+
+    // 보드가 넓으면 그만큼 넣는다
+    assert_eq!(handler.tick(1, 100, 200), vec![MoveLeft; 100]);
+    handler.release(MoveLeft);
+    handler.press(SoftDrop);
+    let inputs = handler.tick(1, 100, 200);
+    assert_eq!(inputs.len(), 201);
+    assert!(inputs.iter().all(|i| *i == SoftDrop));
+}
+
+#[test]
+fn ghost_position_does_not_touch_board() {
+    let mut board: Board = r#"
+.....
+.....
+.....
+g...g
+gg.gg
+"#
+    .parse()
+    .unwrap();
+    assert_eq!(board.ghost_position(), None);
+    board
+        .apply_spawn_falling(board.try_spawn_falling_at(Tetrimino::T, 2, 0).unwrap())
+        .unwrap();
+    let before = board.view();
+    let ghost = board.ghost_position().unwrap();
+    let mut cells = ghost.map(|l| (l.x, l.y));
+    cells.sort_unstable();
+    assert_eq!(cells, [(1, 3), (2, 2), (2, 3), (3, 3)]);
+    assert_eq!(board.view(), before);
+
+    let expected = r#"
+______
+0..T..
+1.TTT.
+2..H..
+3gHHHg
+4gg.gg
+_01234
+"#;
+    assert_eq!(format!("{board:#}"), expected);
+    assert_eq!(format!("{board}"), expected.replace('H', "."));
+    assert!(board.board().iter().flatten().all(|t| *t != Tile::Hint(3)));
+}
+
+#[test]
+fn ghost_position_on_tall_board() {
+    // 고스트가 i8 을 넘는 줄까지 내려간다
+    let mut board = Board::new(10, 200);
+    board
+        .apply_spawn_falling(board.try_spawn_falling(Tetrimino::I).unwrap())
+        .unwrap();
+    let ghost = board.ghost_position().unwrap();
+    assert!(ghost.iter().all(|l| l.y == 199));
+}
@@ -5,11 +5,28 @@ use wasm_bindgen::prelude::*;
 
 use crate::Board;
 use crate::FallingBlockPlan;
+use crate::FumenPage;
+use crate::Game;
+use crate::GameConfig;
+use crate::GameInput;
+use crate::HandlingConfig;
+use crate::HandlingEvent;
+use crate::InputHandler;
+use crate::LockDelay;
+use crate::LockReset;
 use crate::MoveDirection;
+use crate::PieceRandomizer;
+use crate::Randomizer;
+use crate::RandomizerKind;
 use crate::RotateDirection;
+use crate::RotatePlan;
+use crate::RotationSystemKind;
 use crate::Tetrimino;
 use crate::Tile;
 use crate::TileAt;
+use crate::fumen;
+use crate::game;
+use crate::perfect_clear;
 
 #[wasm_bindgen]
 pub struct JsBoard {
@@ -37,24 +54,40 @@ impl JsBoard {
         self.inner.y_len()
     }
 
+    #[wasm_bindgen(getter)]
+    #[wasm_bindgen(js_name = visibleHeight)]
+    pub fn visible_height(&self) -> usize {
+        self.inner.visible_height()
+    }
+
+    #[wasm_bindgen(getter)]
+    #[wasm_bindgen(js_name = bufferHeight)]
+    pub fn buffer_height(&self) -> usize {
+        self.inner.buffer_height()
+    }
+
     #[wasm_bindgen(js_name = setLocation)]
     pub fn set_location(&mut self, x: usize, y: usize, tile: JsValue) -> Result<(), JsValue> {
-        Ok(*self.inner.location_mut(x, y) = from_value::<Tile>(tile)?)
+        let tile = from_value::<Tile>(tile)?;
+        match self.inner.set_location(x, y, tile) {
+            Ok(()) => Ok(()),
+            Err(err) => Err(to_value(&err)?),
+        }
     }
 
     #[wasm_bindgen(js_name = getLocation)]
     pub fn get_location(&self, x: usize, y: usize) -> Result<JsValue, JsValue> {
-        Ok(to_value(self.inner.location(x, y))?)
+        Ok(to_value(&self.inner.view_location(x, y))?)
     }
 
     #[wasm_bindgen(js_name = getBoard)]
     pub fn get_board(&self) -> Result<JsValue, JsValue> {
-        Ok(to_value(self.inner.board())?)
+        Ok(to_value(&self.inner.view())?)
     }
 
     #[wasm_bindgen(js_name = getLine)]
     pub fn get_line(&self, y: usize) -> Result<JsValue, JsValue> {
-        Ok(to_value(self.inner.line(y))?)
+        Ok(to_value(&self.inner.view().get(y))?)
     }
 
     #[wasm_bindgen(js_name = trySpawnFalling)]
@@ -83,7 +116,10 @@ impl JsBoard {
     #[wasm_bindgen(js_name = applySpawnFalling)]
     pub fn apply_spawn_falling(&mut self, tiles: JsValue) -> Result<(), JsValue> {
         let tiles = from_value::<Vec<TileAt>>(tiles)?;
-        Ok(self.inner.apply_spawn_falling(tiles))
+        match self.inner.apply_spawn_falling(tiles) {
+            Ok(()) => Ok(()),
+            Err(err) => Err(to_value(&err)?),
+        }
     }
 
     #[wasm_bindgen(js_name = getFallingBlocks)]
@@ -103,7 +139,10 @@ impl JsBoard {
     #[wasm_bindgen(js_name = applyMoveFalling)]
     pub fn apply_move_falling(&mut self, fallings: JsValue) -> Result<(), JsValue> {
         let tiles = from_value::<Vec<FallingBlockPlan>>(fallings)?;
-        Ok(self.inner.apply_move_falling(tiles))
+        match self.inner.apply_move_falling(tiles) {
+            Ok(()) => Ok(()),
+            Err(err) => Err(to_value(&err)?),
+        }
     }
 
     #[wasm_bindgen(js_name = tryRotateFalling)]
@@ -117,8 +156,18 @@ impl JsBoard {
 
     #[wasm_bindgen(js_name = applyRotateFalling)]
     pub fn apply_rotate_falling(&mut self, fallings: JsValue) -> Result<(), JsValue> {
-        let tiles = from_value::<Vec<FallingBlockPlan>>(fallings)?;
-        Ok(self.inner.apply_rotate_falling(tiles))
+        let plan = from_value::<RotatePlan>(fallings)?;
+        match self.inner.apply_rotate_falling(plan) {
+            Ok(()) => Ok(()),
+            Err(err) => Err(to_value(&err)?),
+        }
+    }
+
+    #[wasm_bindgen(js_name = setRotationSystem)]
+    pub fn set_rotation_system(&mut self, kind: JsValue) -> Result<(), JsValue> {
+        let kind = from_value::<RotationSystemKind>(kind)?;
+        self.inner.set_rotation_system(kind);
+        Ok(())
     }
 
     #[wasm_bindgen(js_name = tryStep)]
@@ -132,12 +181,31 @@ impl JsBoard {
     #[wasm_bindgen(js_name = applyStep)]
     pub fn apply_step(&mut self, fallings: JsValue) -> Result<(), JsValue> {
         let tiles = from_value::<Vec<FallingBlockPlan>>(fallings)?;
-        Ok(self.inner.apply_step(tiles))
+        match self.inner.apply_step(tiles) {
+            Ok(()) => Ok(()),
+            Err(err) => Err(to_value(&err)?),
+        }
     }
 
     #[wasm_bindgen(js_name = placeFalling)]
     pub fn place_falling(&mut self) -> Result<(), JsValue> {
-        Ok(self.inner.place_falling())
+        match self.inner.place_falling() {
+            Ok(()) => Ok(()),
+            Err(err) => Err(to_value(&err)?),
+        }
+    }
+
+    #[wasm_bindgen(js_name = detectTspin)]
+    pub fn detect_tspin(&self) -> Result<JsValue, JsValue> {
+        Ok(to_value(&self.inner.detect_tspin())?)
+    }
+
+    #[wasm_bindgen(js_name = lockFalling)]
+    pub fn lock_falling(&mut self) -> Result<JsValue, JsValue> {
+        match self.inner.lock_falling() {
+            Ok(res) => Ok(to_value(&res)?),
+            Err(err) => Err(to_value(&err)?),
+        }
     }
 
     #[wasm_bindgen(js_name = tryLineClear)]
@@ -148,22 +216,40 @@ impl JsBoard {
     #[wasm_bindgen(js_name = applyLineClear)]
     pub fn apply_line_clear(&mut self, to_clear_lines: JsValue) -> Result<(), JsValue> {
         let to_clear_lines = from_value::<Vec<usize>>(to_clear_lines)?;
-        Ok(self.inner.apply_line_clear(to_clear_lines))
+        match self.inner.apply_line_clear(to_clear_lines) {
+            Ok(()) => Ok(()),
+            Err(err) => Err(to_value(&err)?),
+        }
     }
 
     #[wasm_bindgen(js_name = hardDrop)]
-    pub fn hard_drop(&mut self) -> usize {
-        self.inner.hard_drop() as usize
+    pub fn hard_drop(&mut self) -> Result<usize, JsValue> {
+        match self.inner.hard_drop() {
+            Ok(count) => Ok(count as usize),
+            Err(err) => Err(to_value(&err)?),
+        }
     }
 
-    #[wasm_bindgen(js_name = showFallingHint)]
-    pub fn show_falling_hint(&mut self) {
-        self.inner.show_falling_hint()
+    #[wasm_bindgen(js_name = ghostPosition)]
+    pub fn ghost_position(&self) -> Result<JsValue, JsValue> {
+        Ok(to_value(&self.inner.ghost_position())?)
     }
 
-    #[wasm_bindgen(js_name = removeFallingHint)]
-    pub fn remove_falling_hint(&mut self) {
-        self.inner.remove_falling_hint()
+    /// 고스트를 `Hint` 칸으로 겹친 화면용 칸
+    #[wasm_bindgen(js_name = getBoardWithGhost)]
+    pub fn get_board_with_ghost(&self) -> Result<JsValue, JsValue> {
+        Ok(to_value(&self.inner.view_with_ghost())?)
+    }
+
+    #[wasm_bindgen(js_name = isBlockOut)]
+    pub fn is_block_out(&self, tiles: JsValue) -> Result<bool, JsValue> {
+        let tiles = from_value::<Vec<TileAt>>(tiles)?;
+        Ok(self.inner.is_block_out(&tiles))
+    }
+
+    #[wasm_bindgen(js_name = isLockOut)]
+    pub fn is_lock_out(&self) -> bool {
+        self.inner.is_lock_out()
     }
 
     #[wasm_bindgen(js_name = hasPlacedAbove)]
@@ -178,6 +264,275 @@ impl JsBoard {
 
     #[wasm_bindgen(js_name = pushGarbageLine)]
     pub fn push_garbage_line(&mut self, empty_x: usize) -> bool {
-        self.inner.push_garbage_line(empty_x)
+        self.inner.push_garbage_line(empty_x).is_ok()
+    }
+
+    #[wasm_bindgen(js_name = pushGarbage)]
+    pub fn push_garbage(&mut self, holes: JsValue) -> Result<bool, JsValue> {
+        let holes = from_value::<Vec<usize>>(holes)?;
+        Ok(self.inner.push_garbage(&holes).is_ok())
+    }
+
+    #[wasm_bindgen(js_name = toFumen)]
+    pub fn to_fumen(&self) -> Result<String, JsValue> {
+        match self.inner.to_fumen() {
+            Ok(fumen) => Ok(fumen),
+            Err(err) => Err(to_value(&err)?),
+        }
+    }
+
+    #[wasm_bindgen(js_name = fromFumen)]
+    pub fn from_fumen(fumen: &str) -> Result<Self, JsValue> {
+        match Board::from_fumen(fumen) {
+            Ok(inner) => Ok(Self { inner }),
+            Err(err) => Err(to_value(&err)?),
+        }
+    }
+}
+
+/// 여러 페이지 fumen 을 `FumenPage[]` 로 읽는다
+#[wasm_bindgen(js_name = decodeFumen)]
+pub fn decode_fumen(fumen: &str) -> Result<JsValue, JsValue> {
+    match fumen::decode(fumen) {
+        Ok(pages) => Ok(to_value(&pages)?),
+        Err(err) => Err(to_value(&err)?),
+    }
+}
+
+#[wasm_bindgen(js_name = encodeFumen)]
+pub fn encode_fumen(pages: JsValue) -> Result<String, JsValue> {
+    let pages = from_value::<Vec<FumenPage>>(pages)?;
+    match fumen::encode(&pages) {
+        Ok(fumen) => Ok(fumen),
+        Err(err) => Err(to_value(&err)?),
+    }
+}
+
+/// 기본 게임 설정, 시드 같은 값만 바꿔서 `JsGame` 에 넘긴다
+#[wasm_bindgen(js_name = defaultGameConfig)]
+pub fn default_game_config() -> Result<JsValue, JsValue> {
+    Ok(to_value(&GameConfig::default())?)
+}
+
+/// 레벨마다 한 칸 떨어지는 tick, `JsInputHandler.tick` 에 넘긴다
+#[wasm_bindgen(js_name = levelToGravityTick)]
+pub fn level_to_gravity_tick(level: u32) -> u32 {
+    game::level_to_gravity_tick(level)
+}
+
+#[wasm_bindgen]
+pub struct JsGame {
+    inner: Game,
+}
+
+#[wasm_bindgen]
+impl JsGame {
+    #[wasm_bindgen(constructor)]
+    pub fn new(config: JsValue) -> Result<Self, JsValue> {
+        let config = if config.is_undefined() || config.is_null() {
+            GameConfig::default()
+        } else {
+            from_value::<GameConfig>(config)?
+        };
+        Ok(Self {
+            inner: Game::new(config),
+        })
+    }
+
+    #[wasm_bindgen(js_name = pushNext)]
+    pub fn push_next(&mut self, tetrimino: JsValue) -> Result<(), JsValue> {
+        let tetrimino = from_value::<Tetrimino>(tetrimino)?;
+        self.inner.push_next(tetrimino);
+        Ok(())
+    }
+
+    pub fn tick(&mut self, inputs: JsValue) -> Result<JsValue, JsValue> {
+        let inputs = from_value::<Vec<GameInput>>(inputs)?;
+        Ok(to_value(&self.inner.tick(&inputs))?)
+    }
+
+    /// 줄마다 구멍 x 목록, 게임이 끝나면 `GameOver` 이벤트가 나온다
+    #[wasm_bindgen(js_name = pushGarbage)]
+    pub fn push_garbage(&mut self, lines: JsValue) -> Result<JsValue, JsValue> {
+        let lines = from_value::<Vec<Vec<usize>>>(lines)?;
+        Ok(to_value(&self.inner.push_garbage(&lines))?)
+    }
+
+    #[wasm_bindgen(js_name = getBoard)]
+    pub fn get_board(&self) -> Result<JsValue, JsValue> {
+        Ok(to_value(&self.inner.board().view())?)
+    }
+
+    /// 고스트를 `Hint` 칸으로 겹친 화면용 칸
+    #[wasm_bindgen(js_name = getBoardWithGhost)]
+    pub fn get_board_with_ghost(&self) -> Result<JsValue, JsValue> {
+        Ok(to_value(&self.inner.board().view_with_ghost())?)
+    }
+
+    #[wasm_bindgen(js_name = getHold)]
+    pub fn get_hold(&self) -> Result<JsValue, JsValue> {
+        Ok(to_value(&self.inner.hold())?)
+    }
+
+    /// `maxLines` 줄 안에 퍼펙트 클리어하는 순서, 없으면 null
+    #[wasm_bindgen(js_name = findPerfectClear)]
+    pub fn find_perfect_clear(&self, max_lines: usize) -> Result<JsValue, JsValue> {
+        Ok(to_value(&perfect_clear::solve(&self.inner, max_lines))?)
+    }
+
+    #[wasm_bindgen(js_name = getNext)]
+    pub fn get_next(&self) -> Result<JsValue, JsValue> {
+        Ok(to_value(self.inner.next())?)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn level(&self) -> u32 {
+        self.inner.level()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn lines(&self) -> u32 {
+        self.inner.lines()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn score(&self) -> u32 {
+        self.inner.score()
+    }
+
+    #[wasm_bindgen(getter)]
+    #[wasm_bindgen(js_name = isOver)]
+    pub fn is_over(&self) -> bool {
+        self.inner.is_over()
+    }
+
+    #[wasm_bindgen(getter)]
+    #[wasm_bindgen(js_name = canUndo)]
+    pub fn can_undo(&self) -> bool {
+        self.inner.can_undo()
+    }
+
+    #[wasm_bindgen(getter)]
+    #[wasm_bindgen(js_name = canRedo)]
+    pub fn can_redo(&self) -> bool {
+        self.inner.can_redo()
+    }
+
+    /// 마지막으로 놓은 블록을 되돌린다, 기록이 없으면 false
+    pub fn undo(&mut self) -> bool {
+        self.inner.undo()
+    }
+
+    pub fn redo(&mut self) -> bool {
+        self.inner.redo()
+    }
+}
+
+#[wasm_bindgen]
+pub struct JsInputHandler {
+    inner: InputHandler,
+}
+
+#[wasm_bindgen]
+impl JsInputHandler {
+    #[wasm_bindgen(constructor)]
+    pub fn new(config: JsValue) -> Result<Self, JsValue> {
+        let config = if config.is_undefined() || config.is_null() {
+            HandlingConfig::default()
+        } else {
+            from_value::<HandlingConfig>(config)?
+        };
+        Ok(Self {
+            inner: InputHandler::new(config),
+        })
+    }
+
+    pub fn press(&mut self, input: JsValue) -> Result<(), JsValue> {
+        let input = from_value::<GameInput>(input)?;
+        self.inner.press(input);
+        Ok(())
+    }
+
+    pub fn release(&mut self, input: JsValue) -> Result<(), JsValue> {
+        let input = from_value::<GameInput>(input)?;
+        self.inner.release(input);
+        Ok(())
+    }
+
+    /// 서버가 보낸 다른 보드의 누르고 뗀 키
+    pub fn handle(&mut self, event: JsValue) -> Result<(), JsValue> {
+        let event = from_value::<HandlingEvent>(event)?;
+        self.inner.handle(event);
+        Ok(())
+    }
+
+    /// 이번 tick 에 넣을 입력 목록, 보드 크기만큼 반복해서 ARR, SDF 0 을 처리한다
+    pub fn tick(
+        &mut self,
+        gravity_tick: u32,
+        width: usize,
+        height: usize,
+    ) -> Result<JsValue, JsValue> {
+        Ok(to_value(&self.inner.tick(gravity_tick, width, height))?)
+    }
+}
+
+#[wasm_bindgen]
+pub struct JsLockDelay {
+    inner: LockDelay,
+}
+
+#[wasm_bindgen]
+impl JsLockDelay {
+    #[wasm_bindgen(constructor)]
+    pub fn new(delay: u32, reset: JsValue) -> Result<Self, JsValue> {
+        let reset = if reset.is_undefined() || reset.is_null() {
+            LockReset::default()
+        } else {
+            from_value::<LockReset>(reset)?
+        };
+        Ok(Self {
+            inner: LockDelay::new(delay, reset),
+        })
+    }
+
+    pub fn spawn(&mut self, board: &JsBoard) {
+        self.inner.spawn(&board.inner)
+    }
+
+    #[wasm_bindgen(js_name = onMove)]
+    pub fn on_move(&mut self, board: &JsBoard) {
+        self.inner.on_move(&board.inner)
+    }
+
+    /// 지금 고정해야 하면 true
+    pub fn tick(&mut self, board: &JsBoard) -> bool {
+        self.inner.tick(&board.inner)
+    }
+
+    #[wasm_bindgen(getter)]
+    #[wasm_bindgen(js_name = resetsLeft)]
+    pub fn resets_left(&self) -> Option<u32> {
+        self.inner.resets_left()
+    }
+}
+
+#[wasm_bindgen]
+pub struct JsRandomizer {
+    inner: PieceRandomizer,
+}
+
+#[wasm_bindgen]
+impl JsRandomizer {
+    #[wasm_bindgen(constructor)]
+    pub fn new(kind: JsValue, seed: u64) -> Result<Self, JsValue> {
+        let kind = from_value::<RandomizerKind>(kind)?;
+        Ok(Self {
+            inner: kind.build(seed),
+        })
+    }
+
+    pub fn next(&mut self) -> Result<JsValue, JsValue> {
+        Ok(to_value(&self.inner.next_tetrimino())?)
     }
 }
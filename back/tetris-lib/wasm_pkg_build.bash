@@ -5,19 +5,57 @@ cargo test --features wasm
 mv  bindings pkg
 
 echo "
+export * from './ActivePiece'
+export * from './AttackTable'
 export * from './Board'
+export * from './BotConfig'
+export * from './BotMove'
+export * from './BotWeights'
+export * from './ClearChain'
+export * from './ClearResult'
+export * from './EngineError'
 export * from './FallingBlock'
 export * from './FallingBlockAt'
 export * from './FallingBlockPlan'
+export * from './FinesseInput'
+export * from './FinesseReport'
+export * from './FumenError'
+export * from './FumenPage'
+export * from './GameConfig'
+export * from './GameEvent'
+export * from './GameInput'
+export * from './GarbageMode'
+export * from './HandlingConfig'
+export * from './HandlingEvent'
+export * from './HoldError'
+export * from './HoldMode'
+export * from './KickTable'
 export * from './Location'
+export * from './LockDelay'
+export * from './LockReset'
+export * from './LockResult'
 export * from './MoveDirection'
-export * from './MoveError'
+export * from './ParseBoardError'
+export * from './PerfectClearStep'
+export * from './PieceDef'
+export * from './PieceSet'
+export * from './PieceSetError'
+export * from './Placement'
+export * from './RandomizerKind'
+export * from './Replay'
+export * from './ReplayAction'
+export * from './ReplayError'
+export * from './ReplayFrame'
+export * from './ReplayResult'
 export * from './Rotate'
 export * from './RotateDirection'
-export * from './RotateError'
-export * from './SpawnError'
-export * from './StepError'
+export * from './RotatePlan'
+export * from './RotationSystemKind'
+export * from './ScoreTable'
+export * from './SetPiece'
+export * from './TSpin'
 export * from './Tetrimino'
 export * from './Tile'
 export * from './TileAt'
+export * from './TopOut'
 " >> pkg/bindings/index.ts
\ No newline at end of file
@@ -362,6 +362,17 @@ pub fn process_clinet_msg(
                         game_type,
                     }));
                 }
+                RoomGarbageModeChange {
+                    room_id,
+                    garbage_mode,
+                } => {
+                    let _ =
+                        ws_world_command_tx.send(WsWorldCommand::Room(Room::GarbageModeChange {
+                            ws_id: ws_id.to_string(),
+                            room_id,
+                            garbage_mode,
+                        }));
+                }
                 GameAction {
                     action, game_id, ..
                 } => {
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use tetris_lib::Tetrimino;
+use tetris_lib::{GarbageMode, HandlingEvent};
 
 /// client -> server
 #[derive(Debug, Serialize, Deserialize)]
@@ -46,6 +46,11 @@ pub enum ClientToServerWsMsg {
     #[serde(rename_all = "camelCase")]
     RoomGameTypeChange { room_id: String, game_type: String },
     #[serde(rename_all = "camelCase")]
+    RoomGarbageModeChange {
+        room_id: String,
+        garbage_mode: GarbageMode,
+    },
+    #[serde(rename_all = "camelCase")]
     RoomGameStart { room_id: String },
 
     // === 게임관련 ===
@@ -70,71 +75,22 @@ pub enum GameActionInput {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum GameActionType {
-    MoveLeft,
-    MoveRight,
-    RotateLeft,
-    RotateRight,
-    HardDrop,
-    SoftDrop,
-    Hold,
-    Step,
-    LineClear,
-    Placing,
-    RemoveFalling,
-    ShiftNext {
-        next: Tetrimino,
-    },
-    PushNext {
-        next: Tetrimino,
-    },
-    Setup {
-        next: Vec<Tetrimino>,
-        hold: Option<Tetrimino>,
-    },
-    Spawn {
-        spawn: Tetrimino,
-    },
-    AddHold {
-        hold: Tetrimino,
-    },
-    SetInfo {
-        level: Option<u32>,
-        score: Option<u32>,
-        line: Option<u32>,
-    },
-    ScoreEffect {
-        kind: String,
-        combo: u32,
-    },
-    BoardEnd,
-    AddGarbageQueue {
-        empty: Vec<u8>,
-    },
+    /// 클라이언트가 `tick` 번 돌린 뒤 키를 누르고 뗀 것
+    Handling { tick: u32, event: HandlingEvent },
+    /// 입력이 없을 때도 클라이언트가 돌린 tick 을 알린다
+    Ticking { tick: u32 },
+    /// 클라이언트가 `tick` 번 돌린 뒤 넣은 방해 줄
+    AddGarbageQueue { tick: u32, empty: Vec<Vec<usize>> },
 }
 
 impl From<GameActionType> for crate::ws_world::command::GameActionType {
     fn from(value: GameActionType) -> Self {
         match value {
-            GameActionType::Hold => Self::Hold,
-            GameActionType::MoveLeft => Self::MoveLeft,
-            GameActionType::MoveRight => Self::MoveRight,
-            GameActionType::RotateLeft => Self::RotateLeft,
-            GameActionType::RotateRight => Self::RotateRight,
-            GameActionType::HardDrop => Self::HardDrop,
-            GameActionType::SoftDrop => Self::SoftDrop,
-            GameActionType::Step => Self::Step,
-            GameActionType::LineClear => Self::LineClear,
-            GameActionType::Placing => Self::Placing,
-            GameActionType::RemoveFalling => Self::RemoveFalling,
-            GameActionType::ShiftNext { next } => Self::ShiftNext { next },
-            GameActionType::PushNext { next } => Self::PushNext { next },
-            GameActionType::Setup { next, hold } => Self::Setup { next, hold },
-            GameActionType::Spawn { spawn } => Self::Spawn { spawn },
-            GameActionType::AddHold { hold } => Self::AddHold { hold },
-            GameActionType::SetInfo { level, score, line } => Self::SetInfo { level, score, line },
-            GameActionType::ScoreEffect { kind, combo } => Self::ScoreEffect { kind, combo },
-            GameActionType::BoardEnd => Self::BoardEnd,
-            GameActionType::AddGarbageQueue { empty } => Self::AddGarbageQueue { empty },
+            GameActionType::Handling { tick, event } => Self::Handling { tick, event },
+            GameActionType::Ticking { tick } => Self::Ticking { tick },
+            GameActionType::AddGarbageQueue { tick, empty } => {
+                Self::AddGarbageQueue { tick, empty }
+            }
         }
     }
 }
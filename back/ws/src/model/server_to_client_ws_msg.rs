@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
+use tetris_lib::GarbageMode;
 use time::OffsetDateTime;
 
 use crate::ws_world::{game::model::TetrisGameAction, model::WsWorldRoomStatus};
@@ -173,6 +174,7 @@ pub struct Room {
     pub room_status: WsWorldRoomStatus,
     pub games: Vec<String>,
     pub game_type: String,
+    pub garbage_mode: GarbageMode,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
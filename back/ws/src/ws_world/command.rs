@@ -1,4 +1,4 @@
-use tetris_lib::Tetrimino;
+use tetris_lib::{GarbageMode, HandlingEvent};
 
 use crate::app::state::ArcWsAppState;
 
@@ -29,46 +29,9 @@ pub enum Game {
 }
 #[derive(Debug)]
 pub enum GameActionType {
-    MoveLeft,
-    MoveRight,
-    RotateLeft,
-    RotateRight,
-    HardDrop,
-    SoftDrop,
-    Hold,
-    Step,
-    LineClear,
-    Placing,
-    RemoveFalling,
-    ShiftNext {
-        next: Tetrimino,
-    },
-    PushNext {
-        next: Tetrimino,
-    },
-    Setup {
-        next: Vec<Tetrimino>,
-        hold: Option<Tetrimino>,
-    },
-    Spawn {
-        spawn: Tetrimino,
-    },
-    AddHold {
-        hold: Tetrimino,
-    },
-    SetInfo {
-        level: Option<u32>,
-        score: Option<u32>,
-        line: Option<u32>,
-    },
-    ScoreEffect {
-        kind: String,
-        combo: u32,
-    },
-    BoardEnd,
-    AddGarbageQueue {
-        empty: Vec<u8>,
-    },
+    Handling { tick: u32, event: HandlingEvent },
+    Ticking { tick: u32 },
+    AddGarbageQueue { tick: u32, empty: Vec<Vec<usize>> },
 }
 
 pub enum Ws {
@@ -147,6 +110,11 @@ pub enum Room {
         room_id: String,
         game_type: String,
     },
+    GarbageModeChange {
+        ws_id: String,
+        room_id: String,
+        garbage_mode: GarbageMode,
+    },
 }
 
 pub enum Lobby {
@@ -3,7 +3,7 @@ use rand::seq::IndexedRandom;
 use crate::ws_world::{
     command::GameActionType,
     connections::WsConnections,
-    game::model::{TetrisScore, attack_line},
+    game::model::BoardEndKind,
     model::{GameId, WsData, WsId, WsWorldGameStatus, WsWorldGameType},
     pubsub::WsPubSub,
     util::err_publish,
@@ -52,102 +52,36 @@ pub fn action(
         return;
     }
 
-    match action {
-        GameActionType::MoveLeft => {
-            tetris.action_move_left();
-        }
-        GameActionType::MoveRight => {
-            tetris.action_move_right();
-        }
-        GameActionType::RotateLeft => {
-            tetris.action_rotate_left();
-        }
-        GameActionType::RotateRight => {
-            tetris.action_rotate_right();
-        }
-        GameActionType::HardDrop => {
-            tetris.action_hard_drop();
-        }
-        GameActionType::SoftDrop => {
-            tetris.action_soft_drop();
-        }
-        GameActionType::Hold => {
-            tetris.action_hold();
-        }
-        GameActionType::AddHold { hold } => {
-            tetris.add_hold(hold);
-        }
-        GameActionType::Step => {
-            tetris.step();
-        }
-        GameActionType::LineClear => {
-            // tetris.
-            tetris.line_clear();
-        }
-        GameActionType::Placing => {
-            tetris.placing();
-        }
-        GameActionType::RemoveFalling => {
-            tetris.remove_falling();
-        }
-        GameActionType::ShiftNext { next } => {
-            tetris.shift_next();
-        }
-        GameActionType::PushNext { next } => {
-            tetris.push_next(next);
-        }
-        GameActionType::Setup { next, hold } => {
-            tetris.setup(next, hold);
-        }
-        GameActionType::Spawn { spawn } => {
-            tetris.spawn(spawn);
-        }
-
-        GameActionType::SetInfo { level, score, line } => {
-            tetris.set_info(level, score, line);
+    let result = match action {
+        GameActionType::Handling { tick, event } => tetris.handling(tick, event),
+        GameActionType::Ticking { tick } => tetris.ticking(tick),
+        GameActionType::AddGarbageQueue { tick, empty } => tetris.add_garbage(tick, empty),
+    };
+    // 클라이언트 보드는 이미 움직였으니 서버에서 못 따라가면 보드가 어긋난 채로 남는다
+    if let Err(err) = result {
+        err_publish(pubsub, &ws_id, &format!("[game action] desync: {err}"));
+        if !tetris.is_board_end {
+            tetris.board_end(BoardEndKind::Desync);
         }
-        GameActionType::ScoreEffect { kind, combo } => {
-            if matches!(game.game_type, WsWorldGameType::MultiBattle) {
-                let score = match kind.as_str() {
-                    "TSpinZero" => Some(TetrisScore::TSpinZero),
-                    "TSpinSingle" => Some(TetrisScore::TSpinSingle),
-                    "TSpinDouble" => Some(TetrisScore::TSpinDouble),
-                    "TSpinTriple" => Some(TetrisScore::TSpinTriple),
-                    "Single" => Some(TetrisScore::Single),
-                    "Double" => Some(TetrisScore::Double),
-                    "Triple" => Some(TetrisScore::Triple),
-                    "Tetris" => Some(TetrisScore::Tetris),
-                    _ => None,
-                };
+    }
 
-                if let Some(score) = score {
-                    let attack_line = attack_line(score);
-                    if let Some(attack_line) = attack_line {
-                        let targets = other_tetris
-                            .iter()
-                            .filter(|(f, g)| **f != ws_id && !g.is_board_end)
-                            .map(|t| t.0)
-                            .cloned()
-                            .collect::<Vec<_>>();
+    // 공격량은 클라이언트가 보낸 값이 아니라 서버 보드에서 고정한 결과로 정한다
+    let attacks = tetris.attack_list.drain(..).collect::<Vec<_>>();
+    if !matches!(game.game_type, WsWorldGameType::MultiBattle) {
+        return;
+    }
+    for attack_line in attacks {
+        let targets = other_tetris
+            .iter()
+            .filter(|(f, g)| **f != ws_id && !g.is_board_end)
+            .map(|t| t.0)
+            .cloned()
+            .collect::<Vec<_>>();
 
-                        if let Some(target) = targets.choose(&mut rand::rng()) {
-                            if let Some((_, target_game)) =
-                                other_tetris.iter_mut().find(|f| f.0 == target)
-                            {
-                                target_game.garbage_queueing(attack_line, ws_id.to_string());
-                            }
-                        }
-                    }
-                }
+        if let Some(target) = targets.choose(&mut rand::rng()) {
+            if let Some((_, target_game)) = other_tetris.iter_mut().find(|f| f.0 == target) {
+                target_game.garbage_queueing(attack_line, ws_id.to_string());
             }
-
-            tetris.score_effect(kind, combo);
-        }
-        GameActionType::BoardEnd => {
-            tetris.board_end();
         }
-        GameActionType::AddGarbageQueue { empty } => {
-            tetris.add_garbage(empty);
-        }
-    };
+    }
 }
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use tetris_lib::Tetrimino;
+use tetris_lib::{GameConfig, HandlingEvent, Tetrimino, TopOut};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GarbageQueue {
@@ -23,19 +23,16 @@ pub struct TetrisGameAction {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum TetrisGameActionType {
-    Setup {
-        next: Vec<Tetrimino>,
-    },
     End {
         //
     },
-    BoardStart,
-    Ticking,
-    PushNext {
-        next: Tetrimino,
+    /// 모든 보드가 이 설정으로 `Game` 을 만들어서 같은 입력을 같은 tick 에 넣는다
+    BoardStart {
+        config: GameConfig,
     },
-    ShiftNext {
-        next: Option<Tetrimino>,
+    /// 클라이언트가 이 tick 까지 돌렸다
+    Ticking {
+        tick: u32,
     },
     SpawnFromNext {
         spawn: Tetrimino,
@@ -44,26 +41,7 @@ pub enum TetrisGameActionType {
         spawn: Tetrimino,
         hold: Option<Tetrimino>,
     },
-    Spawn {
-        spawn: Tetrimino,
-    },
-    RemoveFalling,
-    MoveRight,
-    MoveLeft,
-    RotateRight,
-    RotateLeft,
-    Step,
     DoStep,
-    Placing,
-    LineClear,
-    HardDrop,
-    AddHold {
-        hold: Tetrimino,
-    },
-    SoftDrop,
-    HoldFalling {
-        hold: Tetrimino,
-    },
     Score {
         kind: TetrisScore,
         level: u8,
@@ -74,11 +52,14 @@ pub enum TetrisGameActionType {
     GarbageQueue {
         queue: Vec<GarbageQueue>,
     },
+    /// 클라이언트가 `tick` 번 돌린 뒤 넣은 방해 줄
     AddGarbage {
-        empty: Vec<u8>,
+        tick: u32,
+        empty: Vec<Vec<usize>>,
     },
+    /// 줄마다 구멍 x 목록
     DoGarbageAdd {
-        empty: Vec<u8>,
+        empty: Vec<Vec<usize>>,
     },
     BoardEnd {
         // msg: Option<String>,
@@ -86,14 +67,10 @@ pub enum TetrisGameActionType {
         elapsed: u128,
         // end_th: u8,
     },
-    SetInfo {
-        level: Option<u32>,
-        score: Option<u32>,
-        line: Option<u32>,
-    },
-    ScoreEffect {
-        kind: String,
-        combo: u32,
+    /// 클라이언트가 `tick` 번 돌린 뒤 키를 누르고 뗀 것, 다른 보드와 리플레이가 그대로 따라 한다
+    Handling {
+        tick: u32,
+        event: HandlingEvent,
     },
 }
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -102,6 +79,21 @@ pub enum BoardEndKind {
     Line40Clear,
     BattleWinner,
     Exit,
+    BlockOut,
+    LockOut,
+    GarbageOut,
+    /// 서버 보드에서 받아들일 수 없는 동작이라 클라이언트 보드와 어긋났다
+    Desync,
+}
+
+impl From<TopOut> for BoardEndKind {
+    fn from(reason: TopOut) -> Self {
+        match reason {
+            TopOut::BlockOut => BoardEndKind::BlockOut,
+            TopOut::LockOut => BoardEndKind::LockOut,
+            TopOut::GarbageOut => BoardEndKind::GarbageOut,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -141,36 +133,7 @@ impl TetrisScore {
     }
 }
 
-const LEVEL_UP_LINE: u32 = 10;
-pub const PLACING_DELAY: u32 = 30;
-pub fn level_to_gravity_tick(level: u32) -> u32 {
-    match level {
-        1 => 48,
-        2 => 43,
-        3 => 38,
-        4 => 33,
-        5 => 28,
-        6 => 23,
-        7 => 18,
-        8 => 13,
-        9 => 8,
-        10 => 6,
-        11..=13 => 5,
-        14..=15 => 4,
-        16..=17 => 3,
-        18..=19 => 2,
-        _ => 1, // 20 이상
-    }
-}
-
-pub fn attack_line(kind: TetrisScore) -> Option<u8> {
-    match kind {
-        TetrisScore::Double => Some(1),
-        TetrisScore::Triple => Some(2),
-        TetrisScore::Tetris => Some(4),
-        TetrisScore::TSpinSingle => Some(2),
-        TetrisScore::TSpinDouble => Some(4),
-        TetrisScore::TSpinTriple => Some(6),
-        _ => None,
-    }
-}
+/// 바닥에 닿은 뒤 고정되기까지의 tick, 클라이언트와 같은 값
+pub const LOCK_DELAY: u32 = 30;
+/// 클라이언트 tick 이 서버 tick 보다 이만큼 넘게 앞서면 어긋난 것으로 본다
+pub const MAX_TICK_AHEAD: u32 = 60;
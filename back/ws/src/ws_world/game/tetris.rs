@@ -1,11 +1,17 @@
 use std::collections::VecDeque;
 
-use rand::{Rng, seq::IndexedRandom};
+use anyhow::anyhow;
 use serde::{Deserialize, Serialize};
-use tetris_lib::{Board, SpawnError, StepError, Tetrimino};
+use tetris_lib::{
+    Game, GameConfig, GameEvent, GarbageGenerator, GarbageMode, HandlingConfig, HandlingEvent,
+    InputHandler, game::level_to_gravity_tick,
+};
 
 use crate::ws_world::{
-    game::model::{GarbageQueue, GarbageQueueKind, TetrisGameAction, TetrisGameActionType},
+    game::model::{
+        BoardEndKind, GarbageQueue, GarbageQueueKind, LOCK_DELAY, MAX_TICK_AHEAD, TetrisGameAction,
+        TetrisGameActionType,
+    },
     model::{UserId, WsId},
 };
 
@@ -14,13 +20,12 @@ pub struct TetrisGame {
     pub ws_id: WsId,
     pub user_id: UserId,
     pub nick_name: String,
-    pub board: Board,
-    pub hold: Option<Tetrimino>,
-    pub score: u32,
-    pub clear_line: u32,
-    pub level: u32,
-    pub next: VecDeque<Tetrimino>,
-    pub is_can_hold: bool,
+    /// 클라이언트와 같은 설정으로 만들어서 같은 입력을 같은 tick 에 넣는다
+    pub game: Game,
+    /// 클라이언트가 보낸 키 입력을 tick 별 입력으로 바꾼다
+    pub input: InputHandler,
+    /// 클라이언트 기준으로 `game` 을 돌린 tick 수
+    pub game_tick: u32,
     pub is_started: bool,
     pub is_board_end: bool,
     pub actions: Vec<TetrisGameAction>,
@@ -30,39 +35,38 @@ pub struct TetrisGame {
     pub tick: u32,
     pub step_tick: u32,
 
-    pub placing_delay_tick: u32,
-    pub is_placing_delay: bool,
-    pub placing_reset_cnt: u8,
-
-    pub is_tspin: bool,
-
-    pub combo: u32,
-    pub combo_tick: u32,
     //
     pub garbage_queue: VecDeque<GarbageQueue>,
+    /// 받은 공격의 구멍 위치, 방 시드를 같이 써서 모두 같은 순서로 나온다
+    pub garbage: GarbageGenerator,
+    /// 클라이언트에 넣으라고 보낸 방해 줄, 보낸 순서대로 돌아와야 한다
+    pub sent_garbage: VecDeque<Vec<Vec<usize>>>,
 
+    /// 아직 다른 보드에 보내지 않은 공격 줄 수
     pub attack_list: VecDeque<u8>,
 
     //
     pub line_40_clear: bool,
     pub battle_win: bool,
-    pub seven_bag: VecDeque<Tetrimino>,
+    pub seed: u64,
     pub act_seq: u32,
     pub last_step_tick: u32,
 }
 impl TetrisGame {
-    pub fn new(ws_id: WsId, user_id: UserId, nick_name: String) -> Self {
+    pub fn new(ws_id: WsId, user_id: UserId, nick_name: String, seed: u64) -> Self {
+        let config = GameConfig {
+            seed,
+            lock_delay: LOCK_DELAY,
+            ..Default::default()
+        };
         Self {
             ws_id,
             user_id,
             nick_name,
-            board: Board::new(10, 26),
-            hold: None,
-            clear_line: 0,
-            score: 0,
-            next: VecDeque::new(),
-            level: 1,
-            is_can_hold: true,
+            garbage: GarbageGenerator::new(GarbageMode::default(), config.width, seed),
+            game: Game::new(config),
+            input: InputHandler::new(HandlingConfig::default()),
+            game_tick: 0,
             is_started: false,
             is_board_end: false,
             tick: 0,
@@ -70,21 +74,20 @@ impl TetrisGame {
             actions: vec![],
             actions_buffer: vec![],
             elapsed: 0,
-            placing_delay_tick: 0,
-            is_placing_delay: false,
-            placing_reset_cnt: 10,
-            combo: 0,
-            combo_tick: 0,
-            is_tspin: false,
             garbage_queue: VecDeque::new(),
+            sent_garbage: VecDeque::new(),
             line_40_clear: false,
             battle_win: false,
-            seven_bag: VecDeque::new(),
+            seed,
             act_seq: 0,
             attack_list: VecDeque::new(),
             last_step_tick: 0,
         }
     }
+    pub fn with_garbage_mode(mut self, mode: GarbageMode) -> Self {
+        self.garbage = GarbageGenerator::new(mode, self.game.config().width, self.seed);
+        self
+    }
     pub fn push_action_buffer(&mut self, action: TetrisGameActionType) {
         self.act_seq += 1;
         self.actions_buffer.push(TetrisGameAction {
@@ -100,7 +103,10 @@ impl TetrisGame {
         return s;
     }
     pub fn board_reset(&mut self) {
-        self.board = Board::new(10, 26);
+        self.game = Game::new(self.game.config().clone());
+        self.input = InputHandler::new(self.input.config().clone());
+        self.game_tick = 0;
+        self.sent_garbage.clear();
     }
 
     pub fn garbage_queueing(&mut self, attack_line: u8, from: String) {
@@ -115,13 +121,6 @@ impl TetrisGame {
             queue: self.garbage_queue.clone().into(),
         });
     }
-    pub fn add_garbage(&mut self, empty: Vec<u8>) {
-        for x in &empty {
-            if !self.board.push_garbage_line(*x as usize) {
-                // return false;
-            };
-        }
-    }
     pub fn garbage_add(&mut self, clear_len: u8) {
         let mut is_garbage_changed = false;
 
@@ -144,15 +143,10 @@ impl TetrisGame {
 
         let mut add_gargabe = vec![];
         loop {
-            let x = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
-                .choose(&mut rand::rng())
-                .unwrap();
             if let Some(front) = self.garbage_queue.pop_front() {
                 is_garbage_changed = true;
                 if matches!(front.kind, GarbageQueueKind::Ready) {
-                    for _ in 0..front.line {
-                        add_gargabe.push(*x);
-                    }
+                    add_gargabe.extend(self.garbage.attack(front.line));
                 } else {
                     self.garbage_queue.push_front(front);
                     break;
@@ -167,179 +161,103 @@ impl TetrisGame {
             });
         }
         if !add_gargabe.is_empty() {
+            self.sent_garbage.push_back(add_gargabe.clone());
             self.push_action_buffer(TetrisGameActionType::DoGarbageAdd { empty: add_gargabe });
         }
     }
 }
 
 impl TetrisGame {
-    pub fn step(&mut self) -> Result<(), StepError> {
-        match self.board.try_step() {
-            Ok(step) => {
-                self.board.apply_step(step);
-                self.push_action_buffer(TetrisGameActionType::Step);
-            }
-            Err(err) => Err(err)?,
-        };
-        self.last_step_tick = self.tick;
-        Ok(())
-    }
-
-    pub fn line_clear(&mut self) -> usize {
-        let clear = self.board.try_line_clear();
-        let clear_len = clear.len();
-        self.garbage_add(clear.len() as u8);
-
-        self.board.apply_line_clear(clear);
-        self.push_action_buffer(TetrisGameActionType::LineClear);
-        clear_len
-    }
-
-    pub fn placing(&mut self) {
-        self.board.place_falling();
-        self.push_action_buffer(TetrisGameActionType::Placing);
-    }
-
-    pub fn remove_falling(&mut self) {
-        tracing::info!("remove falling");
-        self.board.remove_falling_blocks();
-        self.push_action_buffer(TetrisGameActionType::RemoveFalling);
-    }
-
-    fn rand_tetrimino(&mut self) -> Tetrimino {
-        if self.seven_bag.is_empty() {
-            self.seven_bag = VecDeque::from(vec![
-                Tetrimino::I,
-                Tetrimino::J,
-                Tetrimino::L,
-                Tetrimino::O,
-                Tetrimino::S,
-                Tetrimino::T,
-                Tetrimino::Z,
-            ]);
+    /// 클라이언트가 `tick` 번 돌린 상태까지 같은 입력으로 따라간다
+    ///
+    /// 이미 지난 tick 이거나 서버 시간보다 너무 앞선 tick 이면 보드가 어긋난 것이다.
+    fn advance_to(&mut self, tick: u32) -> anyhow::Result<()> {
+        if tick < self.game_tick {
+            Err(anyhow!("TickRewind {tick} < {}", self.game_tick))?;
         }
-        let idx = rand::rng().random_range(0..self.seven_bag.len());
-        self.seven_bag.remove(idx).unwrap()
-    }
-    pub fn shift_next(&mut self) -> Option<Tetrimino> {
-        let t = self.next.pop_front();
-        self.push_action_buffer(TetrisGameActionType::ShiftNext { next: t });
-        t
-    }
-    pub fn push_next(&mut self, tetrimino: Tetrimino) {
-        // let added_next = self.rand_tetrimino();
-        self.next.push_back(tetrimino);
-        self.push_action_buffer(TetrisGameActionType::PushNext { next: tetrimino });
-    }
-
-    pub fn spawn(&mut self, tetrimino: Tetrimino) -> Result<(), SpawnError> {
-        let new_tiles = self.board.try_spawn_falling(tetrimino)?;
-        self.board.apply_spawn_falling(new_tiles);
-        self.push_action_buffer(TetrisGameActionType::Spawn { spawn: tetrimino });
-        Ok(())
-    }
-    pub fn setup(&mut self, next: Vec<Tetrimino>, hold: Option<Tetrimino>) {
-        self.next.extend(next);
-        self.hold = hold;
-        self.push_action_buffer(TetrisGameActionType::Setup {
-            next: self.next.clone().into(),
-        });
-    }
-
-    pub fn action_move_left(&mut self) -> anyhow::Result<()> {
-        let plan = self
-            .board
-            .try_move_falling(tetris_lib::MoveDirection::Left)?;
-        self.board.apply_move_falling(plan);
-        self.push_action_buffer(TetrisGameActionType::MoveLeft);
-        Ok(())
-    }
-
-    pub fn action_move_right(&mut self) -> anyhow::Result<()> {
-        let plan = self
-            .board
-            .try_move_falling(tetris_lib::MoveDirection::Right)?;
-        self.board.apply_move_falling(plan);
-        self.push_action_buffer(TetrisGameActionType::MoveRight);
-        Ok(())
-    }
-
-    pub fn action_rotate_left(&mut self) -> anyhow::Result<()> {
-        let plan = self
-            .board
-            .try_rotate_falling(tetris_lib::RotateDirection::Left)?;
-        self.board.apply_rotate_falling(plan);
-        self.push_action_buffer(TetrisGameActionType::RotateLeft);
+        if tick > self.tick + MAX_TICK_AHEAD {
+            Err(anyhow!("TickAhead {tick} > {}", self.tick))?;
+        }
+        while self.game_tick < tick && !self.is_board_end {
+            self.game_tick += 1;
+            let config = self.game.config();
+            let inputs = self.input.tick(
+                level_to_gravity_tick(self.game.level()),
+                config.width,
+                config.height,
+            );
+            let events = self.game.tick(&inputs);
+            self.on_events(events);
+        }
+        self.last_step_tick = self.step_tick;
         Ok(())
     }
 
-    pub fn action_rotate_right(&mut self) -> anyhow::Result<()> {
-        let plan = self
-            .board
-            .try_rotate_falling(tetris_lib::RotateDirection::Right)?;
-        self.board.apply_rotate_falling(plan);
-        self.push_action_buffer(TetrisGameActionType::RotateRight);
-        Ok(())
-    }
-    pub fn action_soft_drop(&mut self) -> anyhow::Result<()> {
-        match self.board.try_step() {
-            Ok(step) => {
-                self.board.apply_step(step);
-                self.push_action_buffer(TetrisGameActionType::SoftDrop);
+    /// 점수, 레벨, 줄 수는 `game` 이 세고 여기서는 공격과 방해 줄, 탑 아웃만 처리한다
+    fn on_events(&mut self, events: Vec<GameEvent>) {
+        for event in events {
+            match event {
+                GameEvent::Lock { result, attack, .. } => {
+                    self.garbage_add(result.lines);
+                    if attack > 0 {
+                        self.attack_list.push_back(attack.min(u8::MAX as u32) as u8);
+                    }
+                }
+                GameEvent::GameOver { reason } => {
+                    if !self.is_board_end {
+                        self.board_end(reason.into());
+                    }
+                }
+                GameEvent::Spawn { .. } | GameEvent::Hold { .. } | GameEvent::LevelUp { .. } => {}
             }
-            Err(err) => Err(err)?,
-        };
-
-        Ok(())
+        }
     }
-    pub fn action_hard_drop(&mut self) -> anyhow::Result<()> {
-        let dropcnt = self.board.hard_drop();
-        self.push_action_buffer(TetrisGameActionType::HardDrop);
 
-        Ok(())
-    }
-    pub fn action_hold(&mut self) -> anyhow::Result<()> {
+    /// 클라이언트가 `tick` 번 돌린 뒤 누르거나 뗀 키, 다른 보드도 그대로 따라 하도록 남긴다
+    pub fn handling(&mut self, tick: u32, event: HandlingEvent) -> anyhow::Result<()> {
+        self.advance_to(tick)?;
+        self.input.handle(event);
+        self.push_action_buffer(TetrisGameActionType::Handling { tick, event });
         Ok(())
     }
 
-    pub fn add_hold(&mut self, tetrimino: Tetrimino) {
-        self.hold = Some(tetrimino);
-        self.push_action_buffer(TetrisGameActionType::AddHold { hold: tetrimino });
+    /// 입력이 없어도 클라이언트가 돌린 tick 까지 따라간다
+    pub fn ticking(&mut self, tick: u32) -> anyhow::Result<()> {
+        self.advance_to(tick)?;
+        self.push_action_buffer(TetrisGameActionType::Ticking { tick });
+        Ok(())
     }
 
-    pub fn set_info(&mut self, level: Option<u32>, score: Option<u32>, line: Option<u32>) {
-        if let Some(level) = level {
-            self.level = level;
-        }
-        if let Some(score) = score {
-            self.score = score;
+    /// 클라이언트가 `tick` 번 돌린 뒤 넣은 방해 줄, 서버가 보낸 순서와 같아야 한다
+    pub fn add_garbage(&mut self, tick: u32, empty: Vec<Vec<usize>>) -> anyhow::Result<()> {
+        self.advance_to(tick)?;
+        if self.sent_garbage.front() != Some(&empty) {
+            Err(anyhow!("GarbageMismatch {empty:?}"))?;
         }
-        if let Some(line) = line {
-            self.clear_line = line;
-        }
-        self.push_action_buffer(TetrisGameActionType::SetInfo { level, score, line });
-    }
-    pub fn score_effect(&mut self, kind: String, combo: u32) {
-        self.push_action_buffer(TetrisGameActionType::ScoreEffect { kind, combo });
+        self.sent_garbage.pop_front();
+        let events = self.game.push_garbage(&empty);
+        self.push_action_buffer(TetrisGameActionType::AddGarbage { tick, empty });
+        self.on_events(events);
+        Ok(())
     }
 
-    pub fn board_end(&mut self) {
+    pub fn board_end(&mut self, kind: BoardEndKind) {
         self.is_board_end = true;
         self.push_action_buffer(TetrisGameActionType::BoardEnd {
-            kind: super::model::BoardEndKind::SpawnImpossible,
+            kind,
             elapsed: self.elapsed,
         });
     }
 
     // TODO: boardEmpty to 0 mapping for reduce msg size
     pub fn game_sync_data(&self) -> serde_json::Value {
-        let next = self.next.clone();
-        let board = self.board.board().clone();
-        let hold = self.hold.clone();
+        let next = self.game.next().clone();
+        let board = self.game.board().view();
+        let hold = self.game.hold();
         let garbage_q = self.garbage_queue.clone();
-        let score = self.score;
-        let level = self.level;
-        let line = self.clear_line;
+        let score = self.game.score();
+        let level = self.game.level();
+        let line = self.game.lines();
         let is_board_end = self.is_board_end;
         let elapsed = self.elapsed;
 
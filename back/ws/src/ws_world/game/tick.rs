@@ -108,14 +108,6 @@ fn game_loop(
                 // step tick
                 tetris.step_tick += 1;
 
-                // combo tick
-                if tetris.combo_tick > 0 {
-                    tetris.combo_tick -= 1;
-                } else {
-                    tetris.combo_tick = 0;
-                    tetris.combo = 0;
-                }
-
                 // garbage queue->ready tick
                 if matches!(game.game_type, WsWorldGameType::MultiBattle) {
                     let mut garbage_ready = false;
@@ -133,7 +125,7 @@ fn game_loop(
                         });
                     }
                 } else if matches!(game.game_type, WsWorldGameType::Multi40Line) {
-                    if tetris.clear_line >= 40 {
+                    if tetris.game.lines() >= 40 {
                         tetris.is_board_end = true;
                         tetris.line_40_clear = true;
 
@@ -144,12 +136,9 @@ fn game_loop(
                     }
                 }
 
-                // 10초 동안 step 이 안올라오면 퇴장
+                // 10초 동안 tick 이 안올라오면 퇴장
                 if tetris.step_tick - tetris.last_step_tick > 600 {
-                    tetris.push_action_buffer(TetrisGameActionType::BoardEnd {
-                        kind: BoardEndKind::SpawnImpossible,
-                        elapsed: tetris.elapsed,
-                    });
+                    tetris.board_end(BoardEndKind::SpawnImpossible);
                 }
 
                 // if tetris.step_tick >= level_to_gravity_tick(tetris.level) {
@@ -161,7 +150,8 @@ fn game_loop(
             tetris.is_started = true;
             tetris.is_board_end = false;
 
-            tetris.push_action_buffer(TetrisGameActionType::BoardStart);
+            let config = tetris.game.config().clone();
+            tetris.push_action_buffer(TetrisGameActionType::BoardStart { config });
         }
     }
 
@@ -227,9 +217,9 @@ fn game_end(
                     (
                         t.ws_id.clone(),
                         t.nick_name.clone(),
-                        t.score,
+                        t.game.score(),
                         t.elapsed,
-                        t.clear_line,
+                        t.game.lines(),
                     )
                 })
                 .collect::<Vec<_>>();
@@ -244,10 +234,10 @@ fn game_end(
                     (
                         t.ws_id.clone(),
                         t.nick_name.clone(),
-                        t.score,
+                        t.game.score(),
                         t.elapsed,
                         t.line_40_clear,
-                        t.clear_line,
+                        t.game.lines(),
                     )
                 })
                 .collect::<Vec<_>>();
@@ -262,10 +252,10 @@ fn game_end(
                     (
                         t.ws_id.clone(),
                         t.nick_name.clone(),
-                        t.score,
+                        t.game.score(),
                         t.elapsed,
                         t.battle_win,
-                        t.clear_line,
+                        t.game.lines(),
                     )
                 })
                 .collect::<Vec<_>>();
@@ -201,6 +201,20 @@ fn process(
                     game_type,
                 );
             }
+            Room::GarbageModeChange {
+                ws_id,
+                room_id,
+                garbage_mode,
+            } => {
+                room::room_garbage_mode_change(
+                    &connections,
+                    data,
+                    pubsub,
+                    WsId(ws_id),
+                    RoomId(room_id),
+                    garbage_mode,
+                );
+            }
         },
         WsWorldCommand::Game(cmd) => match cmd {
             Game::Action {
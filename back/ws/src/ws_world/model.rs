@@ -5,6 +5,7 @@ use std::{
 };
 
 use serde::{Deserialize, Serialize};
+use tetris_lib::GarbageMode;
 use time::OffsetDateTime;
 
 use crate::ws_world::game::tetris::TetrisGame;
@@ -64,6 +65,8 @@ pub struct WsWorldRoom {
     pub room_status: WsWorldRoomStatus,
     pub games: Vec<GameId>,
     pub game_type: WsWorldGameType,
+    #[serde(default)]
+    pub garbage_mode: GarbageMode,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -1,6 +1,7 @@
 use std::{collections::HashMap, time::Instant};
 
 use nanoid::nanoid;
+use tetris_lib::GarbageMode;
 use time::OffsetDateTime;
 
 use crate::{
@@ -48,6 +49,7 @@ pub fn create(
         room_status: WsWorldRoomStatus::Waiting,
         games: vec![],
         game_type: WsWorldGameType::MultiScore,
+        garbage_mode: GarbageMode::default(),
     };
     data.rooms.insert(room_id.clone(), room);
 
@@ -496,6 +498,79 @@ pub fn room_game_type_change(
     }
 }
 
+/// 방장만 실행 가능
+pub fn room_garbage_mode_change(
+    connections: &WsConnections,
+    data: &mut WsData,
+    pubsub: &mut WsPubSub,
+    ws_id: WsId,
+    room_id: RoomId,
+    garbage_mode: GarbageMode,
+) {
+    // === 유저 가드
+    let Some(_) = connections.get_user_by_ws_id(&ws_id) else {
+        err_publish(
+            pubsub,
+            &ws_id,
+            dbg!("[room_garbage_mode_change] not authenticated"),
+        );
+        return;
+    };
+
+    // === 방 가드
+    let Some(room) = data.rooms.get_mut(&room_id) else {
+        err_publish(
+            pubsub,
+            &ws_id,
+            dbg!("[room_garbage_mode_change] room is not exists"),
+        );
+        return;
+    };
+
+    // === 방 Waiting 인지 체크
+    if room.room_status != WsWorldRoomStatus::Waiting {
+        err_publish(
+            pubsub,
+            &ws_id,
+            dbg!("[room_garbage_mode_change] room not waiting"),
+        );
+        return;
+    }
+
+    // === 유저 방장 체크
+    if room
+        .room_host_ws_id
+        .as_ref()
+        .filter(|host_id| *host_id == &ws_id)
+        .is_none()
+    {
+        err_publish(
+            pubsub,
+            &ws_id,
+            "[room_garbage_mode_change] you're not the host",
+        );
+        return;
+    }
+
+    // === 방해 줄 모드 체인지
+    room.garbage_mode = garbage_mode;
+    if let Some(pub_room) = gen_room_publish_msg(connections, &data.rooms, &room_id) {
+        pubsub.publish(
+            &topic!(TOPIC_ROOM_ID, room_id),
+            ServerToClientWsMsg::RoomUpdated { room: pub_room },
+        );
+    }
+    let pub_lobby = gen_lobby_publish_msg(connections, &data.rooms);
+    pubsub.publish(
+        &topic!(TOPIC_LOBBY),
+        ServerToClientWsMsg::LobbyUpdated {
+            rooms: pub_lobby.rooms,
+            users: pub_lobby.users,
+            chats: vec![],
+        },
+    );
+}
+
 /// 방장만 실행 가능
 pub fn room_game_start(
     connections: &WsConnections,
@@ -548,6 +623,9 @@ pub fn room_game_start(
 
     let mut tetries = HashMap::new();
 
+    // 같은 방은 같은 순서로 블록이 나오도록 시드를 공유한다
+    // 클라이언트가 JS number 로 받아도 값이 바뀌지 않게 53비트만 쓴다
+    let seed = rand::random::<u64>() >> 11;
     for (_, room_user) in &room.room_users {
         //
         let nick_name = connections
@@ -560,7 +638,9 @@ pub fn room_game_start(
                 room_user.ws_id.clone(),
                 room_user.user_id.clone(),
                 nick_name,
-            ),
+                seed,
+            )
+            .with_garbage_mode(room.garbage_mode.clone()),
         );
     }
 
@@ -59,6 +59,7 @@ pub fn gen_room_publish_msg(
         room_status: room.room_status.clone(),
         games,
         game_type: room.game_type.to_string(),
+        garbage_mode: room.garbage_mode.clone(),
     })
 }
 
@@ -8,40 +8,41 @@ import type {
   TetrisIMesh,
   Transform,
   TickerDelegation,
-  BoardSetup,
   BoardSyncData,
   GarbageQueue,
 } from "./type";
 import * as THREE from "three";
-import { JsBoard } from "tetris-lib";
+import {
+  defaultGameConfig,
+  JsGame,
+  JsInputHandler,
+  levelToGravityTick,
+} from "tetris-lib";
 import type {
   Board,
-  FallingBlockAt,
+  ClearResult,
+  GameConfig,
+  GameEvent,
+  GameInput,
+  HandlingEvent,
   Tetrimino,
-  Tile,
-  TileAt,
 } from "tetris-lib/bindings";
 import type { WsSender } from "./wsHandle";
 
+// 서버와 같은 설정, 같은 입력이면 같은 tick 에 같은 결과가 나온다
 export class TetrisBoard {
-  board: JsBoard = new JsBoard(10, 26);
+  config: GameConfig = defaultGameConfig() as GameConfig;
+  game: JsGame = new JsGame(this.config);
+  tiles: Board = [];
   hold: Tetrimino | null = null;
   next: Tetrimino[] = [];
   boardId: BoardId;
   nickName: string;
   info: InfoData = {};
-  combo: number = 0;
-  sevenBag: Tetrimino[] = [];
   isBoardActive = false;
   isAddEndCover = false;
-  isCanHold = true;
-  isTSpin = false;
   tick = 0;
-  stepTick = 0;
-  comboTick = 0;
-  isPlacingDelay = false;
-  placingDelayTick = 0;
-  placingResetCnt = CONSTANT.rule.placingResetCnt;
+  inputHandler = new JsInputHandler(null);
 
   // addGarbageQueue: number[] = [];
   garbageQueue: GarbageQueue[] = [];
@@ -65,11 +66,7 @@ export class TetrisBoard {
 
   boardSync(syncData: BoardSyncData) {
     // boardSet
-    for (const [lineIdx, line] of syncData.board.entries()) {
-      for (const [tileIdx, tile] of line.entries()) {
-        this.board.setLocation(tileIdx, lineIdx, tile);
-      }
-    }
+    this.tiles = syncData.board;
     // hold set
     this.hold = syncData.hold;
 
@@ -96,200 +93,64 @@ export class TetrisBoard {
     this.renderHandler.isDirty = true;
   }
 
-  getTetriminoFromSevenBag(): Tetrimino {
-    if (this.sevenBag.length === 0) {
-      const tetriminos: Tetrimino[] = ["I", "O", "T", "J", "L", "S", "Z"];
-      const randTetriminos: Tetrimino[] = [];
-      while (tetriminos.length) {
-        const idx = Math.floor(Math.random() * tetriminos.length);
-        randTetriminos.push(tetriminos.splice(idx, 1)[0]);
-      }
-      this.sevenBag = randTetriminos;
-    }
-    return this.sevenBag.shift()!;
+  start(config: GameConfig) {
+    this.game.free();
+    this.game = new JsGame(config);
+    this.config = config;
+    this.inputHandler.free();
+    this.inputHandler = new JsInputHandler(null);
+    this.tick = 0;
+    this.syncFromGame();
   }
 
-  placing(): [number, string | null] {
-    this.ctrl.placing();
-
-    const clear = this.board.tryLineClear() as number[];
-    const clearlen = clear.length;
-
-    if (clear.length) {
-      if (this.info.line !== undefined) {
-        this.info.line += clearlen;
-      } else {
-        this.info.line = 0;
-      }
-
-      if (this.info.level !== undefined) {
-        const clamp = (value: number, min: number, max: number) => {
-          return Math.min(Math.max(value, min), max);
-        };
-        const LVUP_LINE = 10;
-
-        this.info.level = clamp(
-          Math.floor(this.info.line / LVUP_LINE) + 1,
-          1,
-          20
-        );
-      }
-
-      // combo
-      if (this.comboTick > 0) {
-        this.combo += 1;
-      }
-      this.comboTick = 150; //2.5sec
-    }
-    this.ctrl.lineClear();
-
-    //
-    let score = null;
-    let scoreNum = 0;
-    if (this.isTSpin) {
-      if (clearlen === 0) {
-        score = "TSpinZero";
-        scoreNum = CONSTANT.score["TSpinZero"];
-      } else if (clearlen === 1) {
-        score = "TSpinSingle";
-        scoreNum = CONSTANT.score["TSpinSingle"];
-      } else if (clearlen === 2) {
-        score = "TSpinDouble";
-        scoreNum = CONSTANT.score["TSpinDouble"];
-      } else if (clearlen === 3) {
-        score = "TSpinTriple";
-        scoreNum = CONSTANT.score["TSpinTriple"];
-      }
-    } else {
-      if (clearlen === 1) {
-        score = "Single";
-        scoreNum = CONSTANT.score["Single"];
-      } else if (clearlen === 2) {
-        score = "Double";
-        scoreNum = CONSTANT.score["Double"];
-      } else if (clearlen === 3) {
-        score = "Triple";
-        scoreNum = CONSTANT.score["Triple"];
-      } else if (clearlen === 4) {
-        score = "Tetris";
-        scoreNum = CONSTANT.score["Tetris"];
-      }
-    }
-
-    if (score) {
-      const lv = this.info.level ?? 1;
-      if (this.info.score !== undefined) {
-        this.info.score += scoreNum * lv + 200 * this.combo;
-      }
-    }
-
-    this.ctrl.setInfo({
-      line: this.info.line,
-      level: this.info.level,
-      score: this.info.score,
-    });
-
-    this.isCanHold = true;
-    this.isTSpin = false;
-
-    this.renderHandler.isDirty = true;
-    return [clearlen, score];
+  // 누르고 있는 키를 이번 tick 의 입력으로 바꿔서 게임을 한 tick 돌린다
+  runTick(): GameEvent[] {
+    this.tick += 1;
+    const inputs = this.inputHandler.tick(
+      levelToGravityTick(this.game.level),
+      this.config.width,
+      this.config.height
+    ) as GameInput[];
+    const events = this.game.tick(inputs) as GameEvent[];
+    this.applyEvents(events);
+    return events;
   }
 
-  spawnFromNext(): boolean {
-    const nextTetr = this.ctrl.shiftNext();
-    if (nextTetr) {
-      // console.log("spawnFromNext", nextTetr);
-      try {
-        if (!this.spawnWithGameOverCheck(nextTetr)) {
-          return false;
-        }
-
-        this.ctrl.spawn(nextTetr);
-
-        this.stepTick = 999;
-
-        this.ctrl.pushNext(this.getTetriminoFromSevenBag());
-
-        this.renderHandler.isDirty = true;
-        return true;
-      } catch (e) {
-        console.error("spawnWithGameOverCheck catch", e, "nextTetr", nextTetr);
-        return false;
-      }
-    } else {
-      return false;
+  // 다른 보드는 서버가 알려준 tick 까지 같은 입력으로 따라간다
+  advanceTo(tick: number) {
+    while (this.tick < tick && this.isBoardActive) {
+      this.runTick();
     }
   }
 
-  spawnWithGameOverCheck(tetrimino: Tetrimino): boolean {
-    const plan = this.board.trySpawnFalling(tetrimino) as TileAt[];
-    for (const { location } of plan) {
-      if (
-        (this.board.getLocation(location.x, location.y) as Tile) !== "Empty"
-      ) {
-        return false;
-      }
-    }
-
-    return true;
+  pushGarbage(empty: number[][]) {
+    const events = this.game.pushGarbage(empty) as GameEvent[];
+    this.applyEvents(events);
   }
 
-  tSpinCheck() {
-    const fallings = this.board.getFallingBlocks() as FallingBlockAt[];
-
-    if (fallings.length && fallings[0].falling.kind === "T") {
-      //
-      let isTSpin = false;
-      let is3Corner = false;
-
-      const offsets = [
-        [-1, -1],
-        [1, 1],
-        [1, -1],
-        [-1, 1],
-      ];
-
-      const centerLoc = fallings
-        .filter((f) => f.falling.id === 2)
-        .map((m) => m.location);
-
-      let placedCnt = 0;
-      if (centerLoc.length) {
-        const b = this.board;
-        for (const [x, y] of offsets) {
-          const findX = centerLoc[0].x + x;
-          const findY = centerLoc[0].y + y;
-          if (findX < b.xLen && findY < b.yLen) {
-            const tile = b.getLocation(findX, findY) as Tile;
-            if (typeof tile === "object" && "Placed" in tile) {
-              placedCnt += 1;
-            } else {
-              //
-            }
-          } else {
-            //wall
-            placedCnt += 1;
-          }
-        }
-
-        if (placedCnt >= 3) {
-          is3Corner = true;
+  applyEvents(events: GameEvent[]) {
+    if (!events.length) return;
+    for (const event of events) {
+      if ("Lock" in event) {
+        const kind = scoreKind(event.Lock.result);
+        if (kind) {
+          this.renderHandler.scoreEffect(kind, event.Lock.result.combo ?? 0);
         }
+      } else if ("GameOver" in event) {
+        this.ctrl.boardEnd();
       }
-
-      if (is3Corner) {
-        isTSpin = true;
-      }
-      return isTSpin;
-    } else {
-      return false;
     }
+    this.syncFromGame();
   }
-  showFallingHint() {
-    if (!this.isBoardActive) return;
-    this.board.removeFallingHint();
-    this.board.showFallingHint();
+
+  syncFromGame() {
+    this.tiles = this.game.getBoardWithGhost() as Board;
+    this.hold = this.game.getHold() as Tetrimino | null;
+    this.next = this.game.getNext() as Tetrimino[];
+    this.info.level = this.game.level;
+    this.info.score = this.game.score;
+    this.info.line = this.game.lines;
+    this.renderHandler.isDirty = true;
   }
 
   init(transform: Transform) {
@@ -298,36 +159,32 @@ export class TetrisBoard {
   }
 
   destroy() {
-    // this.board = new JsBoard(10, 26);
     this.renderHandler.destroy();
   }
 }
 
+// 점수 효과에 띄울 이름, 줄을 못 지운 일반 고정이면 null
+function scoreKind(result: ClearResult): string | null {
+  const lineKind = ["", "Single", "Double", "Triple", "Tetris"][result.lines];
+  if (result.tspin === "Full") {
+    return `TSpin${lineKind || "Zero"}`;
+  }
+  if (result.tspin === "Mini") {
+    return `TSpinMini${lineKind || "Zero"}`;
+  }
+  return lineKind || null;
+}
+
 interface TetrisBoardController {
-  setup(setup: BoardSetup): void;
   sync(): void;
   countdown(count: number): void;
-  boardStart(): void;
+  boardStart(config: GameConfig): void;
   boardEnd(elapsed?: number): void;
-  shiftNext(): Tetrimino | undefined;
-  pushNext(tetrimino: Tetrimino): void;
-  spawn(tetrimino: Tetrimino): void;
-  placing(): void;
-  lineClear(): void;
-  setInfo(info: InfoData): void;
-  scoreEffect(kind: string, combo: number): void;
-  step(): void;
-  moveLeft(): void;
-  moveRight(): void;
-  rotateLeft(): void;
-  rotateRight(): void;
-  softDrop(): void;
-  hardDrop(): number;
-  addHold(tetrimino: Tetrimino): void;
-  removeFalling(): void;
+  handling(tick: number, event: HandlingEvent): void;
+  ticking(tick: number): void;
   garbageQueue(gq: GarbageQueue[]): void;
-  garbageAdd(empty: number[]): void;
-  //addGargabeQueue
+  garbageAdd(empty: number[][]): void;
+  addGarbage(tick: number, empty: number[][]): void;
 }
 
 class Controller implements TetrisBoardController {
@@ -335,28 +192,6 @@ class Controller implements TetrisBoardController {
   constructor(tetrisBoard: TetrisBoard) {
     this.tb = tetrisBoard;
   }
-  setup(setup: BoardSetup): void {
-    this.tb.hold = setup.hold;
-    this.tb.next = setup.next;
-    this.tb.board = new JsBoard(10, 26);
-
-    this.tb.info.level = 1;
-    this.tb.info.score = 0;
-    this.tb.info.time = 0;
-    this.tb.info.line = 0;
-    // TODO: etc setup
-
-    this.tb.renderHandler.isDirty = true;
-
-    if (this.tb.wsSender) {
-      this.tb.wsSender.wsSend({
-        setup: {
-          next: setup.next,
-          hold: setup.hold,
-        },
-      });
-    }
-  }
   sync(): void {
     throw new Error("Method not implemented.");
   }
@@ -367,33 +202,44 @@ class Controller implements TetrisBoardController {
     this.tb.garbageQueue = gq;
     this.tb.renderHandler.garbageQueueSet(gq);
   }
-  garbageAdd(empty: number[]): void {
-    for (const e of empty) {
-      if (!this.tb.board.pushGarbageLine(e)) {
-        this.boardEnd();
-      }
-    }
-
+  // 서버가 넣으라고 보낸 방해 줄, 넣은 tick 을 서버에 알려서 같은 자리에 넣게 한다
+  garbageAdd(empty: number[][]): void {
+    if (!this.tb.isBoardActive) return;
     if (this.tb.wsSender) {
       this.tb.wsSender.wsSend({
         addGarbageQueue: {
+          tick: this.tb.tick,
           empty: empty,
         },
       });
     }
-    this.tb.renderHandler.isDirty = true;
+    this.tb.pushGarbage(empty);
+  }
+  // 다른 보드가 tick 번 돌린 뒤 넣은 방해 줄
+  addGarbage(tick: number, empty: number[][]): void {
+    this.tb.advanceTo(tick);
+    if (!this.tb.isBoardActive) return;
+    this.tb.pushGarbage(empty);
+  }
+  handling(tick: number, event: HandlingEvent): void {
+    this.tb.advanceTo(tick);
+    this.tb.inputHandler.handle(event);
+  }
+  ticking(tick: number): void {
+    this.tb.advanceTo(tick);
   }
 
-  boardStart(): void {
+  boardStart(config: GameConfig): void {
     console.log("boardStart");
+    this.tb.start(config);
     this.tb.timer.reset();
     this.tb.renderHandler.removeEndCover();
     this.tb.isAddEndCover = false;
     this.tb.renderHandler.removeGarbageQueue();
     this.tb.timer.on();
     this.tb.isBoardActive = true;
-    // this.tb.spawnFromNext();
   }
+  // 탑 아웃은 서버도 같은 tick 에 판정하니 따로 알리지 않는다
   boardEnd(elapsed?: number): void {
     console.log("gameEnd");
     this.tb.renderHandler.removeEndCover();
@@ -404,199 +250,6 @@ class Controller implements TetrisBoardController {
       this.tb.info.time = elapsed;
     }
     this.tb.isBoardActive = false;
-    if (this.tb.wsSender) {
-      this.tb.wsSender.wsSend("boardEnd");
-    }
-  }
-  shiftNext(): Tetrimino | undefined {
-    const next = this.tb.next.shift();
-    this.tb.renderHandler.isDirty = true;
-
-    if (this.tb.wsSender) {
-      this.tb.wsSender.wsSend({
-        shiftNext: {
-          next: next,
-        },
-      });
-    }
-    return next;
-  }
-  pushNext(tetrimino: Tetrimino): void {
-    this.tb.next.push(tetrimino);
-
-    this.tb.renderHandler.isDirty = true;
-
-    if (this.tb.wsSender) {
-      this.tb.wsSender.wsSend({
-        pushNext: {
-          next: tetrimino,
-        },
-      });
-    }
-  }
-  spawn(tetrimino: Tetrimino): void {
-    const b = this.tb.board;
-    const plan = b.trySpawnFalling(tetrimino);
-    b.applySpawnFalling(plan);
-
-    this.tb.renderHandler.isDirty = true;
-
-    if (this.tb.wsSender) {
-      this.tb.wsSender.wsSend({
-        spawn: {
-          spawn: tetrimino,
-        },
-      });
-    }
-  }
-  placing(): void {
-    const b = this.tb.board;
-    b.placeFalling();
-
-    this.tb.renderHandler.isDirty = true;
-
-    if (this.tb.wsSender) {
-      this.tb.wsSender.wsSend("placing");
-    }
-  }
-  lineClear(): void {
-    const b = this.tb.board;
-    const plan = b.tryLineClear();
-    b.applyLineClear(plan);
-
-    this.tb.renderHandler.isDirty = true;
-
-    if (this.tb.wsSender) {
-      this.tb.wsSender.wsSend("lineClear");
-    }
-  }
-  setInfo(info: InfoData): void {
-    if (info.level) {
-      this.tb.info.level = info.level;
-    }
-    if (info.score) {
-      this.tb.info.score = info.score;
-    }
-    if (info.line) {
-      this.tb.info.line = info.line;
-    }
-    if (this.tb.wsSender) {
-      this.tb.wsSender.wsSend({
-        setInfo: {
-          line: info.line,
-          score: info.score,
-          level: info.level,
-        },
-      });
-    }
-  }
-  scoreEffect(kind: string, combo: number): void {
-    this.tb.renderHandler.scoreEffect(kind, combo);
-    if (this.tb.wsSender) {
-      this.tb.wsSender.wsSend({
-        scoreEffect: {
-          kind,
-          combo,
-        },
-      });
-    }
-  }
-  step(): void {
-    const b = this.tb.board;
-    const plan = b.tryStep();
-    b.applyStep(plan);
-
-    this.tb.renderHandler.isDirty = true;
-
-    if (this.tb.wsSender) {
-      this.tb.wsSender.wsSend("step");
-    }
-  }
-  moveLeft(): void {
-    const b = this.tb.board;
-    const plan = b.tryMoveFalling("Left");
-    b.applyMoveFalling(plan);
-
-    this.tb.renderHandler.isDirty = true;
-    if (this.tb.wsSender) {
-      this.tb.wsSender.wsSend("moveLeft");
-    }
-  }
-  moveRight(): void {
-    const b = this.tb.board;
-    const plan = b.tryMoveFalling("Right");
-    b.applyMoveFalling(plan);
-
-    this.tb.renderHandler.isDirty = true;
-
-    if (this.tb.wsSender) {
-      this.tb.wsSender.wsSend("moveRight");
-    }
-  }
-  rotateLeft(): void {
-    const b = this.tb.board;
-    const plan = b.tryRotateFalling("Left");
-    b.applyRotateFalling(plan);
-
-    this.tb.renderHandler.isDirty = true;
-
-    if (this.tb.wsSender) {
-      this.tb.wsSender.wsSend("rotateLeft");
-    }
-  }
-  rotateRight(): void {
-    const b = this.tb.board;
-    const plan = b.tryRotateFalling("Right");
-    b.applyRotateFalling(plan);
-
-    this.tb.renderHandler.isDirty = true;
-
-    if (this.tb.wsSender) {
-      this.tb.wsSender.wsSend("rotateRight");
-    }
-  }
-  softDrop(): void {
-    const b = this.tb.board;
-    try {
-      const plan = b.tryStep();
-      b.applyStep(plan);
-      this.tb.renderHandler.isDirty = true;
-
-      if (this.tb.wsSender) {
-        this.tb.wsSender.wsSend("softDrop");
-      }
-    } catch (e) {
-      console.error("softDrop", e);
-    }
-  }
-  hardDrop(): number {
-    const dropcnt = this.tb.board.hardDrop();
-    this.tb.renderHandler.isDirty = true;
-    if (this.tb.wsSender) {
-      this.tb.wsSender.wsSend("hardDrop");
-    }
-    return dropcnt;
-  }
-  addHold(tetrimino: Tetrimino): void {
-    this.tb.hold = tetrimino;
-
-    this.tb.renderHandler.isDirty = true;
-
-    if (this.tb.wsSender) {
-      this.tb.wsSender.wsSend({
-        addHold: {
-          hold: tetrimino,
-        },
-      });
-    }
-  }
-  removeFalling(): void {
-    const b = this.tb.board;
-    b.removeFallingBlocks();
-    this.tb.renderHandler.isDirty = true;
-    if (this.tb.wsSender) {
-      this.tb.wsSender.wsSend("removeFalling");
-    }
   }
 }
 
@@ -633,7 +286,9 @@ class Timer {
   }
 }
 
-// ticking은 solo ticker와 같음
+// 입력이 없어도 서버와 다른 보드가 따라오도록 몇 tick 마다 tick 을 알린다
+const TICKING_INTERVAL = 10;
+
 export class MultiTickerHandler implements TickerDelegation {
   tb: TetrisBoard;
   constructor(tetrisBoard: TetrisBoard) {
@@ -648,53 +303,10 @@ export class MultiTickerHandler implements TickerDelegation {
   ticking(): void {
     if (!this.tb.isBoardActive) return;
 
-    if (!this.tb.board.getFallingBlocks().length) {
-      const isSuccess = this.tb.spawnFromNext();
-      if (!isSuccess) {
-        this.tb.ctrl.boardEnd();
-      }
-    }
-
-    this.tb.tick += 1;
-    this.tb.stepTick += 1;
-
-    // combo tick
-    if (this.tb.comboTick > 0) {
-      this.tb.comboTick -= 1;
-    } else {
-      this.tb.comboTick = 0;
-      this.tb.combo = 0;
-    }
-
-    if (this.tb.isPlacingDelay) {
-      try {
-        this.tb.board.tryStep();
-      } catch {
-        this.tb.placingDelayTick += 1;
-        if (this.tb.placingDelayTick >= 30) {
-          this.tb.isPlacingDelay = false;
-          const [, score] = this.tb.placing();
-
-          if (score) {
-            this.tb.ctrl.scoreEffect(score, this.tb.combo);
-          }
-        }
-      }
-    }
-
-    const lv = this.tb.info.level ?? 1;
-    if (this.tb.stepTick >= CONSTANT.levelGravityTick[lv > 20 ? 20 : lv]) {
-      this.tb.stepTick = 0;
-
-      try {
-        this.tb.ctrl.step();
-      } catch {
-        if (!this.tb.isPlacingDelay) {
-          this.tb.isPlacingDelay = true;
-          this.tb.placingDelayTick = 0;
-          this.tb.placingResetCnt = 15;
-        }
-      }
+    const events = this.tb.runTick();
+    const isLocked = events.some((event) => "Lock" in event);
+    if (isLocked || this.tb.tick % TICKING_INTERVAL === 0) {
+      this.tb.wsSender?.wsSend({ ticking: { tick: this.tb.tick } });
     }
   }
 }
@@ -708,84 +320,15 @@ export class SoloTickerHandler implements TickerDelegation {
     // throw new Error("Method not implemented.");
   }
   initTicking(): void {
-    const next = Array(5)
-      .fill(null)
-      .map(() => {
-        return this.tb.getTetriminoFromSevenBag();
-      });
-
-    this.tb.ctrl.setup({
-      hold: null,
-      next: next,
+    this.tb.ctrl.boardStart({
+      ...(defaultGameConfig() as GameConfig),
+      seed: BigInt(Math.floor(Math.random() * 2 ** 32)),
     });
-
-    this.tb.ctrl.boardStart();
-    this.tb.stepTick = 999;
-    // console.log("3");
-    // setTimeout(() => {
-    //   console.log("2");
-    //   setTimeout(() => {
-    //     console.log("1");
-    //     setTimeout(() => {
-    //       this.tb.ctrl.boardStart();
-
-    //       this.tb.stepTick = 999;
-    //     }, 1000);
-    //   }, 1000);
-    // }, 1000);
   }
 
   ticking() {
     if (!this.tb.isBoardActive) return;
-
-    if (!this.tb.board.getFallingBlocks().length) {
-      const isSuccess = this.tb.spawnFromNext();
-      if (!isSuccess) {
-        this.tb.ctrl.boardEnd();
-      }
-    }
-
-    this.tb.tick += 1;
-    this.tb.stepTick += 1;
-
-    // combo tick
-    if (this.tb.comboTick > 0) {
-      this.tb.comboTick -= 1;
-    } else {
-      this.tb.comboTick = 0;
-      this.tb.combo = 0;
-    }
-
-    if (this.tb.isPlacingDelay) {
-      try {
-        this.tb.board.tryStep();
-      } catch {
-        this.tb.placingDelayTick += 1;
-        if (this.tb.placingDelayTick >= 30) {
-          this.tb.isPlacingDelay = false;
-          const [, score] = this.tb.placing();
-
-          if (score) {
-            this.tb.ctrl.scoreEffect(score, this.tb.combo);
-          }
-        }
-      }
-    }
-
-    const lv = this.tb.info.level ?? 1;
-    if (this.tb.stepTick >= CONSTANT.levelGravityTick[lv > 20 ? 20 : lv]) {
-      this.tb.stepTick = 0;
-
-      try {
-        this.tb.ctrl.step();
-      } catch {
-        if (!this.tb.isPlacingDelay) {
-          this.tb.isPlacingDelay = true;
-          this.tb.placingDelayTick = 0;
-          this.tb.placingResetCnt = 15;
-        }
-      }
-    }
+    this.tb.runTick();
   }
 }
 
@@ -794,157 +337,20 @@ export class ActionHandler implements ActionDelegation {
   constructor(tetrisBoard: TetrisBoard) {
     this.tb = tetrisBoard;
   }
-  actMoveLeft(): void {
-    if (!this.tb.isBoardActive) return;
-    const fallings = this.tb.board.getFallingBlocks() as FallingBlockAt[];
-    if (fallings.length === 0) return;
-    try {
-      this.tb.ctrl.moveLeft();
-
-      if (this.tb.placingResetCnt > 0) {
-        this.tb.placingDelayTick = 0;
-        this.tb.placingResetCnt -= 1;
-      }
-      this.tb.renderHandler.isDirty = true;
-    } catch (e) {
-      if (e instanceof Error) {
-        // console.error(e.message); // OK
-      } else {
-        // console.error("Unknown error", e);
-      }
-    }
-  }
-  actMoveRight(): void {
-    if (!this.tb.isBoardActive) return;
-    const fallings = this.tb.board.getFallingBlocks() as FallingBlockAt[];
-    if (fallings.length === 0) return;
-    try {
-      this.tb.ctrl.moveRight();
-
-      if (this.tb.placingResetCnt > 0) {
-        this.tb.placingDelayTick = 0;
-        this.tb.placingResetCnt -= 1;
-      }
-
-      this.tb.renderHandler.isDirty = true;
-    } catch (e) {
-      if (e instanceof Error) {
-        // console.error(e.message); // OK
-      } else {
-        // console.error("Unknown error", e);
-      }
-    }
-  }
-  actRotateLeft(): void {
-    if (!this.tb.isBoardActive) return;
-    const fallings = this.tb.board.getFallingBlocks() as FallingBlockAt[];
-    if (fallings.length === 0) return;
-    try {
-      this.tb.ctrl.rotateLeft();
-
-      this.tb.isTSpin = this.tb.tSpinCheck();
-
-      if (this.tb.placingResetCnt > 0) {
-        this.tb.placingDelayTick = 0;
-        this.tb.placingResetCnt -= 1;
-      }
-
-      this.tb.renderHandler.isDirty = true;
-    } catch (e) {
-      if (e instanceof Error) {
-        // console.error(e.message); // OK
-      } else {
-        // console.error("Unknown error", e);
-      }
-    }
-  }
-
-  actRotateRight(): void {
+  // 이미 돌린 tick 을 같이 보내서 서버가 같은 tick 에 키를 누르게 한다
+  press(input: GameInput): void {
     if (!this.tb.isBoardActive) return;
-    const fallings = this.tb.board.getFallingBlocks() as FallingBlockAt[];
-    if (fallings.length === 0) return;
-    try {
-      this.tb.ctrl.rotateRight();
-
-      this.tb.isTSpin = this.tb.tSpinCheck();
-
-      if (this.tb.placingResetCnt > 0) {
-        this.tb.placingDelayTick = 0;
-        this.tb.placingResetCnt -= 1;
-      }
-
-      this.tb.renderHandler.isDirty = true;
-    } catch (e) {
-      if (e instanceof Error) {
-        // console.error(e.message); // OK
-      } else {
-        // console.error("Unknown error", e);
-      }
-    }
+    this.handle({ Press: input });
   }
-  actSoftDrop(): void {
-    if (!this.tb.isBoardActive) return;
-    const fallings = this.tb.board.getFallingBlocks() as FallingBlockAt[];
-    if (fallings.length === 0) return;
-    try {
-      this.tb.ctrl.step();
-
-      if (this.tb.info.score !== undefined) {
-        this.tb.info.score += CONSTANT.score.SoftDrop;
-      }
-    } catch {
-      // console.error("actSoftDrop", e);
-    }
-
-    //TODO setinfo
+  // 게임이 끝나도 눌린 키는 풀어둔다
+  release(input: GameInput): void {
+    this.handle({ Release: input });
   }
-  actHardDrop(): void {
-    if (!this.tb.isBoardActive) return;
-    const fallings = this.tb.board.getFallingBlocks() as FallingBlockAt[];
-    // console.log(fallings.length);
-    if (fallings.length === 0) return;
-    const dropcnt = this.tb.ctrl.hardDrop();
-
-    if (this.tb.info.score !== undefined) {
-      this.tb.info.score += CONSTANT.score.HardDrop * dropcnt;
-    }
-
-    this.tb.isPlacingDelay = false;
-    this.tb.renderHandler.isDirty = true;
-
-    const [, score] = this.tb.placing();
-    if (score) {
-      this.tb.ctrl.scoreEffect(score, this.tb.combo);
-    }
-  }
-  actHold(): void {
-    if (!this.tb.isBoardActive) return;
-    const fallings = this.tb.board.getFallingBlocks() as FallingBlockAt[];
-    if (fallings.length === 0) return;
-    if (!this.tb.isCanHold) {
-      return;
-    }
-    this.tb.isCanHold = false;
-    const hold = this.tb.hold;
-    if (hold) {
-      const fallings = this.tb.board.getFallingBlocks() as FallingBlockAt[];
-      const firstFalling = fallings.shift();
-      if (firstFalling) {
-        this.tb.ctrl.addHold(firstFalling.falling.kind);
-      }
-      this.tb.ctrl.removeFalling();
-
-      this.tb.ctrl.spawn(hold);
-    } else {
-      const fallings = this.tb.board.getFallingBlocks() as FallingBlockAt[];
-      const firstFalling = fallings.shift();
-      if (firstFalling) {
-        this.tb.ctrl.addHold(firstFalling.falling.kind);
-        this.tb.ctrl.removeFalling();
-      }
+  handle(event: HandlingEvent): void {
+    this.tb.inputHandler.handle(event);
+    if (this.tb.wsSender) {
+      this.tb.wsSender.wsSend({ handling: { tick: this.tb.tick, event } });
     }
-
-    this.tb.isPlacingDelay = false;
   }
 }
 
@@ -1091,9 +497,8 @@ class RenderHandler {
       }
     }
 
-    for (const [lineIdx, line] of (
-      this.tetrisBoard.board.getBoard() as Board
-    ).entries()) {
+    // 고스트는 보드에 쓰지 않고 화면용 칸에만 겹친다
+    for (const [lineIdx, line] of this.tetrisBoard.tiles.entries()) {
       for (const [tileIdx, tile] of line.entries()) {
         if (tile === "Empty") {
           //
@@ -1449,7 +854,6 @@ class RenderHandler {
 
   frame() {
     if (this.isDirty) {
-      this.tetrisBoard.showFallingHint();
       this.update();
       this.isDirty = false;
     }
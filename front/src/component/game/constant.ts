@@ -1,17 +1,4 @@
 export const CONSTANT = {
-  score: {
-    Single: 100,
-    Double: 300,
-    Triple: 500,
-    Tetris: 800,
-    TSpinZero: 400,
-    TSpinSingle: 800,
-    TSpinDouble: 1200,
-    TSpinTriple: 1600,
-    SoftDrop: 1,
-    HardDrop: 2,
-    Combo: 200,
-  },
   attckLine: {
     Double: 1,
     Triple: 2,
@@ -20,33 +7,6 @@ export const CONSTANT = {
     TSpinDouble: 4,
     TSpinTriple: 6,
   },
-  rule: {
-    placingResetCnt: 15,
-    placingDelayTick: 30,
-  },
-  levelGravityTick: [
-    48, // index 0: dummy
-    48, // level 1
-    43, // level 2
-    38, // level 3
-    33, // level 4
-    28, // level 5
-    23, // level 6
-    18, // level 7
-    13, // level 8
-    8, // level 9
-    6, // level 10
-    5, // level 11
-    5, // level 12
-    5, // level 13
-    4, // level 14
-    4, // level 15
-    3, // level 16
-    3, // level 17
-    2, // level 18
-    2, // level 19
-    1, // level 20
-  ],
   gfx: {
     instancedMeshReserve: 5000,
     url: {
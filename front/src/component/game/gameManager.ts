@@ -112,7 +112,6 @@ export class GameManager {
   }
 
   frame(delta: number) {
-    this.input.frame(delta);
     this.gameLoop.gameLoopUpdate(delta);
 
     for (const [, board] of Object.entries(this.boards)) {
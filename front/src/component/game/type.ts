@@ -1,4 +1,4 @@
-import type { Board, Tetrimino } from "tetris-lib/bindings";
+import type { Board, GameInput, Tetrimino } from "tetris-lib/bindings";
 import * as THREE from "three";
 
 export type BoardId = string;
@@ -37,13 +37,8 @@ export type InfoData = {
 };
 
 export interface ActionDelegation {
-  actMoveLeft(): void;
-  actMoveRight(): void;
-  actRotateLeft(): void;
-  actRotateRight(): void;
-  actSoftDrop(): void;
-  actHardDrop(): void;
-  actHold(): void;
+  press(input: GameInput): void;
+  release(input: GameInput): void;
 }
 
 export interface WsSendDelegation {
@@ -49,110 +49,36 @@ export class WsReceiver {
         if (
           typeof action === "object" &&
           action !== null &&
-          "setup" in action
+          "handling" in action
         ) {
+          // 내 보드는 이미 눌렀다
           if (k === this.gm.mainBoardId) continue;
-          const next = action["setup"].next;
-          this.gm.boards[k]?.ctrl.setup({ next, hold: null });
+          const { tick, event } = action["handling"];
+          this.gm.boards[k]?.ctrl.handling(tick, event);
         } else if (
           typeof action === "object" &&
           action !== null &&
-          "spawn" in action
+          "ticking" in action
         ) {
           if (k === this.gm.mainBoardId) continue;
-          const spawn = action["spawn"].spawn;
-          this.gm.boards[k]?.ctrl.spawn(spawn);
-        } else if (typeof action === "string" && action === "moveLeft") {
-          if (k === this.gm.mainBoardId) continue;
-          this.gm.boards[k]?.ctrl.moveLeft();
-        } else if (typeof action === "string" && action === "moveRight") {
-          if (k === this.gm.mainBoardId) continue;
-          this.gm.boards[k]?.ctrl.moveRight();
-        } else if (typeof action === "string" && action === "rotateLeft") {
-          if (k === this.gm.mainBoardId) continue;
-          this.gm.boards[k]?.ctrl.rotateLeft();
-        } else if (typeof action === "string" && action === "rotateRight") {
-          if (k === this.gm.mainBoardId) continue;
-          this.gm.boards[k]?.ctrl.rotateRight();
-        } else if (typeof action === "string" && action === "placing") {
-          if (k === this.gm.mainBoardId) continue;
-          this.gm.boards[k]?.ctrl.placing();
-        } else if (typeof action === "string" && action === "lineClear") {
-          if (k === this.gm.mainBoardId) continue;
-          this.gm.boards[k]?.ctrl.lineClear();
-        } else if (typeof action === "string" && action === "step") {
-          if (k === this.gm.mainBoardId) continue;
-          this.gm.boards[k]?.ctrl.step();
-        } else if (typeof action === "string" && action === "softDrop") {
-          if (k === this.gm.mainBoardId) continue;
-          this.gm.boards[k]?.ctrl.softDrop();
-        } else if (typeof action === "string" && action === "hardDrop") {
-          if (k === this.gm.mainBoardId) continue;
-          this.gm.boards[k]?.ctrl.hardDrop();
-        } else if (typeof action === "string" && action === "removeFalling") {
-          if (k === this.gm.mainBoardId) continue;
-          this.gm.boards[k]?.ctrl.removeFalling();
-        } else if (
-          typeof action === "object" &&
-          action !== null &&
-          "pushNext" in action
-        ) {
-          if (k === this.gm.mainBoardId) continue;
-          const t = action["pushNext"].next;
-          this.gm.boards[k]?.ctrl.pushNext(t);
+          const tick = action["ticking"].tick;
+          this.gm.boards[k]?.ctrl.ticking(tick);
         } else if (
           typeof action === "object" &&
           action !== null &&
-          "shiftNext" in action
+          "addGarbage" in action
         ) {
           if (k === this.gm.mainBoardId) continue;
-          // const next = action["shiftNext"].next;
-          this.gm.boards[k]?.ctrl.shiftNext();
+          const { tick, empty } = action["addGarbage"];
+          this.gm.boards[k]?.ctrl.addGarbage(tick, empty);
         } else if (
           typeof action === "object" &&
           action !== null &&
-          "addHold" in action
+          "boardStart" in action
         ) {
-          if (k === this.gm.mainBoardId) continue;
-          const hold = action["addHold"].hold;
-
-          this.gm.boards[k]?.ctrl.addHold(hold);
-        } else if (
-          typeof action === "object" &&
-          action !== null &&
-          "setInfo" in action
-        ) {
-          if (k === this.gm.mainBoardId) continue;
-          const line = action["setInfo"].line;
-          const score = action["setInfo"].score;
-          const level = action["setInfo"].level;
-          this.gm.boards[k]?.ctrl.setInfo({
-            level,
-            score,
-            line,
-          });
-        } else if (
-          typeof action === "object" &&
-          action !== null &&
-          "scoreEffect" in action
-        ) {
-          if (k === this.gm.mainBoardId) continue;
-          const kind = action["scoreEffect"].kind;
-          const combo = action["scoreEffect"].combo;
-          this.gm.boards[k]?.ctrl.scoreEffect(kind, combo);
-        } else if (typeof action === "string" && action === "boardStart") {
-          // if (k !== this.gm.mainBoardId) continue;
-          const next = Array(5)
-            .fill(null)
-            .map(() => {
-              return this.gm.boards[k]!.getTetriminoFromSevenBag();
-            });
-
-          this.gm.boards[k]?.ctrl.setup({
-            hold: null,
-            next: next,
-          });
-          this.gm.boards[k]?.ctrl.boardStart();
+          // 모든 보드가 서버와 같은 설정으로 게임을 만든다
+          const config = action["boardStart"].config;
+          this.gm.boards[k]?.ctrl.boardStart(config);
         } else if (
           typeof action === "object" &&
           action !== null &&
@@ -176,7 +102,8 @@ export class WsReceiver {
           action !== null &&
           "doGarbageAdd" in action
         ) {
-          // if (k !== this.gm.mainBoardId) continue;
+          // 다른 보드는 그 보드가 넣은 tick 을 addGarbage 로 받아서 넣는다
+          if (k !== this.gm.mainBoardId) continue;
           const empty = action["doGarbageAdd"].empty;
           console.log("doGarbageAdd", empty);
           this.gm.boards[k]?.ctrl.garbageAdd(empty);
@@ -5,13 +5,9 @@ import * as THREE from "three";
 import { FontLoader } from "three/examples/jsm/loaders/FontLoader.js";
 import { GameManager } from "../game/gameManager";
 import { CONSTANT } from "../game/constant";
-import type {
-  BoardId,
-  BoardSetup,
-  BoardSyncData,
-  Transform,
-} from "../game/type";
+import type { BoardId, BoardSyncData, Transform } from "../game/type";
 import type { TetrisBoard } from "../game/board";
+import type { GameConfig } from "tetris-lib/bindings";
 
 export type ClientTetrisController = {
   createMulitPlayerBoard: (boardId: BoardId, nickName: string) => void;
@@ -26,9 +22,8 @@ export type ClientTetrisController = {
   boardTimerReset: (boardId: BoardId) => void;
   gameStart: () => void;
 
-  spawnFromNext: (boardId: BoardId) => void;
-  step: (boardId: BoardId) => void;
-  setup: (boardId: BoardId, setup: BoardSetup) => void;
+  boardStart: (boardId: BoardId, config: GameConfig) => void;
+  boardTick: (boardId: BoardId) => void;
   onWsMessage: (msg: string) => void;
   setWsSenderGameId: (gameId: string | undefined) => void;
   gameSync: (gameSyncData: Record<string, BoardSyncData>) => void;
@@ -82,15 +77,11 @@ export const ClientTetris = forwardRef<
       gameManager.current.gameLoop.gameLoopStart();
     },
 
-    spawnFromNext: function (boardId: BoardId): void {
-      gameManager.current.boards[boardId]?.spawnFromNext();
+    boardStart(boardId: BoardId, config: GameConfig) {
+      gameManager.current.boards[boardId]?.ctrl.boardStart(config);
     },
-
-    step: function (boardId: BoardId): void {
-      gameManager.current.boards[boardId]?.ctrl.step();
-    },
-    setup(boardId: BoardId, setup: BoardSetup) {
-      gameManager.current.boards[boardId]?.ctrl.setup(setup);
+    boardTick: function (boardId: BoardId): void {
+      gameManager.current.boards[boardId]?.runTick();
     },
     onWsMessage(msg) {
       gameManager.current.onWsMessage(msg);
@@ -33,6 +33,7 @@ import { format } from "date-fns";
 import { useGameStore } from "../store/useGameStore";
 import { useWsUserStore } from "../store/useWsUserStore";
 import { ReadyState } from "react-use-websocket";
+import type { GarbageMode } from "tetris-lib/bindings";
 
 const RoomPage = () => {
   const { roomId } = useParams();
@@ -432,6 +433,17 @@ const HUDInfoMenu = () => {
   );
 };
 
+// 방해 줄 모드 선택지
+const GARBAGE_MODES: Record<string, GarbageMode> = {
+  Clean: "Clean",
+  Messy: { Messy: { change_rate: 0.3 } },
+  Cheese: "Cheese",
+  MultiHole: { MultiHole: { holes: 2 } },
+  Solid: "Solid",
+};
+const garbageModeKey = (mode: GarbageMode | null) =>
+  mode === null ? "Clean" : typeof mode === "string" ? mode : Object.keys(mode)[0];
+
 const HUDRoomInfo = () => {
   const { roomId } = useParams();
   const send = useWsStore((s) => s.send);
@@ -439,6 +451,7 @@ const HUDRoomInfo = () => {
   const roomUsers = useRoomStore((s) => s.users);
   const roomStatus = useRoomStore((s) => s.roomStatus);
   const roomGameType = useRoomStore((s) => s.gameType);
+  const roomGarbageMode = useRoomStore((s) => s.garbageMode);
   const hostUser = useRoomStore((s) => s.hostUser);
   const myWsId = useWsUserStore((s) => s.wsId);
   const isHost = hostUser?.wsId === myWsId;
@@ -471,6 +484,18 @@ const HUDRoomInfo = () => {
       send(JSON.stringify(obj));
     }
   };
+  const handleGarbageModeChange = (key: string) => {
+    if (roomId) {
+      const obj = {
+        type: "roomGarbageModeChange",
+        data: {
+          roomId,
+          garbageMode: GARBAGE_MODES[key],
+        },
+      };
+      send(JSON.stringify(obj));
+    }
+  };
   return (
     <Flex direction="column">
       <Text>방제목: {roomName}</Text>
@@ -495,6 +520,26 @@ const HUDRoomInfo = () => {
           </Select.Content>
         </Select.Root>
       )}
+      <Text>방해줄: {garbageModeKey(roomGarbageMode)}</Text>
+      {isHost && (
+        <Select.Root
+          defaultValue={garbageModeKey(roomGarbageMode)}
+          onValueChange={handleGarbageModeChange}
+          disabled={roomStatus !== "Waiting"}
+        >
+          <Select.Trigger />
+          <Select.Content>
+            <Select.Group>
+              <Select.Label>Garbage</Select.Label>
+              {Object.keys(GARBAGE_MODES).map((key) => (
+                <Select.Item key={key} value={key}>
+                  {key}
+                </Select.Item>
+              ))}
+            </Select.Group>
+          </Select.Content>
+        </Select.Root>
+      )}
       {isHost && roomStatus === "Waiting" ? (
         <Button
           variant="classic"
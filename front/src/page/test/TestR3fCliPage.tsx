@@ -12,6 +12,8 @@ import {
   type ClientTetrisController,
 } from "../../component/r3f/ClientTetris";
 import { RaTimer } from "../../component/game/util";
+import { defaultGameConfig } from "tetris-lib";
+import type { GameConfig } from "tetris-lib/bindings";
 
 const LazyPerf = React.lazy(() => import("../../component/r3f/Perf"));
 
@@ -112,10 +114,10 @@ const ThreeComponent = () => {
     gameStart: button(() => {
       ref.current?.gameStart();
     }),
-    boardSetup: button((get) => {
-      ref.current?.setup(get("boardId"), {
-        hold: "T",
-        next: ["I", "J", "L", "O", "S"],
+    boardStart: button((get) => {
+      ref.current?.boardStart(get("boardId"), {
+        ...(defaultGameConfig() as GameConfig),
+        initial_hold: "T",
       });
     }),
 
@@ -126,11 +128,8 @@ const ThreeComponent = () => {
         scale: [1.5, 1.5, 1.5],
       });
     }),
-    spawnFromNext: button((get) => {
-      ref.current?.spawnFromNext(get("boardId"));
-    }),
-    step: button((get) => {
-      ref.current?.step(get("boardId"));
+    tick: button((get) => {
+      ref.current?.boardTick(get("boardId"));
     }),
     testRaTimeout: button(() => {
       heavyTask(500);
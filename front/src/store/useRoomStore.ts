@@ -1,4 +1,5 @@
 import { create } from "zustand"
+import type { GarbageMode } from "tetris-lib/bindings";
 
 type RoomState = {
   isRoomEnterd: boolean,
@@ -10,6 +11,7 @@ type RoomState = {
   chats: RoomChat[],
   games: string[],
   gameType: string | null,
+  garbageMode: GarbageMode | null,
   gameResult: GameResult[],
   isGameResultOpen: boolean,
   gameStartTimer: number,
@@ -37,7 +39,8 @@ export type RoomInfo = {
   roomUsers: RoomUser[]
   roomStatus: string,
   games: string[],
-  gameType: string
+  gameType: string,
+  garbageMode: GarbageMode,
 }
 
 export type RoomChat = {
@@ -72,6 +75,7 @@ export const useRoomStore = create<RoomState>(
       roomStatus: null,
       games: [],
       gameType: null,
+      garbageMode: null,
       gameResult: [],
       isGameResultOpen: false,
       gameStartTimer: 0,
@@ -108,6 +112,7 @@ export const useRoomStore = create<RoomState>(
           roomStatus: info.roomStatus,
           games: info.games,
           gameType: info.gameType,
+          garbageMode: info.garbageMode,
         })
       },
       clear: () => {